@@ -0,0 +1,27 @@
+//! Run with `cargo +nightly fuzz run replace_all_ac`.
+#![no_main]
+
+use aho_corasick::{AhoCorasickBuilder, MatchKind};
+use libfuzzer_sys::fuzz_target;
+use slugify_rs::special::{apply_pre_translations, replace_all_ac};
+
+// Input layout: `patterns\0haystack`, patterns separated by `\x01`. The
+// patterns are raw bytes so they may cut through multi-byte characters.
+fuzz_target!(|data: &[u8]| {
+    let (pats, rest) = match data.iter().position(|&b| b == 0) {
+        Some(i) => (&data[..i], &data[i + 1..]),
+        None => return,
+    };
+    let Ok(haystack) = std::str::from_utf8(rest) else {
+        return;
+    };
+    let pats: Vec<&[u8]> = pats.split(|&b| b == 1).collect();
+    for kind in [MatchKind::Standard, MatchKind::LeftmostFirst, MatchKind::LeftmostLongest] {
+        let Ok(ac) = AhoCorasickBuilder::new().match_kind(kind).build(&pats) else {
+            return;
+        };
+        // `String` output guarantees valid UTF-8; this only has to not panic
+        let _ = replace_all_ac(haystack, &ac, |i| if i % 2 == 0 { "é" } else { "" });
+    }
+    let _ = apply_pre_translations(haystack);
+});
@@ -0,0 +1,215 @@
+/* C interface of slugify-rs (cargo feature `ffi`).
+ *
+ * Generated by cbindgen from src/lib_ffi.rs; do not edit by hand.
+ *
+ * Either configure an options handle once and slugify into caller-provided
+ * buffers (slugify_rs_*), or call slugify_c with a SlugifyOptionsC and
+ * release the returned string with slugify_free.
+ * A handle must not be used from several threads at the same time.
+ */
+
+#ifndef SLUGIFY_RS_H
+#define SLUGIFY_RS_H
+
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+
+/**
+ * A pointer argument was null.
+ */
+#define SLUGIFY_RS_ERR_NULL -1
+
+/**
+ * An input string was not valid UTF-8.
+ */
+#define SLUGIFY_RS_ERR_UTF8 -2
+
+/**
+ * The configured options failed to build (e.g. an invalid regex).
+ */
+#define SLUGIFY_RS_ERR_OPTIONS -3
+
+/**
+ * Opaque options handle. Setters store the configuration; the options are
+ * built into a `Slugifier` on the first slug call after a change.
+ */
+typedef struct SlugifyRsOptions SlugifyRsOptions;
+
+/**
+ * Options for `slugify_c`, passed by pointer. Start from
+ * `slugify_options_default()` and change the fields you need.
+ */
+typedef struct {
+  /**
+   * NUL-terminated separator; null means "-".
+   */
+  const char *separator;
+  /**
+   * Maximum slug length, 0 for no limit.
+   */
+  size_t max_length;
+  bool word_boundary;
+  bool save_order;
+  bool lowercase;
+  bool allow_unicode;
+} SlugifyOptionsC;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/**
+ * New options handle with the builder defaults. Free it with
+ * `slugify_rs_options_free`.
+ */
+SlugifyRsOptions *slugify_rs_options_new(void);
+
+/**
+ * Free a handle from `slugify_rs_options_new`; null is ignored.
+ *
+ * # Safety
+ * `opts` must be null or a handle that has not been freed yet.
+ */
+void slugify_rs_options_free(SlugifyRsOptions *opts);
+
+/**
+ * Set the separator. Returns 0 or a negative `SLUGIFY_RS_ERR_*` code.
+ *
+ * # Safety
+ * `opts` must be a live handle and `separator` a NUL-terminated string.
+ */
+ptrdiff_t slugify_rs_options_set_separator(SlugifyRsOptions *opts, const char *separator);
+
+/**
+ * Set the maximum slug length (0 for no limit).
+ *
+ * # Safety
+ * `opts` must be a live handle.
+ */
+ptrdiff_t slugify_rs_options_set_max_length(SlugifyRsOptions *opts, size_t max_length);
+
+/**
+ * Truncate on word boundaries.
+ *
+ * # Safety
+ * `opts` must be a live handle.
+ */
+ptrdiff_t slugify_rs_options_set_word_boundary(SlugifyRsOptions *opts, bool enabled);
+
+/**
+ * Keep word order when truncating.
+ *
+ * # Safety
+ * `opts` must be a live handle.
+ */
+ptrdiff_t slugify_rs_options_set_save_order(SlugifyRsOptions *opts, bool enabled);
+
+/**
+ * Lowercase the slug (default on).
+ *
+ * # Safety
+ * `opts` must be a live handle.
+ */
+ptrdiff_t slugify_rs_options_set_lowercase(SlugifyRsOptions *opts, bool enabled);
+
+/**
+ * Keep Unicode letters instead of transliterating to ASCII.
+ *
+ * # Safety
+ * `opts` must be a live handle.
+ */
+ptrdiff_t slugify_rs_options_set_allow_unicode(SlugifyRsOptions *opts, bool enabled);
+
+/**
+ * Regex of characters to strip instead of the default set; null resets
+ * to the default.
+ *
+ * # Safety
+ * `opts` must be a live handle and `pattern` null or a NUL-terminated
+ * string.
+ */
+ptrdiff_t slugify_rs_options_set_regex_pattern(SlugifyRsOptions *opts, const char *pattern);
+
+/**
+ * Add a word removed from slugs.
+ *
+ * # Safety
+ * `opts` must be a live handle and `word` a NUL-terminated string.
+ */
+ptrdiff_t slugify_rs_options_add_stopword(SlugifyRsOptions *opts, const char *word);
+
+/**
+ * Add a `from` -> `to` replacement.
+ *
+ * # Safety
+ * `opts` must be a live handle; `from` and `to` NUL-terminated strings.
+ */
+ptrdiff_t slugify_rs_options_add_replacement(SlugifyRsOptions *opts,
+                                             const char *from,
+                                             const char *to);
+
+/**
+ * Slugify `input` into `out` (capacity `out_len` bytes, NUL included).
+ *
+ * Returns the slug length without the NUL, like `snprintf`: when it is
+ * `>= out_len` nothing is written and the call can be retried with a
+ * larger buffer. Negative values are `SLUGIFY_RS_ERR_*` codes.
+ *
+ * # Safety
+ * `opts` must be a live handle not used concurrently, `input` a
+ * NUL-terminated string and `out` valid for `out_len` bytes (or null when
+ * `out_len` is 0).
+ */
+ptrdiff_t slugify_rs_slug(SlugifyRsOptions *opts, const char *input, char *out, size_t out_len);
+
+/**
+ * Slugify `count` strings in one call.
+ *
+ * The slugs are written back to back into `out`, each followed by a NUL,
+ * and `offsets[i]` receives the start of slug `i` in `out`. Returns the
+ * number of bytes needed for all slugs (NULs included); when it is
+ * greater than `out_cap` nothing is written and the call can be retried
+ * with a larger buffer. Negative values are `SLUGIFY_RS_ERR_*` codes.
+ *
+ * # Safety
+ * `opts` must be a live handle not used concurrently, `inputs` an array
+ * of `count` NUL-terminated strings, `offsets` valid for `count` writes
+ * and `out` valid for `out_cap` bytes (or null when `out_cap` is 0).
+ */
+ptrdiff_t slugify_rs_slug_many(SlugifyRsOptions *opts,
+                               const char *const *inputs,
+                               size_t count,
+                               char *out,
+                               size_t out_cap,
+                               size_t *offsets);
+
+/**
+ * `SlugifyOptionsC` holding the builder defaults.
+ */
+SlugifyOptionsC slugify_options_default(void);
+
+/**
+ * Slugify `text` into a new string owned by the caller, to be released
+ * with `slugify_free`. A null `opts` uses the defaults. Returns null when
+ * `text` is null, either string is not UTF-8 or the options are invalid.
+ *
+ * # Safety
+ * `text` must be a NUL-terminated string, and `opts` null or a valid
+ * `SlugifyOptionsC` whose `separator` is null or NUL-terminated.
+ */
+char *slugify_c(const char *text, const SlugifyOptionsC *opts);
+
+/**
+ * Free a string returned by `slugify_c`; null is ignored.
+ *
+ * # Safety
+ * `slug` must be null or a pointer from `slugify_c` not freed yet.
+ */
+void slugify_free(char *slug);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* SLUGIFY_RS_H */
@@ -1,5 +1,8 @@
+use clap::{Parser, Subcommand};
 use std::env;
+use std::fs;
 use std::io::{self, Read};
+use std::path::{Path, PathBuf};
 
 #[allow(dead_code)]
 fn bool_from_env(key: &str, default: bool) -> bool {
@@ -34,14 +37,166 @@ fn parse_usize_str(s: &str, default: usize) -> usize {
     s.parse().ok().unwrap_or(default)
 }
 
-/// Read all input from an arbitrary reader, trim trailing newline and return a String.
+/// Read all input from an arbitrary reader, trim trailing newlines (LF or
+/// CRLF) and return a String.
 fn read_input<R: Read>(r: &mut R) -> io::Result<String> {
     let mut input = String::new();
     r.read_to_string(&mut input)?;
-    Ok(input.trim_end_matches('\n').to_string())
+    Ok(input.trim_end_matches(['\n', '\r']).to_string())
+}
+
+/// Slugify stdin. Options come from the flags below; each flag falls back to
+/// its environment variable (SEPARATOR, MAX_LENGTH, STOPWORDS,
+/// ALLOW_UNICODE, REPLACEMENTS, REGEX_PATTERN), and the remaining options
+/// (ENTITIES, WORD_BOUNDARY, LOWERCASE, ...) are read from the environment
+/// only.
+#[derive(Parser, Debug)]
+#[command(name = "slugify_cli", version, about, disable_version_flag = true)]
+struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+    /// Separator placed between words [env: SEPARATOR]
+    #[arg(long, global = true)]
+    separator: Option<String>,
+    /// Maximum slug length, 0 for no limit [env: MAX_LENGTH]
+    #[arg(long, global = true)]
+    max_length: Option<usize>,
+    /// Comma-separated words removed from the slug [env: STOPWORDS]
+    #[arg(long, global = true)]
+    stopwords: Option<String>,
+    /// Keep Unicode letters instead of transliterating to ASCII [env: ALLOW_UNICODE]
+    #[arg(long, global = true)]
+    allow_unicode: bool,
+    /// Replacements as `from=>to` pairs joined by `;;` [env: REPLACEMENTS]
+    #[arg(long, global = true)]
+    replacements: Option<String>,
+    /// Regex of characters to strip instead of the default set [env: REGEX_PATTERN]
+    #[arg(long, global = true)]
+    regex_pattern: Option<String>,
+    /// Treat each stdin line as a separate input and print one slug per line
+    #[arg(long)]
+    lines: bool,
+    /// Print version
+    #[arg(short = 'V', long)]
+    version: bool,
+    /// With --version: print version, cargo features, algorithm version and
+    /// table fingerprints as JSON
+    #[arg(long)]
+    features: bool,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Slugify every stdin line and report length, duplicate and charset
+    /// statistics (MAX_LENGTH reports how many slugs would be truncated)
+    Stats,
+    /// Slugify stdin and print the stages that changed it, with their input
+    /// and output, to find out which one produced an unexpected slug
+    Explain,
+    /// Generate or validate the `slug:` field of Markdown front matter
+    /// (YAML `---` or TOML `+++`) from its `title:`. Directories are walked
+    /// for `.md` / `.markdown` files. Exits with status 1 when a slug is
+    /// missing or stale and the files were not rewritten.
+    Frontmatter {
+        /// Markdown files or directories
+        #[arg(required = true)]
+        paths: Vec<PathBuf>,
+        /// Write changed files back in place
+        #[arg(long)]
+        in_place: bool,
+        /// Report what would change without writing (overrides --in-place)
+        #[arg(long)]
+        dry_run: bool,
+        /// Replace existing slugs that differ from the title's slug
+        #[arg(long)]
+        overwrite: bool,
+        /// Print only the slug of each file, one per line
+        #[arg(long)]
+        slug_column_only: bool,
+    },
+    /// Check JSON options documents (as sent to `slugify_server`): unknown
+    /// keys, wrong types and options that fail to build are reported.
+    /// Exits with status 1 when a document is invalid.
+    #[cfg(feature = "config")]
+    ValidateConfig {
+        /// JSON files to check
+        #[arg(required_unless_present = "schema")]
+        paths: Vec<PathBuf>,
+        /// Print the JSON Schema of the options document instead
+        #[arg(long)]
+        schema: bool,
+    },
+}
+
+// `--version` prints the plain version; adding `--features` (or passing it
+// alone) prints the JSON build info used to compare deployed binaries.
+fn version_output(cli: &Cli) -> Option<String> {
+    if cli.features {
+        Some(slugify_rs::fingerprint::build_info_json())
+    } else if cli.version {
+        Some(format!("slugify_cli {}", env!("CARGO_PKG_VERSION")))
+    } else {
+        None
+    }
+}
+
+// Flags take precedence over the environment: write them into the env map
+// so a single parser (`options_from_env_map`) handles both sources.
+fn apply_flags(cli: &Cli, env_map: &mut StdHashMap<String, String>) {
+    let mut set = |key: &str, val: Option<String>| {
+        if let Some(v) = val {
+            env_map.insert(key.to_string(), v);
+        }
+    };
+    set("SEPARATOR", cli.separator.clone());
+    set("MAX_LENGTH", cli.max_length.map(|n| n.to_string()));
+    set("STOPWORDS", cli.stopwords.clone());
+    set("ALLOW_UNICODE", cli.allow_unicode.then(|| "1".to_string()));
+    set("REPLACEMENTS", cli.replacements.clone());
+    set("REGEX_PATTERN", cli.regex_pattern.clone());
 }
 
 fn main() {
+    // Parse flags before touching stdin so `--help` / `--version` return
+    // immediately.
+    let cli = Cli::parse();
+    if let Some(info) = version_output(&cli) {
+        println!("{}", info);
+        return;
+    }
+
+    #[cfg(feature = "config")]
+    if let Some(Command::ValidateConfig { paths, schema }) = &cli.command {
+        match run_validate_config(paths, *schema) {
+            Ok((out, valid)) => {
+                println!("{}", out);
+                std::process::exit(if valid { 0 } else { 1 });
+            }
+            Err(e) => {
+                eprintln!("{}", e);
+                std::process::exit(2);
+            }
+        }
+    }
+
+    // `frontmatter` reads files, not stdin
+    if let Some(Command::Frontmatter { .. }) = &cli.command {
+        let mut env_map: StdHashMap<String, String> = std::env::vars().collect();
+        apply_flags(&cli, &mut env_map);
+        match run_frontmatter(&cli, &env_map) {
+            Ok((out, clean)) => {
+                if !out.is_empty() {
+                    println!("{}", out);
+                }
+                std::process::exit(if clean { 0 } else { 1 });
+            }
+            Err(e) => {
+                eprintln!("{}", e);
+                std::process::exit(2);
+            }
+        }
+    }
+
     // Read stdin via a small testable helper
     let text = match read_input(&mut io::stdin()) {
         Ok(s) => s,
@@ -56,8 +211,19 @@ fn main() {
     for (k, v) in std::env::vars() {
         env_map.insert(k, v);
     }
+    apply_flags(&cli, &mut env_map);
+
+    let result = match cli.command {
+        Some(Command::Stats) => run_stats_with_env_map(&env_map, &text),
+        Some(Command::Explain) => run_explain_with_env_map(&env_map, &text),
+        Some(Command::Frontmatter { .. }) => unreachable!("handled before reading stdin"),
+        #[cfg(feature = "config")]
+        Some(Command::ValidateConfig { .. }) => unreachable!("handled before reading stdin"),
+        None if cli.lines => run_lines_with_env_map(&env_map, &text),
+        None => run_with_env_map(&env_map, &text),
+    };
 
-    match run_with_env_map(&env_map, &text) {
+    match result {
         Ok(out) => println!("{}", out),
         Err(e) => {
             eprintln!("{}", e);
@@ -73,9 +239,35 @@ use std::collections::HashMap as StdHashMap;
 // `main` without spawning the binary. Returning Result lets callers decide
 // how to handle builder errors (main exits, tests can assert on Err).
 fn run_with_env_map(env_map: &StdHashMap<String, String>, text: &str) -> Result<String, String> {
-    use slugify_rs::slugify::SlugifyOptions;
     use slugify_rs::slugify::slugify_with_options_public;
 
+    let opts = options_from_env_map(env_map)?;
+    Ok(slugify_with_options_public(&opts, text))
+}
+
+// `--lines` mode: slugify each input line on its own (CSV exports, `ls`
+// output) and return one slug per line. Blank lines yield empty lines so the
+// output stays aligned with the input.
+fn run_lines_with_env_map(
+    env_map: &StdHashMap<String, String>,
+    text: &str,
+) -> Result<String, String> {
+    use slugify_rs::slugify::slugify_with_options_public;
+
+    let opts = options_from_env_map(env_map)?;
+    let slugs: Vec<String> = text
+        .lines()
+        .map(|line| slugify_with_options_public(&opts, line))
+        .collect();
+    Ok(slugs.join("\n"))
+}
+
+// Build `SlugifyOptions` from the env map (shared by every CLI mode).
+fn options_from_env_map(
+    env_map: &StdHashMap<String, String>,
+) -> Result<slugify_rs::slugify::SlugifyOptions, String> {
+    use slugify_rs::slugify::{EmojiPolicy, SlugifyOptions};
+
     let get = |k: &str| env_map.get(k).map(|s| s.as_str());
 
     let entities = get("ENTITIES").map(|v| matches!(v, "1" | "true" | "True" | "yes")).unwrap_or(true);
@@ -100,6 +292,7 @@ fn run_with_env_map(env_map: &StdHashMap<String, String>, text: &str) -> Result<
     };
     let allow_unicode = get("ALLOW_UNICODE").map(|v| matches!(v, "1" | "true" | "True" | "yes")).unwrap_or(false);
     let transliterate_icons_env = get("TRANSLITERATE_ICONS");
+    let emoji_policy_env = get("EMOJI_POLICY");
 
     let mut builder = SlugifyOptions::builder()
         .entities(entities)
@@ -116,11 +309,191 @@ fn run_with_env_map(env_map: &StdHashMap<String, String>, text: &str) -> Result<
         .allow_unicode(allow_unicode);
 
     if let Some(val) = transliterate_icons_env {
-        builder = builder.transliterate_icons(matches!(val, "1" | "true" | "True" | "yes"));
+        builder = builder.emoji_policy(EmojiPolicy::from_transliterate_icons(matches!(
+            val,
+            "1" | "true" | "True" | "yes"
+        )));
+    }
+    // EMOJI_POLICY (remove / transliterate / shortcode / keep) takes
+    // precedence over the legacy TRANSLITERATE_ICONS flag
+    if let Some(val) = emoji_policy_env {
+        let policy = match val.to_ascii_lowercase().as_str() {
+            "remove" => EmojiPolicy::Remove,
+            "transliterate" => EmojiPolicy::Transliterate,
+            "shortcode" => EmojiPolicy::Shortcode,
+            "keep" => EmojiPolicy::Keep,
+            other => return Err(format!("invalid EMOJI_POLICY: {}", other)),
+        };
+        builder = builder.emoji_policy(policy);
     }
 
-    let opts = builder.build().map_err(|e| format!("failed to build options: {:?}", e))?;
-    Ok(slugify_with_options_public(&opts, text))
+    builder.build().map_err(|e| format!("failed to build options: {:?}", e))
+}
+
+// `stats` subcommand: slugify every input line without truncation and
+// report the length distribution, duplicates and charset. When MAX_LENGTH
+// is set, also report how many slugs that limit would truncate.
+fn run_stats_with_env_map(env_map: &StdHashMap<String, String>, text: &str) -> Result<String, String> {
+    use slugify_rs::slugify::slugify_with_options_public;
+    use slugify_rs::stats::summarize;
+
+    let mut opts = options_from_env_map(env_map)?;
+    let max_length = opts.max_length;
+    opts.max_length = 0;
+    let slugs: Vec<String> = text
+        .lines()
+        .map(|line| slugify_with_options_public(&opts, line))
+        .collect();
+    Ok(format_stats(&summarize(&slugs), max_length))
+}
+
+// `explain` subcommand: the `SlugTrace` of the whole input.
+fn run_explain_with_env_map(
+    env_map: &StdHashMap<String, String>,
+    text: &str,
+) -> Result<String, String> {
+    let opts = options_from_env_map(env_map)?;
+    Ok(slugify_rs::explain::slugify_explain(text, &opts).to_string())
+}
+
+// `frontmatter` subcommand: one report line per file (or only the slug with
+// `--slug-column-only`). Returns whether every file was up to date or has
+// been rewritten.
+fn run_frontmatter(
+    cli: &Cli,
+    env_map: &StdHashMap<String, String>,
+) -> Result<(String, bool), String> {
+    use slugify_rs::frontmatter::{SlugUpdate, update_slug};
+
+    let Some(Command::Frontmatter {
+        paths,
+        in_place,
+        dry_run,
+        overwrite,
+        slug_column_only,
+    }) = &cli.command
+    else {
+        return Err("not a frontmatter command".to_string());
+    };
+    let write = *in_place && !*dry_run;
+    let opts = options_from_env_map(env_map)?;
+    let mut files = Vec::new();
+    for path in paths {
+        collect_markdown(path, &mut files).map_err(|e| format!("{}: {}", path.display(), e))?;
+    }
+
+    let mut lines = Vec::new();
+    let mut clean = true;
+    for file in files {
+        let doc = fs::read_to_string(&file).map_err(|e| format!("{}: {}", file.display(), e))?;
+        let name = file.display();
+        // (report line, slug now in effect, rewritten document, out of date)
+        let (line, slug, document, pending) = match update_slug(&doc, &opts, *overwrite) {
+            SlugUpdate::NoFrontMatter => (format!("{}: no front matter", name), None, None, false),
+            SlugUpdate::NoTitle => (format!("{}: no title", name), None, None, false),
+            SlugUpdate::Unchanged { slug } => {
+                (format!("{}: ok {}", name, slug), Some(slug), None, false)
+            }
+            SlugUpdate::Added { slug, document } => {
+                (format!("{}: add {}", name, slug), Some(slug), Some(document), true)
+            }
+            SlugUpdate::Mismatch {
+                existing,
+                expected,
+                document,
+            } => (
+                format!("{}: stale {} (title gives {})", name, existing, expected),
+                Some(if document.is_some() { expected } else { existing }),
+                document,
+                true,
+            ),
+        };
+        match document {
+            Some(document) if write => {
+                fs::write(&file, document).map_err(|e| format!("{}: {}", name, e))?
+            }
+            _ if pending => clean = false,
+            _ => {}
+        }
+        if *slug_column_only {
+            lines.extend(slug);
+        } else {
+            lines.push(line);
+        }
+    }
+    Ok((lines.join("\n"), clean))
+}
+
+// One "path: ok" / "path: error" line per document, and whether all of
+// them are valid. With `schema`, the JSON Schema is returned instead.
+#[cfg(feature = "config")]
+fn run_validate_config(paths: &[PathBuf], schema: bool) -> Result<(String, bool), String> {
+    if schema {
+        return Ok((slugify_rs::config::schema_json(), true));
+    }
+    let mut lines = Vec::new();
+    let mut valid = true;
+    for path in paths {
+        let json = fs::read_to_string(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+        match slugify_rs::config::validate(&json) {
+            Ok(_) => lines.push(format!("{}: ok", path.display())),
+            Err(e) => {
+                valid = false;
+                lines.push(format!("{}: {:?}", path.display(), e));
+            }
+        }
+    }
+    Ok((lines.join("\n"), valid))
+}
+
+// Expand directories into their Markdown files, sorted for stable output.
+fn collect_markdown(path: &Path, out: &mut Vec<PathBuf>) -> io::Result<()> {
+    if !path.is_dir() {
+        out.push(path.to_path_buf());
+        return Ok(());
+    }
+    let mut entries: Vec<PathBuf> = fs::read_dir(path)?
+        .map(|e| e.map(|e| e.path()))
+        .collect::<io::Result<_>>()?;
+    entries.sort();
+    for entry in entries {
+        let markdown = entry
+            .extension()
+            .is_some_and(|ext| ext == "md" || ext == "markdown");
+        if entry.is_dir() || markdown {
+            collect_markdown(&entry, out)?;
+        }
+    }
+    Ok(())
+}
+
+fn format_stats(stats: &slugify_rs::stats::SlugStats, max_length: usize) -> String {
+    let mut lines = vec![
+        format!("slugs: {}", stats.total),
+        format!("empty: {}", stats.empty),
+        format!(
+            "length min/mean/max: {}/{:.1}/{}",
+            stats.min_len().unwrap_or(0),
+            stats.mean_len(),
+            stats.max_len().unwrap_or(0)
+        ),
+    ];
+    if max_length > 0 {
+        let over = stats.exceeding(max_length);
+        let pct = if stats.total == 0 { 0.0 } else { over as f64 * 100.0 / stats.total as f64 };
+        lines.push(format!("over max_length {}: {} ({:.1}%)", max_length, over, pct));
+    }
+    lines.push(format!("duplicates: {}", stats.duplicates.len()));
+    for (slug, n) in &stats.duplicates {
+        lines.push(format!("  {} x{}", slug, n));
+    }
+    lines.push("length histogram:".to_string());
+    for (start, n) in stats.histogram(10) {
+        lines.push(format!("  {}-{}: {}", start, start + 9, n));
+    }
+    let charset: String = stats.charset.keys().collect();
+    lines.push(format!("charset: {}", charset));
+    lines.join("\n")
 }
 
 
@@ -139,7 +512,7 @@ mod tests {
     // the test harness finds the test binary itself and would otherwise
     // spawn it (causing exponential test runs and system overload).
     use std::collections::HashMap;
-    use slugify_rs::slugify::SlugifyOptions;
+    use slugify_rs::slugify::{EmojiPolicy, SlugifyOptions};
     use slugify_rs::slugify::slugify_with_options_public;
 
     fn parse_bool_opt(v: Option<&str>, default: bool) -> bool {
@@ -201,7 +574,10 @@ mod tests {
             .allow_unicode(allow_unicode);
 
         if let Some(val) = transliterate_icons_env {
-            builder = builder.transliterate_icons(matches!(val, "1" | "true" | "True" | "yes"));
+            builder = builder.emoji_policy(EmojiPolicy::from_transliterate_icons(matches!(
+                val,
+                "1" | "true" | "True" | "yes"
+            )));
         }
 
         let opts = builder.build().expect("build options");
@@ -377,6 +753,17 @@ mod tests {
         assert_eq!(out.trim(), "i-love");
     }
 
+    #[test]
+    fn test_emoji_policy_env() {
+        let mut m = HashMap::new();
+        m.insert("TRANSLITERATE_ICONS".to_string(), "0".to_string());
+        m.insert("EMOJI_POLICY".to_string(), "Transliterate".to_string());
+        let opts = super::options_from_env_map(&m).expect("valid env");
+        assert_eq!(opts.emoji_policy, EmojiPolicy::Transliterate);
+        m.insert("EMOJI_POLICY".to_string(), "bogus".to_string());
+        assert!(super::options_from_env_map(&m).is_err());
+    }
+
     // NOTE: avoid manipulating global process env in tests (set_var/remove_var)
     // because it can be unsafe in multithreaded test harnesses. We exercise
     // parsing logic via inproc helpers instead.
@@ -400,6 +787,168 @@ mod tests {
     assert!(!out2.trim().is_empty());
     }
 
+    #[test]
+    fn test_run_stats_with_env_map() {
+        use std::collections::HashMap as StdHashMap;
+
+        let mut m = StdHashMap::new();
+        m.insert("MAX_LENGTH".to_string(), "5".to_string());
+        let out = super::run_stats_with_env_map(&m, "Hello World\nfoo\nFoo\n").expect("stats failed");
+        assert!(out.contains("slugs: 3"));
+        assert!(out.contains("over max_length 5: 1 (33.3%)"));
+        assert!(out.contains("  foo x2"));
+        assert!(out.contains("length min/mean/max: 3/5.7/11"));
+        assert!(out.contains("charset: -defhlorw"));
+    }
+
+    #[test]
+    fn test_run_explain_with_env_map() {
+        use std::collections::HashMap as StdHashMap;
+
+        let mut m = StdHashMap::new();
+        m.insert("STOPWORDS".to_string(), "the".to_string());
+        let out = super::run_explain_with_env_map(&m, "The &amp; Fox").expect("explain failed");
+        assert!(out.contains("entities: \"The &amp; Fox\" -> \"The & Fox\""));
+        assert!(out.contains("stopwords: \"the-fox\" -> \"fox\""));
+        assert!(out.ends_with("slug: \"fox\""));
+    }
+
+    #[test]
+    fn test_flags_override_env() {
+        use clap::{CommandFactory, Parser};
+        use std::collections::HashMap as StdHashMap;
+
+        super::Cli::command().debug_assert();
+        let cli = super::Cli::try_parse_from([
+            "slugify_cli",
+            "--separator",
+            "_",
+            "--stopwords",
+            "The,a",
+            "--max-length",
+            "9",
+        ])
+        .expect("flags should parse");
+        let mut m = StdHashMap::new();
+        m.insert("SEPARATOR".to_string(), ".".to_string());
+        m.insert("LOWERCASE".to_string(), "0".to_string());
+        super::apply_flags(&cli, &mut m);
+        // flags win, env-only options still apply
+        let out = super::run_with_env_map(&m, "The Quick Brown Fox").expect("run failed");
+        assert_eq!(out, "Quick_Bro");
+
+        let cli = super::Cli::try_parse_from(["slugify_cli", "stats", "--allow-unicode"])
+            .expect("subcommand should parse");
+        assert!(matches!(cli.command, Some(super::Command::Stats)));
+        assert!(cli.allow_unicode);
+        assert!(super::Cli::try_parse_from(["slugify_cli", "--max-length", "x"]).is_err());
+    }
+
+    #[test]
+    fn test_version_features_json() {
+        use clap::Parser;
+
+        let parse = |args: &[&str]| super::Cli::try_parse_from(args).expect("parse");
+        assert_eq!(super::version_output(&parse(&["slugify_cli"])), None);
+        assert_eq!(
+            super::version_output(&parse(&["slugify_cli", "-V"])),
+            Some(format!("slugify_cli {}", env!("CARGO_PKG_VERSION")))
+        );
+        let json = super::version_output(&parse(&["slugify_cli", "--version", "--features"]))
+            .expect("json");
+        assert_eq!(json, slugify_rs::fingerprint::build_info_json());
+        assert!(json.contains("\"features\":[\"cli\""));
+        assert!(json.contains("\"algorithm_fingerprint\":"));
+    }
+
+    #[test]
+    fn test_run_lines_with_env_map() {
+        use clap::Parser;
+        use std::collections::HashMap as StdHashMap;
+
+        let m = StdHashMap::new();
+        let out = super::run_lines_with_env_map(&m, "Hello World\r\n\nfoo.txt\nBar Baz")
+            .expect("lines failed");
+        assert_eq!(out, "hello-world\n\nfoo-txt\nbar-baz");
+        let cli = super::Cli::try_parse_from(["slugify_cli", "--lines"]).expect("parse");
+        assert!(cli.lines);
+    }
+
+    #[test]
+    fn test_run_frontmatter() {
+        use clap::Parser;
+        use std::collections::HashMap as StdHashMap;
+
+        let dir = std::env::temp_dir().join(format!("slugify_cli_fm_{}", std::process::id()));
+        let nested = dir.join("posts");
+        std::fs::create_dir_all(&nested).expect("mkdir");
+        let new = nested.join("new.md");
+        let stale = nested.join("stale.markdown");
+        std::fs::write(&new, "---\ntitle: Hello World\n---\nBody\n").expect("write");
+        std::fs::write(&stale, "+++\ntitle = \"Fresh Title\"\nslug = \"old\"\n+++\n").expect("write");
+        std::fs::write(dir.join("notes.txt"), "---\ntitle: Skipped\n---\n").expect("write");
+        let run = |args: &[&str]| {
+            let mut argv = vec!["slugify_cli", "frontmatter"];
+            argv.extend_from_slice(args);
+            argv.push(dir.to_str().expect("utf-8 temp dir"));
+            let cli = super::Cli::try_parse_from(argv).expect("parse");
+            super::run_frontmatter(&cli, &StdHashMap::new()).expect("frontmatter failed")
+        };
+
+        let (out, clean) = run(&["--dry-run", "--in-place"]);
+        assert!(!clean);
+        assert!(out.contains("new.md: add hello-world"));
+        assert!(out.contains("stale.markdown: stale old (title gives fresh-title)"));
+        assert!(!out.contains("notes.txt"));
+        assert_eq!(run(&["--slug-column-only"]).0, "hello-world\nold");
+
+        let (_, clean) = run(&["--in-place"]);
+        assert!(!clean, "stale slug kept without --overwrite");
+        assert_eq!(
+            std::fs::read_to_string(&new).expect("read"),
+            "---\ntitle: Hello World\nslug: hello-world\n---\nBody\n"
+        );
+        let (_, clean) = run(&["--in-place", "--overwrite"]);
+        assert!(clean);
+        let (out, clean) = run(&["--slug-column-only"]);
+        assert!(clean);
+        assert_eq!(out, "hello-world\nfresh-title");
+        std::fs::remove_dir_all(&dir).expect("cleanup");
+    }
+
+    #[cfg(feature = "config")]
+    #[test]
+    fn test_run_validate_config() {
+        use clap::Parser;
+
+        let dir = std::env::temp_dir().join(format!("slugify_cli_cfg_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).expect("mkdir");
+        let good = dir.join("good.json");
+        let bad = dir.join("bad.json");
+        std::fs::write(&good, r#"{"separator": "_", "max_length": 40}"#).expect("write");
+        std::fs::write(&bad, r#"{"separater": "_"}"#).expect("write");
+
+        let (out, valid) = super::run_validate_config(std::slice::from_ref(&good), false)
+            .expect("validate failed");
+        assert!(valid);
+        assert!(out.ends_with("good.json: ok"));
+        let (out, valid) =
+            super::run_validate_config(&[good, bad], false).expect("validate failed");
+        assert!(!valid);
+        assert!(out.contains("bad.json: Json(") && out.contains("separater"));
+        assert!(super::run_validate_config(&[dir.join("missing.json")], false).is_err());
+
+        let cli = super::Cli::try_parse_from(["slugify_cli", "validate-config", "--schema"])
+            .expect("parse");
+        let Some(super::Command::ValidateConfig { paths, schema }) = cli.command else {
+            panic!("expected validate-config");
+        };
+        let (out, _) = super::run_validate_config(&paths, schema).expect("schema");
+        assert!(out.contains("\"max_length\""));
+        assert!(super::Cli::try_parse_from(["slugify_cli", "validate-config"]).is_err());
+        std::fs::remove_dir_all(&dir).expect("cleanup");
+    }
+
     #[test]
     fn test_bin_path_basic() {
         // Do not mutate the process environment; just ensure the returned
@@ -416,6 +965,14 @@ mod tests {
         assert_eq!(s, "hello world");
     }
 
+    #[test]
+    fn test_read_input_crlf() {
+        // Windows pipes end lines with CRLF; output must match Unix input
+        let mut data = "hello world\r\n".as_bytes();
+        let s = super::read_input(&mut data).expect("read should succeed");
+        assert_eq!(s, "hello world");
+    }
+
     struct FailingReader;
     impl Read for FailingReader {
         fn read(&mut self, _buf: &mut [u8]) -> io::Result<usize> {
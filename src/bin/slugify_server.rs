@@ -0,0 +1,180 @@
+// Minimal HTTP/JSON front end so services without bindings can share one
+// slug implementation:
+//
+//   POST /slugify  {"text": "Hello World", "options": {"separator": "_"}}
+//               -> {"slug": "hello_world"}
+//   POST /slugify  {"texts": ["a b", "c d"]}  -> {"slugs": ["a-b", "c-d"]}
+//
+// Options form a `config::OptionsDocument` (`max_length`, `allow_unicode`,
+// ...; see `slugify_cli validate-config --schema`).
+// Listens on the first argument, `SLUGIFY_SERVER_ADDR`, or 127.0.0.1:8080.
+use serde_json::{Value, json};
+use slugify_rs::config::OptionsDocument;
+use slugify_rs::slugifier::Slugifier;
+use slugify_rs::slugify::SlugifyOptions;
+use std::env;
+use std::io::Read;
+use std::sync::Arc;
+use std::thread;
+use tiny_http::{Header, Method, Request, Response, Server};
+
+const DEFAULT_ADDR: &str = "127.0.0.1:8080";
+// Larger request bodies are rejected with 413.
+const MAX_BODY_BYTES: u64 = 1 << 20;
+
+fn strings(value: &Value, key: &str) -> Result<Vec<String>, String> {
+    value
+        .as_array()
+        .ok_or_else(|| format!("{} must be an array", key))?
+        .iter()
+        .map(|v| {
+            v.as_str()
+                .map(str::to_string)
+                .ok_or_else(|| format!("{} must hold strings", key))
+        })
+        .collect()
+}
+
+// Builder defaults, overridden by the keys present in `options`.
+fn options_from_json(options: Option<&Value>) -> Result<SlugifyOptions, String> {
+    let doc = match options.filter(|o| !o.is_null()) {
+        Some(v) => OptionsDocument::from_value(v.clone())
+            .map_err(|e| format!("invalid options: {:?}", e))?,
+        None => OptionsDocument::default(),
+    };
+    doc.build().map_err(|e| format!("{:?}", e))
+}
+
+// Status code and JSON body for one request.
+fn handle(method: &Method, url: &str, body: &str) -> (u16, Value) {
+    let path = url.split('?').next().unwrap_or(url);
+    if path != "/slugify" {
+        return (404, json!({ "error": "not found" }));
+    }
+    if *method != Method::Post {
+        return (405, json!({ "error": "use POST" }));
+    }
+    match slugify_body(body) {
+        Ok(v) => (200, v),
+        Err(e) => (400, json!({ "error": e })),
+    }
+}
+
+fn slugify_body(body: &str) -> Result<Value, String> {
+    let req: Value = serde_json::from_str(body).map_err(|e| format!("invalid JSON: {}", e))?;
+    let slugifier = Slugifier::new(options_from_json(req.get("options"))?);
+    match (req.get("text"), req.get("texts")) {
+        (Some(text), None) => {
+            let text = text.as_str().ok_or("text must be a string")?;
+            Ok(json!({ "slug": slugifier.slugify(text) }))
+        }
+        (None, Some(texts)) => {
+            let slugs: Vec<String> = strings(texts, "texts")?
+                .iter()
+                .map(|t| slugifier.slugify(t))
+                .collect();
+            Ok(json!({ "slugs": slugs }))
+        }
+        _ => Err("expected exactly one of text or texts".to_string()),
+    }
+}
+
+fn respond(mut request: Request) {
+    let mut body = String::new();
+    let read = request
+        .as_reader()
+        .take(MAX_BODY_BYTES + 1)
+        .read_to_string(&mut body);
+    let (status, value) = match read {
+        Err(_) => (400, json!({ "error": "body must be UTF-8" })),
+        Ok(n) if n as u64 > MAX_BODY_BYTES => (413, json!({ "error": "body too large" })),
+        Ok(_) => handle(request.method(), request.url(), &body),
+    };
+    let mut response = Response::from_string(value.to_string()).with_status_code(status);
+    if let Ok(header) = Header::from_bytes("Content-Type", "application/json") {
+        response = response.with_header(header);
+    }
+    if let Err(e) = request.respond(response) {
+        eprintln!("slugify_server: failed to respond: {}", e);
+    }
+}
+
+fn main() {
+    let addr = env::args()
+        .nth(1)
+        .or_else(|| env::var("SLUGIFY_SERVER_ADDR").ok())
+        .unwrap_or_else(|| DEFAULT_ADDR.to_string());
+    let server = match Server::http(&addr) {
+        Ok(s) => Arc::new(s),
+        Err(e) => {
+            eprintln!("slugify_server: cannot listen on {}: {}", addr, e);
+            std::process::exit(1);
+        }
+    };
+    eprintln!("slugify_server: listening on http://{}", addr);
+    let workers = thread::available_parallelism().map_or(4, |n| n.get());
+    let handles: Vec<_> = (0..workers)
+        .map(|_| {
+            let server = Arc::clone(&server);
+            thread::spawn(move || {
+                for request in server.incoming_requests() {
+                    respond(request);
+                }
+            })
+        })
+        .collect();
+    for h in handles {
+        let _ = h.join();
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::expect_used)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    fn post(body: &str) -> (u16, Value) {
+        handle(&Method::Post, "/slugify", body)
+    }
+
+    #[test]
+    fn test_single_and_batch() {
+        assert_eq!(
+            post(r#"{"text": "Hello World!"}"#),
+            (200, json!({ "slug": "hello-world" }))
+        );
+        assert_eq!(
+            post(r#"{"texts": ["First Post", "Élan"], "options": {"separator": "_"}}"#),
+            (200, json!({ "slugs": ["first_post", "elan"] }))
+        );
+        assert_eq!(
+            post(
+                r#"{"text": "the quick brown fox", "options":
+                    {"stopwords": ["the"], "max_length": 11, "replacements": [["fox", "cat"]]}}"#
+            ),
+            (200, json!({ "slug": "quick-brown" }))
+        );
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(handle(&Method::Post, "/other", "{}").0, 404);
+        assert_eq!(handle(&Method::Get, "/slugify?x=1", "").0, 405);
+        for body in [
+            "not json",
+            "{}",
+            r#"{"text": "a", "texts": ["b"]}"#,
+            r#"{"text": 1}"#,
+            r#"{"texts": ["a", 1]}"#,
+            r#"{"text": "a", "options": {"max_length": -1}}"#,
+            r#"{"text": "a", "options": {"regex_pattern": "("}}"#,
+            r#"{"text": "a", "options": []}"#,
+            r#"{"text": "a", "options": {"max_len": 3}}"#,
+        ] {
+            let (status, value) = post(body);
+            assert_eq!(status, 400, "{}", body);
+            assert!(value["error"].is_string(), "{}", body);
+        }
+    }
+}
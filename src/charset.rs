@@ -0,0 +1,208 @@
+// Character sets for `SlugifyOptionsBuilder::allowed_chars`, written like
+// the inside of a regex bracket expression but matched without `regex`:
+// "a-z0-9._" lists ranges and single characters, a leading `^` makes it a
+// denylist ("^/?#"), `-` is literal at either end and `\` makes the next
+// character literal ("a-z\-", "\^"). `CharClassifier` is the programmatic
+// counterpart for `SlugifyOptionsBuilder::char_classifier`.
+use std::fmt;
+use std::sync::Arc;
+
+use crate::slugify::SlugifyError;
+
+/// A parsed `allowed_chars` set.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CharSet {
+    ranges: Vec<(char, char)>,
+    negated: bool,
+}
+
+impl CharSet {
+    /// Parse `spec`. Fails with `SlugifyError::InvalidCharSet` when it is
+    /// empty, ends with a lone `\` or has a range whose ends are reversed
+    /// ("z-a").
+    pub fn parse(spec: &str) -> Result<Self, SlugifyError> {
+        let invalid = || SlugifyError::InvalidCharSet(spec.to_string());
+        let (negated, body) = match spec.strip_prefix('^') {
+            Some(rest) => (true, rest),
+            None => (false, spec),
+        };
+        // (character, escaped) pairs, so an escaped `-` never forms a range
+        let mut items = Vec::new();
+        let mut chars = body.chars();
+        while let Some(c) = chars.next() {
+            if c == '\\' {
+                items.push((chars.next().ok_or_else(invalid)?, true));
+            } else {
+                items.push((c, false));
+            }
+        }
+        if items.is_empty() {
+            return Err(invalid());
+        }
+        let mut ranges = Vec::new();
+        let mut i = 0;
+        while i < items.len() {
+            let start = items[i].0;
+            let is_range = i + 2 < items.len() && items[i + 1] == ('-', false);
+            if is_range {
+                let end = items[i + 2].0;
+                if end < start {
+                    return Err(invalid());
+                }
+                ranges.push((start, end));
+                i += 3;
+            } else {
+                ranges.push((start, start));
+                i += 1;
+            }
+        }
+        Ok(CharSet { ranges, negated })
+    }
+
+    /// Whether `c` is kept.
+    pub fn allows(&self, c: char) -> bool {
+        let listed = self
+            .ranges
+            .iter()
+            .any(|&(start, end)| start <= c && c <= end);
+        listed != self.negated
+    }
+
+    /// `s` with every run of characters the set does not allow replaced by
+    /// `sep`.
+    pub fn replace_disallowed(&self, s: &str, sep: &str) -> String {
+        let mut out = String::with_capacity(s.len());
+        let mut in_run = false;
+        for c in s.chars() {
+            if self.allows(c) {
+                out.push(c);
+                in_run = false;
+            } else if !in_run {
+                out.push_str(sep);
+                in_run = true;
+            }
+        }
+        out
+    }
+}
+
+/// What sanitization does with one character under a `CharClassifier`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CharAction {
+    /// Keep the character.
+    Keep,
+    /// Split words here; a run of separators becomes one.
+    Separator,
+    /// Remove the character without splitting the word.
+    Drop,
+    /// Put this text in its place, as is.
+    Replace(String),
+}
+
+/// A `Fn(char) -> CharAction` callback deciding character by character
+/// what sanitization keeps, shared between threads like the options
+/// holding it.
+#[derive(Clone)]
+pub struct CharClassifier(Arc<dyn Fn(char) -> CharAction + Send + Sync>);
+
+impl CharClassifier {
+    pub fn new<F>(f: F) -> Self
+    where
+        F: Fn(char) -> CharAction + Send + Sync + 'static,
+    {
+        CharClassifier(Arc::new(f))
+    }
+
+    pub fn classify(&self, c: char) -> CharAction {
+        (self.0)(c)
+    }
+
+    /// `s` with each character handled as the callback says, runs of
+    /// `CharAction::Separator` becoming one `sep`.
+    pub fn apply(&self, s: &str, sep: &str) -> String {
+        let mut out = String::with_capacity(s.len());
+        let mut in_run = false;
+        for c in s.chars() {
+            match self.classify(c) {
+                CharAction::Keep => out.push(c),
+                CharAction::Replace(r) => out.push_str(&r),
+                CharAction::Drop => continue,
+                CharAction::Separator => {
+                    if !in_run {
+                        out.push_str(sep);
+                        in_run = true;
+                    }
+                    continue;
+                }
+            }
+            in_run = false;
+        }
+        out
+    }
+}
+
+impl fmt::Debug for CharClassifier {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("CharClassifier(..)")
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::expect_used)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_allowlist() {
+        let set = CharSet::parse("a-z0-9._").unwrap();
+        assert!(set.allows('q') && set.allows('7') && set.allows('.') && set.allows('_'));
+        assert!(!set.allows('A') && !set.allows('-') && !set.allows('é'));
+        assert_eq!(set.replace_disallowed("v1.2 beta!!x", "-"), "v1.2-beta-x");
+    }
+
+    #[test]
+    fn test_denylist() {
+        let set = CharSet::parse("^/?#").unwrap();
+        assert!(set.allows('a') && set.allows('é') && set.allows('-'));
+        assert!(!set.allows('/') && !set.allows('?'));
+        assert_eq!(set.replace_disallowed("a/b?c", "-"), "a-b-c");
+    }
+
+    #[test]
+    fn test_literal_hyphen_and_escapes() {
+        let set = CharSet::parse("-a-c").unwrap();
+        assert!(set.allows('-') && set.allows('b') && !set.allows('d'));
+        let set = CharSet::parse("a-c-").unwrap();
+        assert!(set.allows('-') && set.allows('b'));
+        let set = CharSet::parse(r"a\-c").unwrap();
+        assert!(set.allows('-') && !set.allows('b'));
+        let set = CharSet::parse(r"\^\\").unwrap();
+        assert!(set.allows('^') && set.allows('\\') && !set.allows('a'));
+        // a lone `^` has nothing to negate
+        assert!(CharSet::parse("^").is_err());
+    }
+
+    #[test]
+    fn test_invalid() {
+        for spec in ["", "z-a", r"ab\"] {
+            assert!(
+                matches!(CharSet::parse(spec), Err(SlugifyError::InvalidCharSet(s)) if s == spec),
+                "{spec:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_classifier() {
+        let classifier = CharClassifier::new(|c| match c {
+            'a'..='z' | '0'..='9' => CharAction::Keep,
+            '\'' => CharAction::Drop,
+            '&' => CharAction::Replace("and".into()),
+            _ => CharAction::Separator,
+        });
+        assert_eq!(classifier.classify('q'), CharAction::Keep);
+        assert_eq!(classifier.apply("don't stop&go!!", "-"), "dont-stopandgo-");
+        assert_eq!(format!("{classifier:?}"), "CharClassifier(..)");
+    }
+}
@@ -0,0 +1,139 @@
+// Slug algorithms of other implementations, reproduced exactly so existing
+// URLs and anchors keep working (see `slugify::Compat`).
+use once_cell::sync::Lazy;
+use regex::Regex;
+use unicode_normalization::UnicodeNormalization;
+
+// github-slugger removes everything but letters, marks, numbers, connector
+// punctuation (`_`), spaces and hyphens.
+#[allow(clippy::unwrap_used)]
+static GITHUB_REMOVED: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"[^\p{L}\p{M}\p{N}\p{Pc} -]").unwrap());
+
+// Django's `[^\w\s-]` and `[-\s]+`. Python's `\w` has no marks, and its
+// `\s` also covers the information separators U+001C..U+001F.
+#[allow(clippy::unwrap_used)]
+static DJANGO_REMOVED: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"[^\p{L}\p{N}_\s\x1C-\x1F-]").unwrap());
+#[allow(clippy::unwrap_used)]
+static DJANGO_SEPARATORS: Lazy<Regex> = Lazy::new(|| Regex::new(r"[-\s\x1C-\x1F]+").unwrap());
+
+/// GitHub heading id of `text` ("Foo & Bar" -> "foo--bar"). Duplicate
+/// headings get `-1`, `-2`, ... on GitHub; track those with a
+/// `registry::SlugRegistry` if needed.
+pub fn github_anchor(text: &str, lowercase: bool) -> String {
+    let text = if lowercase {
+        text.to_lowercase()
+    } else {
+        text.to_string()
+    };
+    GITHUB_REMOVED.replace_all(&text, "").replace(' ', "-")
+}
+
+/// `django.utils.text.slugify(text, allow_unicode)`: NFKC (unicode) or
+/// NFKD with non-ASCII dropped, lowercase, remove everything but word
+/// characters, whitespace and `-`, collapse runs of `-` and whitespace into
+/// `-`, then strip `-` and `_` from both ends.
+pub fn django_slugify(text: &str, allow_unicode: bool) -> String {
+    let text: String = if allow_unicode {
+        text.nfkc().collect()
+    } else {
+        text.nfkd().filter(char::is_ascii).collect()
+    };
+    let text = DJANGO_REMOVED
+        .replace_all(&text.to_lowercase(), "")
+        .into_owned();
+    DJANGO_SEPARATORS
+        .replace_all(&text, "-")
+        .trim_matches(['-', '_'])
+        .to_string()
+}
+
+#[cfg(test)]
+#[allow(clippy::expect_used)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::slugify::{SlugifyOptions, slugify_with_options_public};
+
+    #[test]
+    fn test_github_anchor_corpus() {
+        let opts = SlugifyOptions::github_anchor();
+        let cases = [
+            ("Hello World", "hello-world"),
+            ("foo_bar", "foo_bar"),
+            ("Foo & Bar", "foo--bar"),
+            ("  Leading space", "--leading-space"),
+            ("Trailing?", "trailing"),
+            ("Em—dash", "emdash"),
+            ("C++ Guide", "c-guide"),
+            ("v1.2.3 release", "v123-release"),
+            ("`code` and *emphasis*", "code-and-emphasis"),
+            ("Déjà vu", "déjà-vu"),
+            ("Hello 👋 World", "hello--world"),
+            ("日本語のガイド", "日本語のガイド"),
+            ("Привет, мир", "привет-мир"),
+            ("pre-existing -- dashes", "pre-existing----dashes"),
+            ("tab\there", "tabhere"),
+        ];
+        for (heading, anchor) in cases {
+            assert_eq!(
+                slugify_with_options_public(&opts, heading),
+                anchor,
+                "{:?}",
+                heading
+            );
+        }
+        assert_eq!(github_anchor("Keep Case", false), "Keep-Case");
+    }
+
+    #[test]
+    fn test_django_parity_corpus() {
+        // Django's own test_slugify cases plus a few transliteration edges.
+        let cases = [
+            ("Hello, World!", "hello-world", false),
+            ("spam & eggs", "spam-eggs", false),
+            (
+                " multiple---dash and  space ",
+                "multiple-dash-and-space",
+                false,
+            ),
+            ("\t whitespace-in-value \n", "whitespace-in-value", false),
+            ("underscore_in-value", "underscore_in-value", false),
+            (
+                "__strip__underscore-value___",
+                "strip__underscore-value",
+                false,
+            ),
+            ("--strip-dash-value---", "strip-dash-value", false),
+            ("__strip-mixed-value---", "strip-mixed-value", false),
+            ("_ -strip-mixed-value _-", "strip-mixed-value", false),
+            ("spam & ıçüş", "spam-ıçüş", true),
+            ("foo ıç bar", "foo-ıç-bar", true),
+            ("    foo ıç bar", "foo-ıç-bar", true),
+            ("你好", "你好", true),
+            ("İstanbul", "istanbul", true),
+            ("spam & ıçüş", "spam-cus", false),
+            ("C'est déjà l'été", "cest-deja-lete", false),
+            ("ﬁnal Ⅻ ²", "final-xii-2", false),
+            ("你好", "", false),
+            ("a\u{1F}b\u{a0}c", "a-b-c", false),
+            ("हिन्दी", "हनद", true),
+            ("1,000 reasons #1", "1000-reasons-1", false),
+        ];
+        for (text, expected, allow_unicode) in cases {
+            let opts = SlugifyOptions::builder()
+                .compat(crate::slugify::Compat::Django)
+                .allow_unicode(allow_unicode)
+                .build()
+                .unwrap();
+            assert_eq!(
+                slugify_with_options_public(&opts, text),
+                expected,
+                "{:?} (allow_unicode={})",
+                text,
+                allow_unicode
+            );
+        }
+    }
+}
@@ -0,0 +1,170 @@
+// The options document: a JSON object naming builder options, shared by the
+// `slugify_server` request bodies and the CLI's `validate-config`. Unknown
+// keys are rejected so a misspelt option fails instead of being ignored.
+use schemars::JsonSchema;
+use serde::Deserialize;
+
+use crate::slugify::{SlugifyError, SlugifyOptions};
+
+/// Options document, e.g. `{"separator": "_", "max_length": 40}`. Absent
+/// keys keep the `SlugifyOptions::builder()` default.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+#[schemars(title = "slugify-rs options")]
+pub struct OptionsDocument {
+    /// Separator placed between words.
+    pub separator: Option<String>,
+    /// Maximum slug length, 0 for no limit.
+    pub max_length: Option<usize>,
+    /// Truncate at word boundaries only.
+    pub word_boundary: Option<bool>,
+    /// Keep the original word order when truncating.
+    pub save_order: Option<bool>,
+    pub lowercase: Option<bool>,
+    /// Keep Unicode letters instead of transliterating to ASCII.
+    pub allow_unicode: Option<bool>,
+    /// Decode HTML named entities.
+    pub entities: Option<bool>,
+    /// Decode decimal character references.
+    pub decimal: Option<bool>,
+    /// Decode hexadecimal character references.
+    pub hexadecimal: Option<bool>,
+    /// Words removed from the slug.
+    pub stopwords: Option<Vec<String>>,
+    /// `[from, to]` pairs replaced before and after sanitization.
+    pub replacements: Option<Vec<(String, String)>>,
+    /// Regex of characters to strip instead of the default set.
+    pub regex_pattern: Option<String>,
+    /// Characters to keep, as a regex-free set ("a-z0-9._"); see
+    /// `charset::CharSet`.
+    pub allowed_chars: Option<String>,
+    /// Characters kept on top of the default set ("._~+").
+    pub allowed_extra_chars: Option<String>,
+}
+
+/// Why an options document was rejected.
+#[derive(Debug)]
+pub enum ConfigError {
+    /// Not JSON, not an object, wrong value types or unknown keys.
+    Json(String),
+    /// The document parsed but the options failed to build.
+    Options(SlugifyError),
+}
+
+impl OptionsDocument {
+    pub fn from_json(json: &str) -> Result<Self, ConfigError> {
+        serde_json::from_str(json).map_err(|e| ConfigError::Json(e.to_string()))
+    }
+
+    pub fn from_value(value: serde_json::Value) -> Result<Self, ConfigError> {
+        serde_json::from_value(value).map_err(|e| ConfigError::Json(e.to_string()))
+    }
+
+    pub fn build(&self) -> Result<SlugifyOptions, SlugifyError> {
+        let mut builder = SlugifyOptions::builder();
+        if let Some(v) = &self.separator {
+            builder = builder.separator(v.clone());
+        }
+        if let Some(v) = self.max_length {
+            builder = builder.max_length(v);
+        }
+        if let Some(v) = self.word_boundary {
+            builder = builder.word_boundary(v);
+        }
+        if let Some(v) = self.save_order {
+            builder = builder.save_order(v);
+        }
+        if let Some(v) = self.lowercase {
+            builder = builder.lowercase(v);
+        }
+        if let Some(v) = self.allow_unicode {
+            builder = builder.allow_unicode(v);
+        }
+        if let Some(v) = self.entities {
+            builder = builder.entities(v);
+        }
+        if let Some(v) = self.decimal {
+            builder = builder.decimal(v);
+        }
+        if let Some(v) = self.hexadecimal {
+            builder = builder.hexadecimal(v);
+        }
+        if let Some(v) = &self.stopwords {
+            builder = builder.stopwords(v.clone());
+        }
+        if let Some(v) = &self.replacements {
+            builder = builder.replacements(v.iter().map(|(a, b)| (a, b)));
+        }
+        if let Some(v) = &self.regex_pattern {
+            builder = builder.regex_pattern(Some(v.clone()));
+        }
+        if let Some(v) = &self.allowed_chars {
+            builder = builder.allowed_chars(Some(v.clone()));
+        }
+        if let Some(v) = &self.allowed_extra_chars {
+            builder = builder.allowed_extra_chars(v);
+        }
+        builder.build()
+    }
+}
+
+/// Parse and build `json` in one step, as `validate-config` does.
+pub fn validate(json: &str) -> Result<SlugifyOptions, ConfigError> {
+    OptionsDocument::from_json(json)?
+        .build()
+        .map_err(ConfigError::Options)
+}
+
+/// JSON Schema (draft 2020-12) of `OptionsDocument`, pretty-printed.
+pub fn schema_json() -> String {
+    let schema = schemars::schema_for!(OptionsDocument);
+    serde_json::to_string_pretty(&schema).unwrap_or_default()
+}
+
+#[cfg(test)]
+#[allow(clippy::expect_used)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::slugify::slugify_with_options_public;
+
+    #[test]
+    fn test_validate() {
+        let opts = validate(
+            r#"{"separator": "_", "max_length": 11, "stopwords": ["the"],
+                "replacements": [["fox", "cat"]]}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            slugify_with_options_public(&opts, "The quick brown fox"),
+            "quick_brown"
+        );
+        assert!(validate("{}").is_ok());
+        for bad in [
+            "[]",
+            r#"{"separater": "_"}"#,
+            r#"{"max_length": -1}"#,
+            r#"{"replacements": [["only-one"]]}"#,
+        ] {
+            assert!(matches!(validate(bad), Err(ConfigError::Json(_))), "{}", bad);
+        }
+        assert!(matches!(
+            validate(r#"{"regex_pattern": "("}"#),
+            Err(ConfigError::Options(SlugifyError::InvalidRegex(_)))
+        ));
+        assert!(matches!(
+            validate(r#"{"allowed_chars": "z-a"}"#),
+            Err(ConfigError::Options(SlugifyError::InvalidCharSet(_)))
+        ));
+    }
+
+    #[test]
+    fn test_schema_lists_every_key() {
+        let schema: serde_json::Value = serde_json::from_str(&schema_json()).unwrap();
+        assert_eq!(schema["additionalProperties"], false);
+        let props = schema["properties"].as_object().unwrap();
+        assert_eq!(props.len(), 14);
+        assert!(props.contains_key("max_length"));
+        assert!(props.contains_key("replacements"));
+    }
+}
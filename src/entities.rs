@@ -0,0 +1,573 @@
+// Character reference decoding: named (`&eacute;`), decimal (`&#233;`) and
+// hexadecimal (`&#xE9;`) references, in a single scan of the text. With the
+// `html5_entities` feature (on by default) the full HTML5 table of
+// `html_escape` is used for names; without it, `MINIMAL_ENTITIES` covers
+// the references common in titles (punctuation, symbols, Latin-1 letters)
+// without the extra dependency.
+use std::borrow::Cow;
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+use crate::slugify::{DecodedSeparators, SlugifyError, SlugifyOptions};
+use crate::tables::{Keys, Values, check_sorted, check_translations};
+
+/// How `&name;` references are handled while `entities` decoding is on
+/// (see `SlugifyOptionsBuilder::entity_policy`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum EntityPolicy {
+    /// Decode known references; unknown ones stay text, so `&bogus;` ends
+    /// up as the word "bogus".
+    #[default]
+    Decode,
+    /// Leave every reference undecoded, like `entities(false)`.
+    PassThrough,
+    /// Remove every named reference, known or not.
+    StripAll,
+    /// Decode known references and reject unknown ones:
+    /// `slugify::try_slugify_with_options` fails with
+    /// `SlugifyError::UnknownEntity`, the infallible functions fall back to
+    /// `Decode`.
+    Error,
+}
+
+/// Entities known to the minimal backend, sorted by name (case-sensitive,
+/// without `&` and `;`).
+pub static MINIMAL_ENTITIES: &[(&str, &str)] = &[
+    ("AElig", "Æ"),
+    ("Aacute", "Á"),
+    ("Acirc", "Â"),
+    ("Agrave", "À"),
+    ("Aring", "Å"),
+    ("Atilde", "Ã"),
+    ("Auml", "Ä"),
+    ("Ccedil", "Ç"),
+    ("Dagger", "‡"),
+    ("ETH", "Ð"),
+    ("Eacute", "É"),
+    ("Ecirc", "Ê"),
+    ("Egrave", "È"),
+    ("Euml", "Ë"),
+    ("Iacute", "Í"),
+    ("Icirc", "Î"),
+    ("Igrave", "Ì"),
+    ("Iuml", "Ï"),
+    ("Ntilde", "Ñ"),
+    ("OElig", "Œ"),
+    ("Oacute", "Ó"),
+    ("Ocirc", "Ô"),
+    ("Ograve", "Ò"),
+    ("Oslash", "Ø"),
+    ("Otilde", "Õ"),
+    ("Ouml", "Ö"),
+    ("Prime", "″"),
+    ("Scaron", "Š"),
+    ("THORN", "Þ"),
+    ("Uacute", "Ú"),
+    ("Ucirc", "Û"),
+    ("Ugrave", "Ù"),
+    ("Uuml", "Ü"),
+    ("Yacute", "Ý"),
+    ("Yuml", "Ÿ"),
+    ("aacute", "á"),
+    ("acirc", "â"),
+    ("aelig", "æ"),
+    ("agrave", "à"),
+    ("amp", "&"),
+    ("apos", "'"),
+    ("aring", "å"),
+    ("atilde", "ã"),
+    ("auml", "ä"),
+    ("bdquo", "„"),
+    ("bull", "•"),
+    ("ccedil", "ç"),
+    ("cent", "¢"),
+    ("copy", "©"),
+    ("curren", "¤"),
+    ("dagger", "†"),
+    ("darr", "↓"),
+    ("deg", "°"),
+    ("divide", "÷"),
+    ("eacute", "é"),
+    ("ecirc", "ê"),
+    ("egrave", "è"),
+    ("emsp", "\u{2003}"),
+    ("ensp", "\u{2002}"),
+    ("eth", "ð"),
+    ("euml", "ë"),
+    ("euro", "€"),
+    ("frac12", "½"),
+    ("frac14", "¼"),
+    ("frac34", "¾"),
+    ("ge", "≥"),
+    ("gt", ">"),
+    ("harr", "↔"),
+    ("hearts", "♥"),
+    ("hellip", "…"),
+    ("iacute", "í"),
+    ("icirc", "î"),
+    ("iexcl", "¡"),
+    ("igrave", "ì"),
+    ("infin", "∞"),
+    ("iquest", "¿"),
+    ("iuml", "ï"),
+    ("laquo", "«"),
+    ("larr", "←"),
+    ("ldquo", "“"),
+    ("le", "≤"),
+    ("lrm", "\u{200e}"),
+    ("lsaquo", "‹"),
+    ("lsquo", "‘"),
+    ("lt", "<"),
+    ("mdash", "—"),
+    ("micro", "µ"),
+    ("middot", "·"),
+    ("minus", "−"),
+    ("nbsp", "\u{a0}"),
+    ("ndash", "–"),
+    ("ne", "≠"),
+    ("not", "¬"),
+    ("ntilde", "ñ"),
+    ("oacute", "ó"),
+    ("ocirc", "ô"),
+    ("oelig", "œ"),
+    ("ograve", "ò"),
+    ("ordf", "ª"),
+    ("ordm", "º"),
+    ("oslash", "ø"),
+    ("otilde", "õ"),
+    ("ouml", "ö"),
+    ("para", "¶"),
+    ("permil", "‰"),
+    ("plusmn", "±"),
+    ("pound", "£"),
+    ("prime", "′"),
+    ("quot", "\""),
+    ("raquo", "»"),
+    ("rarr", "→"),
+    ("rdquo", "”"),
+    ("reg", "®"),
+    ("rlm", "\u{200f}"),
+    ("rsaquo", "›"),
+    ("rsquo", "’"),
+    ("sbquo", "‚"),
+    ("scaron", "š"),
+    ("sect", "§"),
+    ("shy", "\u{ad}"),
+    ("sup1", "¹"),
+    ("sup2", "²"),
+    ("sup3", "³"),
+    ("szlig", "ß"),
+    ("thinsp", "\u{2009}"),
+    ("thorn", "þ"),
+    ("times", "×"),
+    ("trade", "™"),
+    ("uacute", "ú"),
+    ("uarr", "↑"),
+    ("ucirc", "û"),
+    ("ugrave", "ù"),
+    ("uuml", "ü"),
+    ("yacute", "ý"),
+    ("yen", "¥"),
+    ("yuml", "ÿ"),
+    ("zwj", "\u{200d}"),
+    ("zwnj", "\u{200c}"),
+];
+const _: () = check_sorted(MINIMAL_ENTITIES);
+const _: () = check_translations(MINIMAL_ENTITIES, Keys::Exact, Values::Text);
+
+/// Text of the named reference `name` (`"eacute"` -> `"é"`) in the
+/// minimal table.
+pub fn minimal_entity(name: &str) -> Option<&'static str> {
+    MINIMAL_ENTITIES
+        .binary_search_by_key(&name, |(n, _)| n)
+        .ok()
+        .map(|i| MINIMAL_ENTITIES[i].1)
+}
+
+/// Text of the named reference `name` in the active backend.
+pub fn named_entity(name: &str) -> Option<Cow<'static, str>> {
+    #[cfg(feature = "html5_entities")]
+    {
+        let full = format!("&{};", name);
+        match html_escape::decode_html_entities(&full) {
+            Cow::Owned(s) if s != full => Some(Cow::Owned(s)),
+            _ => None,
+        }
+    }
+    #[cfg(not(feature = "html5_entities"))]
+    {
+        minimal_entity(name).map(Cow::Borrowed)
+    }
+}
+
+/// Replace `&name;` references known to the active backend; unknown ones
+/// are kept as they are.
+pub fn decode_named_entities(s: &str) -> String {
+    decode(s, &EntityOptions::named_only(EntityPolicy::Decode)).into_owned()
+}
+
+/// `decode_named_entities` under `policy`. Only `EntityPolicy::Error`
+/// fails, with the first unknown reference (`"&bogus;"`).
+pub fn decode_named_entities_with(s: &str, policy: EntityPolicy) -> Result<String, SlugifyError> {
+    try_decode(s, &EntityOptions::named_only(policy)).map(Cow::into_owned)
+}
+
+/// Which references `decode` replaces. The fields mirror the
+/// `SlugifyOptions` ones, and the default matches the builder: every kind
+/// decoded, unknown names kept.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EntityOptions {
+    /// Decode `&name;` references under `policy`.
+    pub named: bool,
+    pub policy: EntityPolicy,
+    /// Decode `&#233;`.
+    pub decimal: bool,
+    /// Decode `&#xE9;` (lowercase `x` only).
+    pub hexadecimal: bool,
+    /// Handling of whitespace and punctuation decoded from numeric
+    /// references.
+    pub decoded_separators: DecodedSeparators,
+}
+
+impl Default for EntityOptions {
+    fn default() -> Self {
+        EntityOptions {
+            named: true,
+            policy: EntityPolicy::default(),
+            decimal: true,
+            hexadecimal: true,
+            decoded_separators: DecodedSeparators::default(),
+        }
+    }
+}
+
+impl From<&SlugifyOptions> for EntityOptions {
+    fn from(opts: &SlugifyOptions) -> Self {
+        EntityOptions {
+            named: opts.entities,
+            policy: opts.entity_policy,
+            decimal: opts.decimal,
+            hexadecimal: opts.hexadecimal,
+            decoded_separators: opts.decoded_separators,
+        }
+    }
+}
+
+impl EntityOptions {
+    fn named_only(policy: EntityPolicy) -> Self {
+        EntityOptions {
+            named: true,
+            policy,
+            decimal: false,
+            hexadecimal: false,
+            decoded_separators: DecodedSeparators::default(),
+        }
+    }
+}
+
+/// Decode the references selected by `opts` in one pass. Text without `&`
+/// is returned borrowed. `EntityPolicy::Error` behaves like `Decode`; use
+/// `try_decode` to reject unknown names.
+///
+/// A `&` produced by a reference can start a reference of a later kind
+/// (named, then decimal, then hexadecimal), so double-encoded text such as
+/// `"&amp;#39;"` still decodes to `"'"`.
+pub fn decode<'a>(text: &'a str, opts: &EntityOptions) -> Cow<'a, str> {
+    let lenient = EntityOptions {
+        policy: match opts.policy {
+            EntityPolicy::Error => EntityPolicy::Decode,
+            policy => policy,
+        },
+        ..*opts
+    };
+    match try_decode(text, &lenient) {
+        Ok(decoded) => decoded,
+        Err(_) => Cow::Borrowed(text),
+    }
+}
+
+/// `decode`, failing with `SlugifyError::UnknownEntity` on the first
+/// unknown name under `EntityPolicy::Error`.
+pub fn try_decode<'a>(text: &'a str, opts: &EntityOptions) -> Result<Cow<'a, str>, SlugifyError> {
+    let named = opts.named && opts.policy != EntityPolicy::PassThrough;
+    if !(named || opts.decimal || opts.hexadecimal) || !text.contains('&') {
+        return Ok(Cow::Borrowed(text));
+    }
+    let mut out = Output {
+        text: String::with_capacity(text.len()),
+        amp: None,
+        tail_kind: None,
+    };
+    let mut rest = text;
+    while let Some(i) = rest.find('&') {
+        out.text.push_str(&rest[..i]);
+        rest = &rest[i + 1..];
+        match parse_reference(rest, opts, None)? {
+            Some((kind, len, decoded)) => {
+                rest = &rest[len..];
+                out.push_decoded(kind, &decoded);
+                rest = out.rejoin(rest, opts)?;
+            }
+            None => {
+                out.amp = Some(out.text.len());
+                out.tail_kind = None;
+                out.text.push('&');
+            }
+        }
+    }
+    out.text.push_str(rest);
+    Ok(Cow::Owned(out.text))
+}
+
+// Reference kinds in the order they used to be decoded (one pass each).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Kind {
+    Named,
+    Decimal,
+    Hex,
+}
+
+// Decoded text. Decoding used to take one pass per kind, so a reference of
+// a later kind could be assembled from the output of an earlier one
+// (`"&amp;#65;"`, `"&#65&semi;"`). `amp` is the last `&` of the text and
+// `tail_kind` the latest kind that produced part of the text after it.
+struct Output {
+    text: String,
+    amp: Option<usize>,
+    tail_kind: Option<Kind>,
+}
+
+impl Output {
+    fn push_decoded(&mut self, kind: Kind, decoded: &str) {
+        match decoded.rfind('&') {
+            Some(i) => {
+                self.amp = Some(self.text.len() + i);
+                self.tail_kind = Some(kind);
+            }
+            None => self.tail_kind = self.tail_kind.max(Some(kind)),
+        }
+        self.text.push_str(decoded);
+    }
+
+    // Decode a reference formed by the text after `amp` and the start of
+    // `rest`, as long as one of a later kind than its parts can be formed.
+    fn rejoin<'a>(
+        &mut self,
+        mut rest: &'a str,
+        opts: &EntityOptions,
+    ) -> Result<&'a str, SlugifyError> {
+        while let (Some(amp), Some(after)) = (self.amp, self.tail_kind) {
+            let tail = &self.text[amp + 1..];
+            let body = tail.strip_suffix(';').unwrap_or(tail);
+            let numeric = body
+                .strip_prefix('#')
+                .is_some_and(|digits| digits.chars().all(char::is_alphanumeric));
+            if !(tail.is_empty() || numeric) {
+                break;
+            }
+            let end = match rest.find(|c: char| !c.is_alphanumeric() && c != '#') {
+                Some(i) if rest[i..].starts_with(';') => i + 1,
+                _ => 0,
+            };
+            let candidate = format!("{}{}", tail, &rest[..end]);
+            let Some((kind, len, decoded)) = parse_reference(&candidate, opts, Some(after))? else {
+                break;
+            };
+            if len < tail.len() {
+                break;
+            }
+            rest = &rest[len - tail.len()..];
+            self.text.truncate(amp);
+            self.amp = None;
+            self.push_decoded(kind, &decoded);
+        }
+        Ok(rest)
+    }
+}
+
+// Matches `\d` in the former reference patterns, which accepted any
+// Unicode decimal digit (references with non-ASCII digits are removed).
+#[allow(clippy::unwrap_used)]
+static UNICODE_DIGIT: Lazy<Regex> = Lazy::new(|| Regex::new(r"^\d$").unwrap());
+
+fn is_digit(c: char) -> bool {
+    c.is_ascii_digit() || (!c.is_ascii() && UNICODE_DIGIT.is_match(c.encode_utf8(&mut [0; 4])))
+}
+
+// Length in bytes of the leading run of `rest` matching `pred`, if it is
+// non-empty and followed by `;`.
+fn run_before_semicolon(rest: &str, pred: impl Fn(char) -> bool) -> Option<usize> {
+    let end = rest.find(|c: char| !pred(c)).unwrap_or(rest.len());
+    (end > 0 && rest[end..].starts_with(';')).then_some(end)
+}
+
+// The reference starting right after a `&`: its kind, its length (without
+// the `&`) and its replacement. With `after`, only kinds decoded later than
+// `after` are recognized.
+fn parse_reference(
+    rest: &str,
+    opts: &EntityOptions,
+    after: Option<Kind>,
+) -> Result<Option<(Kind, usize, String)>, SlugifyError> {
+    let allowed = |kind: Kind| after.is_none_or(|a| kind > a);
+    if let Some(body) = rest.strip_prefix("#x")
+        && opts.hexadecimal
+        && allowed(Kind::Hex)
+        && let Some(end) = run_before_semicolon(body, |c| c.is_ascii_hexdigit() || is_digit(c))
+    {
+        let c = u32::from_str_radix(&body[..end], 16)
+            .ok()
+            .and_then(char::from_u32);
+        return Ok(Some((Kind::Hex, end + 3, numeric_text(c, opts))));
+    }
+    if let Some(body) = rest.strip_prefix('#') {
+        if opts.decimal
+            && allowed(Kind::Decimal)
+            && let Some(end) = run_before_semicolon(body, is_digit)
+        {
+            let c = body[..end].parse::<u32>().ok().and_then(char::from_u32);
+            return Ok(Some((Kind::Decimal, end + 2, numeric_text(c, opts))));
+        }
+        return Ok(None);
+    }
+    if !opts.named || !allowed(Kind::Named) {
+        return Ok(None);
+    }
+    let Some(end) = run_before_semicolon(rest, |c| c.is_ascii_alphanumeric()) else {
+        return Ok(None);
+    };
+    let text = match opts.policy {
+        EntityPolicy::PassThrough => return Ok(None),
+        EntityPolicy::StripAll => String::new(),
+        EntityPolicy::Decode => match named_entity(&rest[..end]) {
+            Some(text) => text.into_owned(),
+            None => return Ok(None),
+        },
+        EntityPolicy::Error => match named_entity(&rest[..end]) {
+            Some(text) => text.into_owned(),
+            None => return Err(SlugifyError::UnknownEntity(format!("&{};", &rest[..end]))),
+        },
+    };
+    Ok(Some((Kind::Named, end + 1, text)))
+}
+
+// Replacement of a numeric reference; invalid code points are removed.
+// Decoded whitespace becomes a plain space so it splits words even where
+// later steps drop control characters such as `&#9;`.
+fn numeric_text(c: Option<char>, opts: &EntityOptions) -> String {
+    let decoded = c.and_then(|c| match opts.decoded_separators {
+        DecodedSeparators::Split if c.is_whitespace() => Some(' '),
+        DecodedSeparators::Strip if DECODED_SEPARATOR_PATTERN.is_match(&c.to_string()) => None,
+        _ => Some(c),
+    });
+    decoded.map(String::from).unwrap_or_default()
+}
+
+// Characters removed by `DecodedSeparators::Strip`.
+#[allow(clippy::unwrap_used)]
+static DECODED_SEPARATOR_PATTERN: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^[\s\p{Cc}\p{Cf}\p{P}[[:punct:]]]$").unwrap());
+
+#[cfg(test)]
+#[allow(clippy::expect_used)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_minimal_table() {
+        assert!(MINIMAL_ENTITIES.windows(2).all(|w| w[0].0 < w[1].0));
+        assert_eq!(minimal_entity("eacute"), Some("é"));
+        assert_eq!(minimal_entity("Eacute"), Some("É"));
+        assert_eq!(minimal_entity("nbsp"), Some("\u{a0}"));
+        assert_eq!(minimal_entity("bogus"), None);
+    }
+
+    #[test]
+    fn test_backends_agree_on_minimal_table() {
+        for (name, text) in MINIMAL_ENTITIES {
+            assert_eq!(named_entity(name).as_deref(), Some(*text), "{}", name);
+        }
+    }
+
+    #[test]
+    fn test_decode_named_entities() {
+        assert_eq!(
+            decode_named_entities("Caf&eacute; &amp; cr&egrave;me &bogus; &amp"),
+            "Café & crème &bogus; &amp"
+        );
+        assert_eq!(
+            named_entity("alpha").is_some(),
+            cfg!(feature = "html5_entities")
+        );
+    }
+
+    #[test]
+    fn test_entity_policies() {
+        let s = "a&amp;b &bogus; c";
+        let with = |p| decode_named_entities_with(s, p);
+        assert_eq!(with(EntityPolicy::Decode).unwrap(), "a&b &bogus; c");
+        assert_eq!(with(EntityPolicy::PassThrough).unwrap(), s);
+        assert_eq!(with(EntityPolicy::StripAll).unwrap(), "ab  c");
+        assert!(matches!(
+            with(EntityPolicy::Error),
+            Err(SlugifyError::UnknownEntity(e)) if e == "&bogus;"
+        ));
+        assert_eq!(
+            decode_named_entities_with("&eacute;t&eacute;", EntityPolicy::Error).unwrap(),
+            "été"
+        );
+    }
+
+    #[test]
+    fn test_decode() {
+        let opts = EntityOptions::default();
+        assert!(matches!(decode("no references", &opts), Cow::Borrowed(_)));
+        assert!(matches!(decode("a & b; c", &opts), Cow::Owned(s) if s == "a & b; c"));
+        assert_eq!(
+            decode("&eacute;&#233;&#xE9;&#xe9 &bogus;", &opts),
+            "ééé&#xe9 &bogus;"
+        );
+        let numeric = EntityOptions {
+            named: false,
+            ..opts
+        };
+        assert_eq!(decode("&eacute;&#233;", &numeric), "&eacute;é");
+        // invalid code points and non-ASCII digits are removed
+        assert_eq!(decode("a&#1114112;b&#\u{663};c", &opts), "abc");
+        assert_eq!(decode("a&#9;b&#44;c", &opts), "a b,c");
+        let strip = EntityOptions {
+            decoded_separators: DecodedSeparators::Strip,
+            ..opts
+        };
+        assert_eq!(decode("a&#9;b&#44;c&amp;d", &strip), "abc&d");
+        assert!(matches!(
+            try_decode("&bogus;", &EntityOptions { policy: EntityPolicy::Error, ..opts }),
+            Err(SlugifyError::UnknownEntity(e)) if e == "&bogus;"
+        ));
+        assert_eq!(
+            decode(
+                "&bogus;",
+                &EntityOptions {
+                    policy: EntityPolicy::Error,
+                    ..opts
+                }
+            ),
+            "&bogus;"
+        );
+    }
+
+    #[test]
+    fn test_decode_double_encoded() {
+        let opts = EntityOptions::default();
+        // a reference assembled from decoded text is decoded when its kind
+        // comes later (named, decimal, hexadecimal)
+        assert_eq!(decode("&amp;#65;", &opts), "A");
+        assert_eq!(decode("&#38;#x41;", &opts), "A");
+        assert_eq!(decode("&&#35;x41;", &opts), "A");
+        assert_eq!(decode("&#x41&#59;", &opts), "A");
+        assert_eq!(decode("&#38;#65;", &opts), "&#65;");
+        assert_eq!(decode("&amp;amp;", &opts), "&amp;");
+    }
+}
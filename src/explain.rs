@@ -0,0 +1,146 @@
+// "Why did my slug come out like this": the text before and after each
+// pipeline stage, so a surprising slug can be blamed on entity decoding,
+// transliteration, stopword removal, ... Inside the pipeline words may be
+// joined with U+001F instead of `-` (`SeparatorScope::Inserted`); the
+// requested separator only appears after the `finish` stage.
+use std::fmt;
+
+use crate::pipeline::BuiltinStage;
+use crate::slugify::{
+    Compat, SlugifyOptions, finish_slug, internal_separator, prepare_input, run_step,
+};
+
+/// Input and output of one stage.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StageTrace {
+    /// `prepare`, a `BuiltinStage` name, `finish`, or `compat` when a
+    /// `compat` mode replaces the pipeline.
+    pub stage: &'static str,
+    pub input: String,
+    pub output: String,
+}
+
+impl StageTrace {
+    pub fn changed(&self) -> bool {
+        self.input != self.output
+    }
+}
+
+/// Every stage `slugify_explain` ran, in order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SlugTrace {
+    /// Same as `slugify_with_options_public(opts, text)`.
+    pub slug: String,
+    pub stages: Vec<StageTrace>,
+}
+
+impl SlugTrace {
+    /// The stages that changed the text.
+    pub fn changes(&self) -> impl Iterator<Item = &StageTrace> {
+        self.stages.iter().filter(|stage| stage.changed())
+    }
+}
+
+/// One line per stage that changed the text: `stopwords: "the-fox" -> "fox"`.
+impl fmt::Display for SlugTrace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for stage in self.changes() {
+            writeln!(
+                f,
+                "{}: {:?} -> {:?}",
+                stage.stage, stage.input, stage.output
+            )?;
+        }
+        write!(f, "slug: {:?}", self.slug)
+    }
+}
+
+/// Slugify `text` and record the text before and after every stage.
+///
+/// `slugify_explain("The &amp; Fox", &opts)` with stopwords `["the"]` shows
+/// `entities` turning "The &amp; Fox" into "The & Fox" and `stopwords`
+/// turning "the-fox" into "fox".
+pub fn slugify_explain(text: &str, opts: &SlugifyOptions) -> SlugTrace {
+    let mut stages = Vec::new();
+    let mut record = |stage: &'static str, input: &str, output: String| {
+        stages.push(StageTrace {
+            stage,
+            input: input.to_string(),
+            output: output.clone(),
+        });
+        output
+    };
+    let slug = if opts.compat != Compat::None {
+        record(
+            "compat",
+            text,
+            crate::slugify::slugify_with_options_public(opts, text),
+        )
+    } else {
+        let sep = internal_separator(opts);
+        let mut current = record("prepare", text, prepare_input(text, sep).into_owned());
+        for step in BuiltinStage::ALL {
+            // only `strict` runs can fail
+            let output = run_step(step, &current, opts, sep, false).unwrap_or_default();
+            current = record(step.as_str(), &current, output);
+        }
+        record("finish", &current, finish_slug(&current, sep, opts))
+    };
+    SlugTrace { slug, stages }
+}
+
+#[cfg(test)]
+#[allow(clippy::expect_used)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::slugify::slugify_with_options_public;
+
+    #[test]
+    fn test_explain_stages() {
+        let opts = SlugifyOptions::builder()
+            .stopwords(["the"])
+            .separator("_")
+            .build()
+            .unwrap();
+        let trace = slugify_explain("The &amp; Café Fox", &opts);
+        assert_eq!(trace.slug, "cafe_fox");
+        assert_eq!(
+            trace.slug,
+            slugify_with_options_public(&opts, "The &amp; Café Fox")
+        );
+        let names: Vec<&str> = trace.stages.iter().map(|s| s.stage).collect();
+        assert_eq!(names.len(), BuiltinStage::ALL.len() + 2);
+        assert_eq!((names[0], names[names.len() - 1]), ("prepare", "finish"));
+
+        let stage = |name: &str| trace.stages.iter().find(|s| s.stage == name).unwrap();
+        assert_eq!(stage("normalize").output, "The &amp; Cafe Fox");
+        assert_eq!(stage("entities").output, "The & Cafe Fox");
+        assert_eq!(stage("stopwords").input, "the-cafe-fox");
+        assert_eq!(stage("stopwords").output, "cafe-fox");
+        assert!(!stage("truncate").changed());
+        // each stage starts from the previous output
+        for pair in trace.stages.windows(2) {
+            assert_eq!(pair[0].output, pair[1].input);
+        }
+        assert!(trace.changes().all(|s| s.stage != "quotes"));
+        assert!(
+            trace
+                .to_string()
+                .contains("stopwords: \"the-cafe-fox\" -> \"cafe-fox\"\n")
+        );
+        assert!(trace.to_string().ends_with("slug: \"cafe_fox\""));
+    }
+
+    #[test]
+    fn test_explain_compat() {
+        let opts = SlugifyOptions::builder()
+            .compat(Compat::GitHub)
+            .build()
+            .unwrap();
+        let trace = slugify_explain("Hello, World!", &opts);
+        assert_eq!(trace.slug, "hello-world");
+        assert_eq!(trace.stages.len(), 1);
+        assert_eq!(trace.stages[0].stage, "compat");
+    }
+}
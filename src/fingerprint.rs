@@ -0,0 +1,269 @@
+// Fingerprints of the tables and behavior that determine slug output, so
+// services can check at startup that every instance slugifies identically.
+use crate::slugify::{
+    CHAR_ENTITY_PATTERN, DECIMAL_PATTERN, DISALLOWED_CHARS_PATTERN,
+    DISALLOWED_UNICODE_CHARS_PATTERN, DUPLICATE_DASH_PATTERN, HEX_PATTERN, NUMERIC_ID_PATTERN,
+    QUOTE_PATTERN, normalize_and_transliterate, slugify_default,
+};
+use crate::entities::{MINIMAL_ENTITIES, named_entity};
+use crate::special::{
+    ABBREVIATIONS, CJK_PUNCTUATION, CJK_QUOTES, Locale, PRE_TRANSLATIONS, small_words,
+    symbol_words,
+};
+
+/// Version of the slugification algorithm. Bumped whenever a change is
+/// expected to alter the output for existing inputs.
+pub const ALGORITHM_VERSION: u32 = 6;
+
+/// Cargo features this build of the crate was compiled with.
+pub const ENABLED_FEATURES: &[&str] = &[
+    #[cfg(feature = "cli")]
+    "cli",
+    #[cfg(feature = "python")]
+    "python",
+    #[cfg(feature = "stemming")]
+    "stemming",
+    #[cfg(feature = "serde")]
+    "serde",
+    #[cfg(feature = "any_ascii")]
+    "any_ascii",
+    #[cfg(feature = "icu")]
+    "icu",
+    #[cfg(feature = "html5_entities")]
+    "html5_entities",
+    #[cfg(feature = "emoji_names")]
+    "emoji_names",
+    #[cfg(feature = "tech_terms")]
+    "tech_terms",
+    #[cfg(feature = "unicode_security")]
+    "unicode_security",
+    #[cfg(feature = "segmenter")]
+    "segmenter",
+];
+
+// Inputs run through the transliteration backend and the default pipeline.
+// Their output captures behavior that lives in dependencies (deunicode,
+// unicode-normalization) or in code rather than in tables.
+const PROBES: &[&str] = &[
+    "C'est déjà l'été.",
+    "İSTANBUL ΣΊΣΥΦΟΣ straße",
+    "Ǆemal ﬁnal Ⅻ",
+    "Компьютер Ёжик",
+    "日本語 العربية עברית",
+    "I ♥ 🚀 🦄",
+    "foo &amp; bar &#381; &#x17D;",
+    "1,000 reasons you are #1",
+];
+
+// 64-bit FNV-1a: simple, dependency-free and identical on every platform
+// (unlike `DefaultHasher`, whose algorithm is not guaranteed to be stable).
+pub(crate) struct Fnv1a(u64);
+
+impl Fnv1a {
+    pub(crate) fn new() -> Self {
+        Fnv1a(0xcbf2_9ce4_8422_2325)
+    }
+
+    pub(crate) fn write(&mut self, bytes: &[u8]) {
+        for b in bytes {
+            self.0 ^= u64::from(*b);
+            self.0 = self.0.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+
+    // Length-prefix strings so ("ab", "c") and ("a", "bc") hash differently.
+    pub(crate) fn write_str(&mut self, s: &str) {
+        self.write(&(s.len() as u64).to_le_bytes());
+        self.write(s.as_bytes());
+    }
+
+    pub(crate) fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn hex(&self) -> String {
+        format!("{:016x}", self.0)
+    }
+}
+
+fn hash_strs<'a, I: IntoIterator<Item = &'a str>>(items: I) -> String {
+    let mut h = Fnv1a::new();
+    for s in items {
+        h.write_str(s);
+    }
+    h.hex()
+}
+
+/// Fingerprint of each table / behavior that influences slug output, as
+/// `(name, 16 hex digits)` pairs in a fixed order.
+pub fn table_fingerprints() -> Vec<(&'static str, String)> {
+    let locales = [Locale::En, Locale::De, Locale::Es, Locale::Fr, Locale::Nl];
+    let patterns = [
+        CHAR_ENTITY_PATTERN.as_str(),
+        DECIMAL_PATTERN.as_str(),
+        HEX_PATTERN.as_str(),
+        QUOTE_PATTERN.as_str(),
+        DISALLOWED_CHARS_PATTERN.as_str(),
+        DISALLOWED_UNICODE_CHARS_PATTERN.as_str(),
+        DUPLICATE_DASH_PATTERN.as_str(),
+        NUMERIC_ID_PATTERN.as_str(),
+    ];
+    let transliterated: Vec<String> = PROBES
+        .iter()
+        .flat_map(|p| {
+            [
+                normalize_and_transliterate(p, false),
+                normalize_and_transliterate(p, true),
+                p.to_lowercase(),
+            ]
+        })
+        .collect();
+    let symbols: Vec<String> = locales
+        .iter()
+        .flat_map(|l| symbol_words(*l).iter())
+        .flat_map(|(c, w)| [c.to_string(), w.to_string()])
+        .collect();
+    let chars = |table: &[char]| table.iter().map(|c| c.to_string()).collect::<Vec<_>>();
+    let (cjk_punctuation, cjk_quotes) = (chars(CJK_PUNCTUATION), chars(CJK_QUOTES));
+    let slugs: Vec<String> = PROBES.iter().map(|p| slugify_default(p)).collect();
+    // decoded by the active backend (minimal table or full HTML5 table)
+    let entities: Vec<String> = MINIMAL_ENTITIES
+        .iter()
+        .map(|(name, _)| *name)
+        .chain(["alpha", "check", "hearts", "NotEqual"])
+        .map(|name| named_entity(name).unwrap_or_default().into_owned())
+        .collect();
+
+    #[cfg_attr(not(feature = "tech_terms"), allow(unused_mut))]
+    let mut tables = vec![
+        (
+            "pre_translations",
+            hash_strs(PRE_TRANSLATIONS.iter().flat_map(|(a, b)| [*a, *b])),
+        ),
+        (
+            "abbreviations",
+            hash_strs(ABBREVIATIONS.iter().flat_map(|(a, b)| [*a, *b])),
+        ),
+        (
+            "small_words",
+            hash_strs(locales.iter().flat_map(|l| small_words(*l).iter().copied())),
+        ),
+        (
+            "symbol_words",
+            hash_strs(symbols.iter().map(|s| s.as_str())),
+        ),
+        ("patterns", hash_strs(patterns)),
+        (
+            "cjk_punctuation",
+            hash_strs(cjk_punctuation.iter().map(|s| s.as_str())),
+        ),
+        (
+            "cjk_quotes",
+            hash_strs(cjk_quotes.iter().map(|s| s.as_str())),
+        ),
+        (
+            "entities",
+            hash_strs(entities.iter().map(|s| s.as_str())),
+        ),
+        (
+            "transliteration",
+            hash_strs(transliterated.iter().map(|s| s.as_str())),
+        ),
+        ("pipeline", hash_strs(slugs.iter().map(|s| s.as_str()))),
+    ];
+    #[cfg(feature = "tech_terms")]
+    tables.push((
+        "tech_terms",
+        hash_strs(crate::special::TECH_TERMS.iter().flat_map(|(a, b)| [*a, *b])),
+    ));
+    tables
+}
+
+/// Single fingerprint combining `ALGORITHM_VERSION` and every entry of
+/// `table_fingerprints()`. Two builds that report the same value produce
+/// the same slugs for the built-in tables and probes.
+pub fn algorithm_fingerprint() -> String {
+    let mut h = Fnv1a::new();
+    h.write(&ALGORITHM_VERSION.to_le_bytes());
+    for (name, fp) in table_fingerprints() {
+        h.write_str(name);
+        h.write_str(&fp);
+    }
+    h.hex()
+}
+
+/// Crate version, `ENABLED_FEATURES`, `ALGORITHM_VERSION` and the
+/// fingerprints as a single-line JSON object, for deployment tooling that
+/// compares builds:
+///
+/// `{"version":"..","features":[..],"algorithm_version":3,"algorithm_fingerprint":"..","tables":{"pre_translations":"..",..}}`
+pub fn build_info_json() -> String {
+    let quote = |s: &str| format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""));
+    let features: Vec<String> = ENABLED_FEATURES.iter().map(|f| quote(f)).collect();
+    let tables: Vec<String> = table_fingerprints()
+        .iter()
+        .map(|(name, fp)| format!("{}:{}", quote(name), quote(fp)))
+        .collect();
+    format!(
+        "{{\"version\":{},\"features\":[{}],\"algorithm_version\":{},\"algorithm_fingerprint\":{},\"tables\":{{{}}}}}",
+        quote(env!("CARGO_PKG_VERSION")),
+        features.join(","),
+        ALGORITHM_VERSION,
+        quote(&algorithm_fingerprint()),
+        tables.join(",")
+    )
+}
+
+#[cfg(test)]
+#[allow(clippy::expect_used)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fnv1a_known_vectors() {
+        // reference values from the FNV specification
+        let mut h = Fnv1a::new();
+        assert_eq!(h.hex(), "cbf29ce484222325");
+        h.write(b"a");
+        assert_eq!(h.hex(), "af63dc4c8601ec8c");
+    }
+
+    #[test]
+    fn test_algorithm_fingerprint_is_stable_hex() {
+        let fp = algorithm_fingerprint();
+        assert_eq!(fp.len(), 16);
+        assert!(fp.chars().all(|c| c.is_ascii_hexdigit()));
+        assert_eq!(fp, algorithm_fingerprint());
+        let names: Vec<&str> = table_fingerprints().iter().map(|(n, _)| *n).collect();
+        assert!(names.contains(&"pipeline"));
+    }
+
+    #[test]
+    fn test_build_info_json() {
+        let json = build_info_json();
+        assert!(json.starts_with(&format!("{{\"version\":\"{}\",", env!("CARGO_PKG_VERSION"))));
+        assert!(json.contains(&format!("\"algorithm_version\":{},", ALGORITHM_VERSION)));
+        assert!(json.contains(&format!("\"algorithm_fingerprint\":\"{}\"", algorithm_fingerprint())));
+        for (name, fp) in table_fingerprints() {
+            assert!(json.contains(&format!("\"{}\":\"{}\"", name, fp)));
+        }
+        assert_eq!(json.contains("\"cli\""), cfg!(feature = "cli"));
+        assert!(json.ends_with("}}"));
+    }
+
+    #[test]
+    fn test_case_mapping_is_locale_independent() {
+        // Turkish dotted I, final sigma and sharp s must not depend on the
+        // process locale; Rust's case mapping is locale-free by design.
+        assert_eq!(
+            slugify_default("İSTANBUL ΣΊΣΥΦΟΣ straße"),
+            "istanbul-sisuphos-strasse"
+        );
+        assert_eq!(slugify_default("Ǆemal ﬁnal Ⅻ"), "dzemal-final-xii");
+        assert_eq!(
+            slugify_default("日本語 العربية עברית"),
+            "ri-ben-yu-l-rby-bryt"
+        );
+    }
+}
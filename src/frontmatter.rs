@@ -0,0 +1,301 @@
+// Read and update the `slug` field of Markdown front matter (static site
+// generators: Hugo, Jekyll, Zola, ...). Only flat `title` / `slug` keys are
+// understood; the rest of the document is preserved byte for byte.
+use crate::slugify::{SlugifyOptions, slugify_with_options_public};
+
+/// Front matter syntax, chosen by the opening delimiter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FrontMatterFormat {
+    /// `---` delimited, `key: value`.
+    Yaml,
+    /// `+++` delimited, `key = "value"`.
+    Toml,
+}
+
+impl FrontMatterFormat {
+    fn delimiter(self) -> &'static str {
+        match self {
+            FrontMatterFormat::Yaml => "---",
+            FrontMatterFormat::Toml => "+++",
+        }
+    }
+}
+
+/// Location and values of the front matter keys of a document.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FrontMatter {
+    pub format: FrontMatterFormat,
+    pub title: Option<String>,
+    pub slug: Option<String>,
+    // Byte ranges (start of line, end of line without newline).
+    title_line: Option<(usize, usize)>,
+    slug_line: Option<(usize, usize)>,
+    // Start of the closing delimiter line.
+    end: usize,
+}
+
+/// What `update_slug` found, and whether the document changed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SlugUpdate {
+    /// No front matter block at the top of the document.
+    NoFrontMatter,
+    /// Front matter without a `title` key.
+    NoTitle,
+    /// The `slug` field already matches the title.
+    Unchanged { slug: String },
+    /// The `slug` field was missing and has been added.
+    Added { slug: String, document: String },
+    /// The `slug` field differs from the title's slug. `document` holds the
+    /// rewritten text when `overwrite` was requested.
+    Mismatch {
+        existing: String,
+        expected: String,
+        document: Option<String>,
+    },
+}
+
+/// Parse the front matter block at the start of `doc`, if any.
+pub fn parse(doc: &str) -> Option<FrontMatter> {
+    let first = doc.lines().next()?;
+    let format = match first.trim_end() {
+        "---" => FrontMatterFormat::Yaml,
+        "+++" => FrontMatterFormat::Toml,
+        _ => return None,
+    };
+    let mut fm = FrontMatter {
+        format,
+        title: None,
+        slug: None,
+        title_line: None,
+        slug_line: None,
+        end: 0,
+    };
+    let mut pos = doc.find('\n')? + 1;
+    while pos < doc.len() {
+        let line_end = doc[pos..].find('\n').map_or(doc.len(), |i| pos + i);
+        let line = doc[pos..line_end].trim_end_matches('\r');
+        if line.trim_end() == format.delimiter() {
+            fm.end = pos;
+            return Some(fm);
+        }
+        if let Some((key, value)) = split_key(line, format) {
+            let span = Some((pos, pos + line.len()));
+            match key {
+                "title" => {
+                    fm.title = Some(unquote(value));
+                    fm.title_line = span;
+                }
+                "slug" => {
+                    fm.slug = Some(unquote(value));
+                    fm.slug_line = span;
+                }
+                _ => {}
+            }
+        }
+        pos = line_end + 1;
+    }
+    None
+}
+
+/// Generate (when missing) or validate the `slug` of `doc` from its
+/// `title`, slugified with `opts`. With `overwrite`, a mismatching slug is
+/// replaced as well.
+pub fn update_slug(doc: &str, opts: &SlugifyOptions, overwrite: bool) -> SlugUpdate {
+    let Some(fm) = parse(doc) else {
+        return SlugUpdate::NoFrontMatter;
+    };
+    let Some(title) = &fm.title else {
+        return SlugUpdate::NoTitle;
+    };
+    let expected = slugify_with_options_public(opts, title);
+    match &fm.slug {
+        Some(existing) if *existing == expected => SlugUpdate::Unchanged { slug: expected },
+        Some(existing) => SlugUpdate::Mismatch {
+            existing: existing.clone(),
+            document: overwrite.then(|| with_slug(doc, &fm, &expected)),
+            expected,
+        },
+        None => SlugUpdate::Added {
+            document: with_slug(doc, &fm, &expected),
+            slug: expected,
+        },
+    }
+}
+
+// Replace the slug line, or insert one after the title line.
+fn with_slug(doc: &str, fm: &FrontMatter, slug: &str) -> String {
+    let line = match fm.format {
+        FrontMatterFormat::Yaml => format!("slug: {}", yaml_scalar(slug)),
+        FrontMatterFormat::Toml => format!(
+            "slug = \"{}\"",
+            slug.replace('\\', "\\\\").replace('"', "\\\"")
+        ),
+    };
+    let mut out = String::with_capacity(doc.len() + line.len() + 1);
+    match (fm.slug_line, fm.title_line) {
+        (Some((start, end)), _) => {
+            out.push_str(&doc[..start]);
+            out.push_str(&line);
+            out.push_str(&doc[end..]);
+        }
+        (None, Some((_, end))) => {
+            // the title line is never the last one: the closing delimiter follows
+            let newline = if doc[end..].starts_with("\r\n") {
+                "\r\n"
+            } else {
+                "\n"
+            };
+            let end = end + newline.len();
+            out.push_str(&doc[..end]);
+            out.push_str(&line);
+            out.push_str(newline);
+            out.push_str(&doc[end..]);
+        }
+        (None, None) => {
+            let newline = if doc.starts_with("---\r\n") || doc.starts_with("+++\r\n") {
+                "\r\n"
+            } else {
+                "\n"
+            };
+            out.push_str(&doc[..fm.end]);
+            out.push_str(&line);
+            out.push_str(newline);
+            out.push_str(&doc[fm.end..]);
+        }
+    }
+    out
+}
+
+fn split_key(line: &str, format: FrontMatterFormat) -> Option<(&str, &str)> {
+    // nested keys are indented; only top-level ones are considered
+    if line.starts_with([' ', '\t']) {
+        return None;
+    }
+    let sep = match format {
+        FrontMatterFormat::Yaml => ':',
+        FrontMatterFormat::Toml => '=',
+    };
+    let (key, value) = line.split_once(sep)?;
+    Some((key.trim(), value.trim()))
+}
+
+fn unquote(value: &str) -> String {
+    let bytes = value.as_bytes();
+    if value.len() >= 2
+        && (bytes[0] == b'"' || bytes[0] == b'\'')
+        && bytes[0] == bytes[value.len() - 1]
+    {
+        let inner = &value[1..value.len() - 1];
+        return if bytes[0] == b'"' {
+            inner.replace("\\\"", "\"").replace("\\\\", "\\")
+        } else {
+            inner.replace("''", "'")
+        };
+    }
+    value.to_string()
+}
+
+// Plain YAML scalar unless it would read as a number, boolean or null.
+fn yaml_scalar(slug: &str) -> String {
+    let ambiguous = slug.is_empty()
+        || slug.parse::<f64>().is_ok()
+        || matches!(
+            slug.to_ascii_lowercase().as_str(),
+            "true" | "false" | "yes" | "no" | "on" | "off" | "null" | "~"
+        );
+    if ambiguous {
+        format!("\"{}\"", slug)
+    } else {
+        slug.to_string()
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::expect_used)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::slugifier::DEFAULT_OPTIONS;
+
+    #[test]
+    fn test_parse_yaml_and_toml() {
+        let fm = parse("---\ntitle: \"Hello: World\"\nslug: hello\ntags:\n  - slug: x\n---\nbody")
+            .unwrap();
+        assert_eq!(fm.format, FrontMatterFormat::Yaml);
+        assert_eq!(fm.title.as_deref(), Some("Hello: World"));
+        assert_eq!(fm.slug.as_deref(), Some("hello"));
+        let fm = parse("+++\ntitle = 'It''s here'\n+++\n").unwrap();
+        assert_eq!(fm.format, FrontMatterFormat::Toml);
+        assert_eq!(fm.title.as_deref(), Some("It's here"));
+        assert_eq!(fm.slug, None);
+        assert!(parse("# No front matter\n").is_none());
+        assert!(parse("---\ntitle: unterminated\n").is_none());
+    }
+
+    #[test]
+    fn test_update_slug_adds_validates_and_overwrites() {
+        let doc = "---\ntitle: Hello World\ndate: 2024-01-01\n---\n\nBody\n";
+        let SlugUpdate::Added { slug, document } = update_slug(doc, &DEFAULT_OPTIONS, false) else {
+            panic!("expected Added");
+        };
+        assert_eq!(slug, "hello-world");
+        assert_eq!(
+            document,
+            "---\ntitle: Hello World\nslug: hello-world\ndate: 2024-01-01\n---\n\nBody\n"
+        );
+        assert_eq!(
+            update_slug(&document, &DEFAULT_OPTIONS, false),
+            SlugUpdate::Unchanged {
+                slug: "hello-world".into()
+            }
+        );
+
+        let stale = "+++\r\nslug = \"old\"\r\ntitle = \"New Title\"\r\n+++\r\n";
+        assert_eq!(
+            update_slug(stale, &DEFAULT_OPTIONS, false),
+            SlugUpdate::Mismatch {
+                existing: "old".into(),
+                expected: "new-title".into(),
+                document: None
+            }
+        );
+        let SlugUpdate::Mismatch {
+            document: Some(doc),
+            ..
+        } = update_slug(stale, &DEFAULT_OPTIONS, true)
+        else {
+            panic!("expected rewritten document");
+        };
+        assert_eq!(
+            doc,
+            "+++\r\nslug = \"new-title\"\r\ntitle = \"New Title\"\r\n+++\r\n"
+        );
+
+        let SlugUpdate::Added { document, .. } = update_slug(
+            "+++\r\ntitle = \"2024\"\r\n+++\r\n",
+            &DEFAULT_OPTIONS,
+            false,
+        ) else {
+            panic!("expected Added");
+        };
+        assert_eq!(
+            document,
+            "+++\r\ntitle = \"2024\"\r\nslug = \"2024\"\r\n+++\r\n"
+        );
+        let SlugUpdate::Added { document, .. } =
+            update_slug("---\ntitle: 2024\n---\n", &DEFAULT_OPTIONS, false)
+        else {
+            panic!("expected Added");
+        };
+        assert_eq!(document, "---\ntitle: 2024\nslug: \"2024\"\n---\n");
+
+        assert_eq!(
+            update_slug("---\ndate: x\n---\n", &DEFAULT_OPTIONS, false),
+            SlugUpdate::NoTitle
+        );
+        assert_eq!(
+            update_slug("Body", &DEFAULT_OPTIONS, false),
+            SlugUpdate::NoFrontMatter
+        );
+    }
+}
@@ -0,0 +1,238 @@
+// Turn slugs back into display text (breadcrumbs, navigation labels).
+use crate::slugify::{
+    DEFAULT_SEPARATOR, SlugifyOptions, slugify_with_options_public, split_words,
+};
+use crate::special::{Locale, small_words as locale_small_words};
+
+/// Split `slug` on `separator` and join the words with single spaces.
+///
+/// Words are iterated with `split_words`, so repeated or dangling
+/// separators never produce double spaces.
+pub fn deslugify(slug: &str, separator: &str) -> String {
+    split_words(slug, separator).collect::<Vec<&str>>().join(" ")
+}
+
+/// `deslugify` for slugs produced with `opts`, guaranteeing that the text
+/// slugifies back to `slug`: `slugify(deslugify_with(s, opts), opts) == s`
+/// for every `s` that `opts` can produce.
+///
+/// Words are joined with spaces where that round-trips. A leading or
+/// trailing number that `strip_numeric_ids` would read as an ID keeps its
+/// separator ("order 12345" would lose its number), and slugs that still would not round-trip (e.g. with
+/// chained `word_translations`) are returned unchanged.
+pub fn deslugify_with(slug: &str, opts: &SlugifyOptions) -> String {
+    let round_trips = |text: &str| slugify_with_options_public(opts, text) == slug;
+    let spaced = deslugify(slug, &opts.separator);
+    if round_trips(&spaced) {
+        return spaced;
+    }
+    let words: Vec<&str> = split_words(slug, &opts.separator).collect();
+    let is_number = |w: &str| w.chars().all(|c| c.is_ascii_digit());
+    let last = words.len().saturating_sub(1);
+    let mut kept = String::with_capacity(slug.len());
+    for (i, word) in words.iter().enumerate() {
+        if i > 0 {
+            let glue = (i == 1 && is_number(words[0])) || (i == last && is_number(word));
+            kept.push_str(if glue { opts.separator.as_str() } else { " " });
+        }
+        kept.push_str(word);
+    }
+    if round_trips(&kept) {
+        return kept;
+    }
+    slug.to_string()
+}
+
+/// Produce title-cased display text from a `-` separated slug.
+///
+/// Casing follows `locale`:
+/// - `En` / `Nl`: every word is capitalized except `small_words` (Dutch
+///   also capitalizes the "ij" digraph as a unit: "ijssel" -> "IJssel").
+/// - `De`, `Es`, `Fr`: only the first word is capitalized; the remaining
+///   words are left as they appear in the slug, since nouns cannot be told
+///   apart from other words once lowercased.
+///
+/// When `small_words` is `None` the locale defaults from
+/// `special::small_words` are used. A `small_words` list given by the caller
+/// applies to every locale: all words but those are capitalized. The first
+/// word is always capitalized.
+pub fn titlecase_slug(slug: &str, locale: Locale, small_words: Option<&[&str]>) -> String {
+    titlecase_words(slug, DEFAULT_SEPARATOR, locale, small_words)
+}
+
+/// `titlecase_slug` for slugs produced with `opts`: words are split on
+/// `opts.separator` instead of `-`.
+pub fn titlecase_slug_with(
+    slug: &str,
+    opts: &SlugifyOptions,
+    locale: Locale,
+    small_words: Option<&[&str]>,
+) -> String {
+    titlecase_words(slug, &opts.separator, locale, small_words)
+}
+
+fn titlecase_words(
+    slug: &str,
+    separator: &str,
+    locale: Locale,
+    small_words: Option<&[&str]>,
+) -> String {
+    // sentence case, unless the caller says which words stay lowercase
+    let sentence_case =
+        small_words.is_none() && matches!(locale, Locale::De | Locale::Es | Locale::Fr);
+    let small = small_words.unwrap_or_else(|| locale_small_words(locale));
+    let words: Vec<String> = split_words(slug, separator)
+        .enumerate()
+        .map(|(i, word)| {
+            if i == 0 {
+                capitalize(word, locale)
+            } else if sentence_case {
+                word.to_string()
+            } else if small.iter().any(|s| s.eq_ignore_ascii_case(word)) {
+                word.to_lowercase()
+            } else {
+                capitalize(word, locale)
+            }
+        })
+        .collect();
+    words.join(" ")
+}
+
+fn capitalize(word: &str, locale: Locale) -> String {
+    if locale == Locale::Nl
+        && let Some(rest) = word.strip_prefix("ij")
+    {
+        return format!("IJ{}", rest);
+    }
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::expect_used)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deslugify_basic() {
+        assert_eq!(deslugify("hello-world", "-"), "hello world");
+        assert_eq!(deslugify("-hello--world-", "-"), "hello world");
+        assert_eq!(deslugify("helloZZworld", "ZZ"), "hello world");
+        assert_eq!(deslugify("", "-"), "");
+    }
+
+    #[test]
+    fn test_deslugify_round_trips_across_options() {
+        use crate::slugify::{DigitBoundaries, Separator};
+
+        let titles = [
+            "Hello World",
+            "Annual Report 2024",
+            "Release 12345",
+            "12345 – Blue Widget",
+            "GPT4 vs. MP3 players",
+            "Déjà vu: l'été",
+            "Q&A @ 50% off",
+            "The Lord of the Rings",
+            "x_y 1,000 items",
+        ];
+        let separators = [
+            Separator::Hyphen,
+            Separator::Underscore,
+            Separator::Dot,
+            Separator::None,
+            Separator::Custom("--".into()),
+        ];
+        for sep in separators {
+            for (lowercase, allow_unicode) in [(true, false), (false, false), (true, true)] {
+                for (strip_ids, digits) in [
+                    (false, DigitBoundaries::Keep),
+                    (true, DigitBoundaries::Both),
+                ] {
+                    for (max_length, word_boundary) in [(0, false), (12, true), (9, false)] {
+                        let opts = SlugifyOptions::builder()
+                            .separator(sep.clone())
+                            .lowercase(lowercase)
+                            .allow_unicode(allow_unicode)
+                            .strip_numeric_ids(strip_ids)
+                            .digit_boundaries(digits)
+                            .max_length(max_length)
+                            .word_boundary(word_boundary)
+                            .stopwords(["the"])
+                            .symbol_locale(Some(Locale::En))
+                            .build()
+                            .unwrap();
+                        for title in titles {
+                            let slug = slugify_with_options_public(&opts, title);
+                            let text = deslugify_with(&slug, &opts);
+                            assert_eq!(
+                                slugify_with_options_public(&opts, &text),
+                                slug,
+                                "{:?} via {:?} ({:?})",
+                                title,
+                                text,
+                                sep
+                            );
+                        }
+                    }
+                }
+            }
+        }
+        let opts = SlugifyOptions::builder().strip_numeric_ids(true).build().unwrap();
+        assert_eq!(deslugify_with("annual-report-12345", &opts), "annual report-12345");
+        assert_eq!(deslugify_with("12345-annual-report", &opts), "12345-annual report");
+        assert_eq!(deslugify_with("annual-report-2024", &opts), "annual report 2024");
+        assert_eq!(deslugify_with("top-10-tips", &opts), "top 10 tips");
+        let slug = "the-lord-of-the-rings";
+        assert_eq!(
+            slugify_with_options_public(
+                &SlugifyOptions::builder().build().unwrap(),
+                &titlecase_slug(slug, Locale::En, None)
+            ),
+            slug
+        );
+    }
+
+    #[test]
+    fn test_titlecase_slug_locales() {
+        assert_eq!(
+            titlecase_slug("the-lord-of-the-rings", Locale::En, None),
+            "The Lord of the Rings"
+        );
+        assert_eq!(
+            titlecase_slug("war-and-peace", Locale::En, Some(&[])),
+            "War And Peace"
+        );
+        assert_eq!(titlecase_slug("ijssel-en-ijmuiden", Locale::Nl, None), "IJssel en IJmuiden");
+        assert_eq!(titlecase_slug("die-neue-zeit", Locale::De, None), "Die neue zeit");
+        assert_eq!(titlecase_slug("le-petit-prince", Locale::Fr, None), "Le petit prince");
+        assert_eq!(titlecase_slug("élan-vital", Locale::En, None), "Élan Vital");
+    }
+
+    #[test]
+    fn test_titlecase_slug_small_words_and_separator() {
+        // the caller's list applies to sentence-case locales too
+        assert_eq!(
+            titlecase_slug("le-petit-prince", Locale::Fr, Some(&["le"])),
+            "Le Petit Prince"
+        );
+        assert_eq!(
+            titlecase_slug("die-neue-zeit-und-wir", Locale::De, Some(&["und"])),
+            "Die Neue Zeit und Wir"
+        );
+        let opts = SlugifyOptions::builder().separator("_").build().unwrap();
+        assert_eq!(
+            titlecase_slug_with("the_lord_of_the_rings", &opts, Locale::En, None),
+            "The Lord of the Rings"
+        );
+        let opts = SlugifyOptions::builder().separator("--").build().unwrap();
+        assert_eq!(
+            titlecase_slug_with("le--petit--prince", &opts, Locale::Fr, None),
+            "Le petit prince"
+        );
+    }
+}
@@ -1,13 +1,64 @@
+pub mod charset;
+pub mod compat;
+pub mod entities;
+pub mod explain;
+pub mod fingerprint;
+pub mod frontmatter;
+pub mod humanize;
+pub mod loss;
+pub mod namespace;
+pub mod path;
+pub mod pipeline;
+pub mod prelude;
+pub mod preview;
+pub mod provenance;
+pub mod punycode;
+pub mod registry;
+pub mod reslug;
+pub mod seo;
+pub mod slug;
+pub mod slugifier;
 pub mod slugify;
 pub mod special;
+pub mod stats;
+pub mod store;
+pub mod stream;
+pub mod suffix;
+mod tables;
+pub mod tags;
+pub mod transliterate;
 
 // Re-export modules for easier external access (integration tests / consumers)
+#[allow(deprecated)]
 pub use slugify as slugify_mod;
 
-pub use slugify::{DEFAULT_SEPARATOR, slugify, smart_truncate};
+pub use pipeline::{BuiltinStage, Pipeline, Stage};
+pub use registry::{SlugRegistry, SuffixStrategy};
+pub use slug::{Slug, SlugReport, is_valid_slug, validate_slug};
+pub use slugifier::{
+    Slugifier, reset_default_options, set_default_options, slugify_fast,
+    with_current_slugifier, with_default_options,
+};
+// `slugify` (the long-argument wrapper) is deprecated but still re-exported
+#[allow(deprecated)]
+pub use slugify::{
+    Budget, CaseFoldStage, CaseStyle, CjkQuotes, Compat, DEFAULT_SEPARATOR, DNS_LABEL_MAX_LENGTH, DecodedSeparators, EntityPolicy, IdentifierStyle, LengthUnit, NumericCleanup, ReplacementRule, ReplacementStage, RestrictedChars, Separator, SlugDisplay, Slugify, TruncationStrategy, WordBreaks, WordSegmentation, is_dns_label, projected_length, slug_pattern, slugify, slugify_batch, slugify_default, smart_truncate, smart_truncate_by, split_words, try_slugify, try_slugify_default,
+};
 
 // Include Python bindings when the `python` feature is enabled so that
 // the PyO3 module (`python_slugify_pi`) is compiled and exports
 // the `PyInit_python_slugify_pi` symbol required by Python imports.
 #[cfg(feature = "python")]
 pub mod lib_py;
+
+// JavaScript bindings for `wasm-pack build --features wasm`.
+#[cfg(feature = "wasm")]
+pub mod lib_wasm;
+
+// JSON options document and its schema (`slugify_server`, `validate-config`).
+#[cfg(feature = "config")]
+pub mod config;
+
+// C ABI exported from the cdylib; see `include/slugify_rs.h`.
+#[cfg(feature = "ffi")]
+pub mod lib_ffi;
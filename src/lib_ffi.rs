@@ -0,0 +1,557 @@
+// C ABI (see `include/slugify_rs.h`, generated by cbindgen from this file).
+// Options are configured once through an opaque handle and compiled on
+// first use; slugs are written into caller-provided buffers so no memory
+// crosses the boundary. `slugify_c` is the simpler alternative: plain
+// options struct in, library-allocated string out (`slugify_free`).
+use std::ffi::{CStr, CString, c_char};
+use std::ptr;
+
+use crate::slugifier::{DEFAULT_SLUGIFIER, Slugifier};
+use crate::slugify::SlugifyOptions;
+
+/// A pointer argument was null.
+pub const SLUGIFY_RS_ERR_NULL: isize = -1;
+/// An input string was not valid UTF-8.
+pub const SLUGIFY_RS_ERR_UTF8: isize = -2;
+/// The configured options failed to build (e.g. an invalid regex).
+pub const SLUGIFY_RS_ERR_OPTIONS: isize = -3;
+
+/// Opaque options handle. Setters store the configuration; the options are
+/// built into a `Slugifier` on the first slug call after a change.
+pub struct SlugifyRsOptions {
+    separator: String,
+    max_length: usize,
+    word_boundary: bool,
+    save_order: bool,
+    lowercase: bool,
+    allow_unicode: bool,
+    stopwords: Vec<String>,
+    replacements: Vec<(String, String)>,
+    regex_pattern: Option<String>,
+    compiled: Option<Slugifier>,
+}
+
+impl SlugifyRsOptions {
+    fn slugifier(&mut self) -> Result<&Slugifier, isize> {
+        if self.compiled.is_none() {
+            let opts = SlugifyOptions::builder()
+                .separator(self.separator.as_str())
+                .max_length(self.max_length)
+                .word_boundary(self.word_boundary)
+                .save_order(self.save_order)
+                .lowercase(self.lowercase)
+                .allow_unicode(self.allow_unicode)
+                .stopwords(self.stopwords.iter().map(String::as_str))
+                .replacements(self.replacements.iter().map(|(a, b)| (a.as_str(), b.as_str())))
+                .regex_pattern(self.regex_pattern.clone())
+                .build()
+                .map_err(|_| SLUGIFY_RS_ERR_OPTIONS)?;
+            self.compiled = Some(Slugifier::new(opts));
+        }
+        self.compiled.as_ref().ok_or(SLUGIFY_RS_ERR_OPTIONS)
+    }
+}
+
+// Borrow a NUL-terminated UTF-8 string.
+unsafe fn str_arg<'a>(s: *const c_char) -> Result<&'a str, isize> {
+    if s.is_null() {
+        return Err(SLUGIFY_RS_ERR_NULL);
+    }
+    // SAFETY: the caller guarantees `s` points to a NUL-terminated string
+    // that outlives the call.
+    unsafe { CStr::from_ptr(s) }
+        .to_str()
+        .map_err(|_| SLUGIFY_RS_ERR_UTF8)
+}
+
+// Apply `f` to the handle behind `opts`, dropping the compiled options.
+unsafe fn update(opts: *mut SlugifyRsOptions, f: impl FnOnce(&mut SlugifyRsOptions)) -> isize {
+    // SAFETY: the caller passes a handle from `slugify_rs_options_new` that
+    // is not used concurrently.
+    match unsafe { opts.as_mut() } {
+        Some(o) => {
+            f(o);
+            o.compiled = None;
+            0
+        }
+        None => SLUGIFY_RS_ERR_NULL,
+    }
+}
+
+/// New options handle with the builder defaults. Free it with
+/// `slugify_rs_options_free`.
+#[unsafe(no_mangle)]
+pub extern "C" fn slugify_rs_options_new() -> *mut SlugifyRsOptions {
+    Box::into_raw(Box::new(SlugifyRsOptions {
+        separator: crate::slugify::DEFAULT_SEPARATOR.to_string(),
+        max_length: 0,
+        word_boundary: false,
+        save_order: false,
+        lowercase: true,
+        allow_unicode: false,
+        stopwords: Vec::new(),
+        replacements: Vec::new(),
+        regex_pattern: None,
+        compiled: None,
+    }))
+}
+
+/// Free a handle from `slugify_rs_options_new`; null is ignored.
+///
+/// # Safety
+/// `opts` must be null or a handle that has not been freed yet.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn slugify_rs_options_free(opts: *mut SlugifyRsOptions) {
+    if !opts.is_null() {
+        // SAFETY: `opts` came from `Box::into_raw` and is freed only once.
+        drop(unsafe { Box::from_raw(opts) });
+    }
+}
+
+/// Set the separator. Returns 0 or a negative `SLUGIFY_RS_ERR_*` code.
+///
+/// # Safety
+/// `opts` must be a live handle and `separator` a NUL-terminated string.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn slugify_rs_options_set_separator(
+    opts: *mut SlugifyRsOptions,
+    separator: *const c_char,
+) -> isize {
+    match unsafe { str_arg(separator) } {
+        Ok(sep) => unsafe { update(opts, |o| o.separator = sep.to_string()) },
+        Err(code) => code,
+    }
+}
+
+/// Set the maximum slug length (0 for no limit).
+///
+/// # Safety
+/// `opts` must be a live handle.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn slugify_rs_options_set_max_length(
+    opts: *mut SlugifyRsOptions,
+    max_length: usize,
+) -> isize {
+    unsafe { update(opts, |o| o.max_length = max_length) }
+}
+
+/// Truncate on word boundaries.
+///
+/// # Safety
+/// `opts` must be a live handle.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn slugify_rs_options_set_word_boundary(
+    opts: *mut SlugifyRsOptions,
+    enabled: bool,
+) -> isize {
+    unsafe { update(opts, |o| o.word_boundary = enabled) }
+}
+
+/// Keep word order when truncating.
+///
+/// # Safety
+/// `opts` must be a live handle.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn slugify_rs_options_set_save_order(
+    opts: *mut SlugifyRsOptions,
+    enabled: bool,
+) -> isize {
+    unsafe { update(opts, |o| o.save_order = enabled) }
+}
+
+/// Lowercase the slug (default on).
+///
+/// # Safety
+/// `opts` must be a live handle.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn slugify_rs_options_set_lowercase(
+    opts: *mut SlugifyRsOptions,
+    enabled: bool,
+) -> isize {
+    unsafe { update(opts, |o| o.lowercase = enabled) }
+}
+
+/// Keep Unicode letters instead of transliterating to ASCII.
+///
+/// # Safety
+/// `opts` must be a live handle.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn slugify_rs_options_set_allow_unicode(
+    opts: *mut SlugifyRsOptions,
+    enabled: bool,
+) -> isize {
+    unsafe { update(opts, |o| o.allow_unicode = enabled) }
+}
+
+/// Regex of characters to strip instead of the default set; null resets
+/// to the default.
+///
+/// # Safety
+/// `opts` must be a live handle and `pattern` null or a NUL-terminated
+/// string.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn slugify_rs_options_set_regex_pattern(
+    opts: *mut SlugifyRsOptions,
+    pattern: *const c_char,
+) -> isize {
+    let pattern = if pattern.is_null() {
+        None
+    } else {
+        match unsafe { str_arg(pattern) } {
+            Ok(p) => Some(p.to_string()),
+            Err(code) => return code,
+        }
+    };
+    unsafe { update(opts, |o| o.regex_pattern = pattern) }
+}
+
+/// Add a word removed from slugs.
+///
+/// # Safety
+/// `opts` must be a live handle and `word` a NUL-terminated string.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn slugify_rs_options_add_stopword(
+    opts: *mut SlugifyRsOptions,
+    word: *const c_char,
+) -> isize {
+    match unsafe { str_arg(word) } {
+        Ok(w) => unsafe { update(opts, |o| o.stopwords.push(w.to_string())) },
+        Err(code) => code,
+    }
+}
+
+/// Add a `from` -> `to` replacement.
+///
+/// # Safety
+/// `opts` must be a live handle; `from` and `to` NUL-terminated strings.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn slugify_rs_options_add_replacement(
+    opts: *mut SlugifyRsOptions,
+    from: *const c_char,
+    to: *const c_char,
+) -> isize {
+    match unsafe { (str_arg(from), str_arg(to)) } {
+        (Ok(f), Ok(t)) => unsafe {
+            update(opts, |o| o.replacements.push((f.to_string(), t.to_string())))
+        },
+        (Err(code), _) | (_, Err(code)) => code,
+    }
+}
+
+/// Slugify `input` into `out` (capacity `out_len` bytes, NUL included).
+///
+/// Returns the slug length without the NUL, like `snprintf`: when it is
+/// `>= out_len` nothing is written and the call can be retried with a
+/// larger buffer. Negative values are `SLUGIFY_RS_ERR_*` codes.
+///
+/// # Safety
+/// `opts` must be a live handle not used concurrently, `input` a
+/// NUL-terminated string and `out` valid for `out_len` bytes (or null when
+/// `out_len` is 0).
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn slugify_rs_slug(
+    opts: *mut SlugifyRsOptions,
+    input: *const c_char,
+    out: *mut c_char,
+    out_len: usize,
+) -> isize {
+    let text = match unsafe { str_arg(input) } {
+        Ok(t) => t,
+        Err(code) => return code,
+    };
+    // SAFETY: see the function contract.
+    let Some(o) = (unsafe { opts.as_mut() }) else {
+        return SLUGIFY_RS_ERR_NULL;
+    };
+    let slug = match o.slugifier() {
+        Ok(s) => s.slugify(text),
+        Err(code) => return code,
+    };
+    if slug.len() < out_len {
+        if out.is_null() {
+            return SLUGIFY_RS_ERR_NULL;
+        }
+        // SAFETY: `out` holds at least `slug.len() + 1` bytes.
+        unsafe { write_nul_terminated(&slug, out) };
+    }
+    slug.len() as isize
+}
+
+/// Slugify `count` strings in one call.
+///
+/// The slugs are written back to back into `out`, each followed by a NUL,
+/// and `offsets[i]` receives the start of slug `i` in `out`. Returns the
+/// number of bytes needed for all slugs (NULs included); when it is
+/// greater than `out_cap` nothing is written and the call can be retried
+/// with a larger buffer. Negative values are `SLUGIFY_RS_ERR_*` codes.
+///
+/// # Safety
+/// `opts` must be a live handle not used concurrently, `inputs` an array
+/// of `count` NUL-terminated strings, `offsets` valid for `count` writes
+/// and `out` valid for `out_cap` bytes (or null when `out_cap` is 0).
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn slugify_rs_slug_many(
+    opts: *mut SlugifyRsOptions,
+    inputs: *const *const c_char,
+    count: usize,
+    out: *mut c_char,
+    out_cap: usize,
+    offsets: *mut usize,
+) -> isize {
+    if count > 0 && (inputs.is_null() || offsets.is_null()) {
+        return SLUGIFY_RS_ERR_NULL;
+    }
+    // SAFETY: see the function contract.
+    let Some(o) = (unsafe { opts.as_mut() }) else {
+        return SLUGIFY_RS_ERR_NULL;
+    };
+    let slugifier = match o.slugifier() {
+        Ok(s) => s,
+        Err(code) => return code,
+    };
+    let mut slugs = Vec::with_capacity(count);
+    for i in 0..count {
+        // SAFETY: `inputs` holds `count` pointers.
+        match unsafe { str_arg(*inputs.add(i)) } {
+            Ok(text) => slugs.push(slugifier.slugify(text)),
+            Err(code) => return code,
+        }
+    }
+    let needed: usize = slugs.iter().map(|s| s.len() + 1).sum();
+    if needed > out_cap {
+        return needed as isize;
+    }
+    if out.is_null() && needed > 0 {
+        return SLUGIFY_RS_ERR_NULL;
+    }
+    let mut pos = 0;
+    for (i, slug) in slugs.iter().enumerate() {
+        // SAFETY: `pos + slug.len() + 1 <= needed <= out_cap`, and
+        // `offsets` holds `count` entries.
+        unsafe {
+            write_nul_terminated(slug, out.add(pos));
+            *offsets.add(i) = pos;
+        }
+        pos += slug.len() + 1;
+    }
+    needed as isize
+}
+
+/// Options for `slugify_c`, passed by pointer. Start from
+/// `slugify_options_default()` and change the fields you need.
+#[repr(C)]
+pub struct SlugifyOptionsC {
+    /// NUL-terminated separator; null means "-".
+    pub separator: *const c_char,
+    /// Maximum slug length, 0 for no limit.
+    pub max_length: usize,
+    pub word_boundary: bool,
+    pub save_order: bool,
+    pub lowercase: bool,
+    pub allow_unicode: bool,
+}
+
+/// `SlugifyOptionsC` holding the builder defaults.
+#[unsafe(no_mangle)]
+pub extern "C" fn slugify_options_default() -> SlugifyOptionsC {
+    SlugifyOptionsC {
+        separator: ptr::null(),
+        max_length: 0,
+        word_boundary: false,
+        save_order: false,
+        lowercase: true,
+        allow_unicode: false,
+    }
+}
+
+/// Slugify `text` into a new string owned by the caller, to be released
+/// with `slugify_free`. A null `opts` uses the defaults. Returns null when
+/// `text` is null, either string is not UTF-8 or the options are invalid.
+///
+/// # Safety
+/// `text` must be a NUL-terminated string, and `opts` null or a valid
+/// `SlugifyOptionsC` whose `separator` is null or NUL-terminated.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn slugify_c(
+    text: *const c_char,
+    opts: *const SlugifyOptionsC,
+) -> *mut c_char {
+    let Ok(text) = (unsafe { str_arg(text) }) else {
+        return ptr::null_mut();
+    };
+    // SAFETY: see the function contract.
+    let slug = match unsafe { opts.as_ref() } {
+        None => DEFAULT_SLUGIFIER.slugify(text),
+        Some(o) => {
+            let separator = if o.separator.is_null() {
+                crate::slugify::DEFAULT_SEPARATOR
+            } else {
+                match unsafe { str_arg(o.separator) } {
+                    Ok(sep) => sep,
+                    Err(_) => return ptr::null_mut(),
+                }
+            };
+            let built = SlugifyOptions::builder()
+                .separator(separator)
+                .max_length(o.max_length)
+                .word_boundary(o.word_boundary)
+                .save_order(o.save_order)
+                .lowercase(o.lowercase)
+                .allow_unicode(o.allow_unicode)
+                .build();
+            match built {
+                Ok(opts) => Slugifier::new(opts).slugify(text),
+                Err(_) => return ptr::null_mut(),
+            }
+        }
+    };
+    CString::new(slug).map_or(ptr::null_mut(), CString::into_raw)
+}
+
+/// Free a string returned by `slugify_c`; null is ignored.
+///
+/// # Safety
+/// `slug` must be null or a pointer from `slugify_c` not freed yet.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn slugify_free(slug: *mut c_char) {
+    if !slug.is_null() {
+        // SAFETY: `slug` came from `CString::into_raw` and is freed only once.
+        drop(unsafe { CString::from_raw(slug) });
+    }
+}
+
+// Copy `s` and a trailing NUL to `dst`, which must hold `s.len() + 1` bytes.
+unsafe fn write_nul_terminated(s: &str, dst: *mut c_char) {
+    // SAFETY: guaranteed by the caller.
+    unsafe {
+        ptr::copy_nonoverlapping(s.as_ptr(), dst.cast::<u8>(), s.len());
+        *dst.add(s.len()) = 0;
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::expect_used)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_options_and_single_slug() {
+        let opts = slugify_rs_options_new();
+        let sep = CString::new("_").unwrap();
+        let word = CString::new("the").unwrap();
+        let input = CString::new("The Quick Brown Fox").unwrap();
+        let mut buf = [0 as c_char; 32];
+        unsafe {
+            assert_eq!(slugify_rs_options_set_separator(opts, sep.as_ptr()), 0);
+            assert_eq!(slugify_rs_options_add_stopword(opts, word.as_ptr()), 0);
+            let n = slugify_rs_slug(opts, input.as_ptr(), buf.as_mut_ptr(), buf.len());
+            assert_eq!(n, "quick_brown_fox".len() as isize);
+            assert_eq!(CStr::from_ptr(buf.as_ptr()).to_str().unwrap(), "quick_brown_fox");
+            // too small: reports the length, writes nothing
+            let mut small = [7 as c_char; 4];
+            assert_eq!(
+                slugify_rs_slug(opts, input.as_ptr(), small.as_mut_ptr(), small.len()),
+                15
+            );
+            assert_eq!(small, [7; 4]);
+
+            let bad = CString::new("[").unwrap();
+            assert_eq!(slugify_rs_options_set_regex_pattern(opts, bad.as_ptr()), 0);
+            assert_eq!(
+                slugify_rs_slug(opts, input.as_ptr(), buf.as_mut_ptr(), buf.len()),
+                SLUGIFY_RS_ERR_OPTIONS
+            );
+            assert_eq!(slugify_rs_options_set_regex_pattern(opts, ptr::null()), 0);
+            assert_eq!(
+                slugify_rs_slug(opts, ptr::null(), buf.as_mut_ptr(), buf.len()),
+                SLUGIFY_RS_ERR_NULL
+            );
+            let invalid = [0xffu8 as c_char, 0];
+            assert_eq!(
+                slugify_rs_slug(opts, invalid.as_ptr(), buf.as_mut_ptr(), buf.len()),
+                SLUGIFY_RS_ERR_UTF8
+            );
+            slugify_rs_options_free(opts);
+            slugify_rs_options_free(ptr::null_mut());
+        }
+    }
+
+    #[test]
+    fn test_slug_many_into_caller_buffer() {
+        let opts = slugify_rs_options_new();
+        let texts: Vec<CString> = ["Hello World", "Déjà vu", ""]
+            .iter()
+            .map(|t| CString::new(*t).unwrap())
+            .collect();
+        let ptrs: Vec<*const c_char> = texts.iter().map(|t| t.as_ptr()).collect();
+        let mut offsets = [usize::MAX; 3];
+        unsafe {
+            let needed = slugify_rs_slug_many(
+                opts,
+                ptrs.as_ptr(),
+                ptrs.len(),
+                ptr::null_mut(),
+                0,
+                offsets.as_mut_ptr(),
+            );
+            assert_eq!(needed, ("hello-world".len() + "deja-vu".len() + 3) as isize);
+            assert_eq!(offsets, [usize::MAX; 3]);
+            let mut out = vec![0 as c_char; needed as usize];
+            let written = slugify_rs_slug_many(
+                opts,
+                ptrs.as_ptr(),
+                ptrs.len(),
+                out.as_mut_ptr(),
+                out.len(),
+                offsets.as_mut_ptr(),
+            );
+            assert_eq!(written, needed);
+            let slug = |i: usize| CStr::from_ptr(out.as_ptr().add(offsets[i])).to_str().unwrap();
+            assert_eq!([slug(0), slug(1), slug(2)], ["hello-world", "deja-vu", ""]);
+            slugify_rs_options_free(opts);
+        }
+    }
+
+    #[test]
+    fn test_slugify_c_allocates() {
+        let input = CString::new("Déjà Vu, Again!").unwrap();
+        let sep = CString::new("_").unwrap();
+        unsafe {
+            let slug = slugify_c(input.as_ptr(), ptr::null());
+            assert_eq!(CStr::from_ptr(slug).to_str().unwrap(), "deja-vu-again");
+            slugify_free(slug);
+
+            let mut opts = slugify_options_default();
+            opts.separator = sep.as_ptr();
+            opts.max_length = 7;
+            opts.word_boundary = true;
+            let slug = slugify_c(input.as_ptr(), &opts);
+            assert_eq!(CStr::from_ptr(slug).to_str().unwrap(), "deja_vu");
+            slugify_free(slug);
+
+            let invalid = [0xffu8 as c_char, 0];
+            opts.separator = invalid.as_ptr();
+            assert!(slugify_c(input.as_ptr(), &opts).is_null());
+            assert!(slugify_c(ptr::null(), ptr::null()).is_null());
+            slugify_free(ptr::null_mut());
+        }
+    }
+
+    #[test]
+    fn test_header_declares_exports() {
+        let header = include_str!("../include/slugify_rs.h");
+        let source = include_str!("lib_ffi.rs");
+        let exports = source
+            .split("#[cfg(test)]")
+            .next()
+            .unwrap()
+            .split("#[unsafe(no_mangle)]")
+            .skip(1)
+            .filter_map(|item| item.split("fn ").nth(1)?.split('(').next());
+        for name in exports {
+            assert!(header.contains(&format!("{}(", name)), "{} missing", name);
+        }
+        assert!(header.contains("} SlugifyOptionsC;"));
+    }
+}
@@ -1,85 +1,600 @@
 use pyo3::prelude::*;
 use pyo3::wrap_pyfunction;
+use pyo3::types::{PyDict, PyIterator, PyList};
+use std::cell::RefCell;
+use std::collections::VecDeque;
 
+use crate::registry::{SlugRegistry, SuffixStrategy};
+#[allow(deprecated)]
 use crate::slugify as slugify_mod;
 
+// Python-side defaults, overridable per thread with
+// `with slugify_rs.options(...)`. `None` fields fall back to the enclosing
+// scope, then to the `slugify()` signature defaults.
+#[derive(Clone, Default)]
+struct PyDefaults {
+    entities: Option<bool>,
+    decimal: Option<bool>,
+    hexadecimal: Option<bool>,
+    max_length: Option<usize>,
+    word_boundary: Option<bool>,
+    separator: Option<String>,
+    save_order: Option<bool>,
+    stopwords: Option<Vec<String>>,
+    regex_pattern: Option<String>,
+    lowercase: Option<bool>,
+    replacements: Option<Vec<(String, String)>>,
+    allow_unicode: Option<bool>,
+    transliterate_icons: Option<bool>,
+    emoji: Option<String>,
+}
+
+impl PyDefaults {
+    // Fields set in `self` win over `outer`.
+    fn over(self, outer: &PyDefaults) -> PyDefaults {
+        // `emoji` and `transliterate_icons` are one setting: either one
+        // replaces both from the enclosing scope
+        let (transliterate_icons, emoji) =
+            if self.transliterate_icons.is_some() || self.emoji.is_some() {
+                (self.transliterate_icons, self.emoji)
+            } else {
+                (outer.transliterate_icons, outer.emoji.clone())
+            };
+        PyDefaults {
+            entities: self.entities.or(outer.entities),
+            decimal: self.decimal.or(outer.decimal),
+            hexadecimal: self.hexadecimal.or(outer.hexadecimal),
+            max_length: self.max_length.or(outer.max_length),
+            word_boundary: self.word_boundary.or(outer.word_boundary),
+            separator: self.separator.or_else(|| outer.separator.clone()),
+            save_order: self.save_order.or(outer.save_order),
+            stopwords: self.stopwords.or_else(|| outer.stopwords.clone()),
+            regex_pattern: self.regex_pattern.or_else(|| outer.regex_pattern.clone()),
+            lowercase: self.lowercase.or(outer.lowercase),
+            replacements: self.replacements.or_else(|| outer.replacements.clone()),
+            allow_unicode: self.allow_unicode.or(outer.allow_unicode),
+            transliterate_icons,
+            emoji,
+        }
+    }
+}
+
+thread_local! {
+    // Merged defaults of every open `options()` scope, innermost last.
+    static SCOPES: RefCell<Vec<PyDefaults>> = const { RefCell::new(Vec::new()) };
+}
+
+fn current_defaults() -> PyDefaults {
+    SCOPES.with(|s| s.borrow().last().cloned().unwrap_or_default())
+}
+
+/// Context manager returned by `options(...)`: inside the `with` block,
+/// `slugify()` calls on the same thread use the given options as defaults.
+/// Scopes nest; arguments passed to `slugify()` still take precedence.
+#[pyclass(unsendable)]
+struct OptionsScope {
+    overrides: PyDefaults,
+    // Stack depth when entered, so `__exit__` restores exactly the
+    // enclosing scope.
+    depth: Option<usize>,
+}
+
+#[pymethods]
+impl OptionsScope {
+    fn __enter__(mut slf: PyRefMut<'_, Self>) -> PyRefMut<'_, Self> {
+        let overrides = slf.overrides.clone();
+        let depth = SCOPES.with(|s| {
+            let mut stack = s.borrow_mut();
+            let merged = overrides.over(&stack.last().cloned().unwrap_or_default());
+            stack.push(merged);
+            stack.len() - 1
+        });
+        slf.depth = Some(depth);
+        slf
+    }
+
+    #[pyo3(signature = (_exc_type=None, _exc_value=None, _traceback=None))]
+    fn __exit__(
+        &mut self,
+        _exc_type: Option<&Bound<'_, PyAny>>,
+        _exc_value: Option<&Bound<'_, PyAny>>,
+        _traceback: Option<&Bound<'_, PyAny>>,
+    ) -> bool {
+        if let Some(depth) = self.depth.take() {
+            SCOPES.with(|s| s.borrow_mut().truncate(depth));
+        }
+        // never swallow exceptions raised inside the block
+        false
+    }
+}
+
+/// `with slugify_rs.options(separator="_", max_length=60): ...`
+#[pyfunction]
+#[allow(clippy::too_many_arguments)]
+#[pyo3(signature=(
+    *,
+    entities=None,
+    decimal=None,
+    hexadecimal=None,
+    max_length=None,
+    word_boundary=None,
+    separator=None,
+    save_order=None,
+    stopwords=None,
+    regex_pattern=None,
+    lowercase=None,
+    replacements=None,
+    allow_unicode=None,
+    transliterate_icons=None,
+    emoji=None
+))]
+fn options(
+    entities: Option<bool>,
+    decimal: Option<bool>,
+    hexadecimal: Option<bool>,
+    max_length: Option<usize>,
+    word_boundary: Option<bool>,
+    separator: Option<String>,
+    save_order: Option<bool>,
+    stopwords: Option<Vec<String>>,
+    regex_pattern: Option<String>,
+    lowercase: Option<bool>,
+    replacements: Option<Vec<(String, String)>>,
+    allow_unicode: Option<bool>,
+    transliterate_icons: Option<bool>,
+    emoji: Option<String>,
+) -> OptionsScope {
+    OptionsScope {
+        overrides: PyDefaults {
+            entities,
+            decimal,
+            hexadecimal,
+            max_length,
+            word_boundary,
+            separator,
+            save_order,
+            stopwords,
+            regex_pattern,
+            lowercase,
+            replacements,
+            allow_unicode,
+            transliterate_icons,
+            emoji,
+        },
+        depth: None,
+    }
+}
+
 #[pyfunction]
 #[allow(clippy::too_many_arguments)]
 #[pyo3(signature=(
     text,
-    entities=true,
-    decimal=false,
-    hexadecimal=false,
-    max_length=0,
-    word_boundary=true,
+    entities=None,
+    decimal=None,
+    hexadecimal=None,
+    max_length=None,
+    word_boundary=None,
     separator=None,
-    save_order=false,
+    save_order=None,
     stopwords=None,
     regex_pattern=None,
-    lowercase=true,
+    lowercase=None,
     replacements=None,
-    allow_unicode=false,
-    transliterate_icons=true
+    allow_unicode=None,
+    transliterate_icons=None,
+    emoji=None
 ))]
 fn slugify(
     text: &str,
-    entities: bool,
-    decimal: bool,
-    hexadecimal: bool,
-    max_length: usize,
-    word_boundary: bool,
-    separator: Option<&str>,
-    save_order: bool,
+    entities: Option<bool>,
+    decimal: Option<bool>,
+    hexadecimal: Option<bool>,
+    max_length: Option<usize>,
+    word_boundary: Option<bool>,
+    separator: Option<String>,
+    save_order: Option<bool>,
     stopwords: Option<Vec<String>>,
     regex_pattern: Option<String>,
-    lowercase: bool,
+    lowercase: Option<bool>,
     replacements: Option<Vec<(String, String)>>,
-    allow_unicode: bool,
-    transliterate_icons: bool,
+    allow_unicode: Option<bool>,
+    transliterate_icons: Option<bool>,
+    emoji: Option<String>,
 ) -> PyResult<String> {
-    let sep = separator.unwrap_or(slugify_mod::DEFAULT_SEPARATOR);
+    let opts = build_options(PyDefaults {
+        entities,
+        decimal,
+        hexadecimal,
+        max_length,
+        word_boundary,
+        separator,
+        save_order,
+        stopwords,
+        regex_pattern,
+        lowercase,
+        replacements,
+        allow_unicode,
+        transliterate_icons,
+        emoji,
+    })?;
+    Ok(slugify_mod::slugify_with_options_public(&opts, text))
+}
+
+// Resolve explicit arguments against the current `options()` scope and
+// the historical Python defaults.
+fn build_options(args: PyDefaults) -> PyResult<slugify_mod::SlugifyOptions> {
+    let args = args.over(&current_defaults());
+    let sep = args
+        .separator
+        .as_deref()
+        .unwrap_or(slugify_mod::DEFAULT_SEPARATOR);
 
-    let stop_vec: Vec<String> = stopwords.unwrap_or_default();
+    let stop_vec: Vec<String> = args.stopwords.clone().unwrap_or_default();
     let stop_refs: Vec<&str> = stop_vec.iter().map(|s| s.as_str()).collect();
 
-    let repl_vec: Vec<(String, String)> = replacements.unwrap_or_default();
+    let repl_vec: Vec<(String, String)> = args.replacements.clone().unwrap_or_default();
     let repl_refs: Vec<(&str, &str)> = repl_vec
         .iter()
         .map(|(a, b)| (a.as_str(), b.as_str()))
         .collect();
 
-    // The core Rust API does not currently support an explicit
-    // `transliterate_icons` flag. We pass the supported parameters
-    // to `slugify` and ignore `transliterate_icons` here to retain
-    // compatibility with the Python tests which may pass it.
     // Build SlugifyOptions using the ergonomic builder API and call the
-    // options-based public function.
+    // options-based public function. Unset arguments keep the historical
+    // Python defaults.
     let builder = slugify_mod::SlugifyOptions::builder()
-        .entities(entities)
-        .decimal(decimal)
-        .hexadecimal(hexadecimal)
-        .max_length(max_length)
-        .word_boundary(word_boundary)
+        .entities(args.entities.unwrap_or(true))
+        .decimal(args.decimal.unwrap_or(false))
+        .hexadecimal(args.hexadecimal.unwrap_or(false))
+        .max_length(args.max_length.unwrap_or(0))
+        .word_boundary(args.word_boundary.unwrap_or(true))
         .separator(sep)
-        .save_order(save_order)
+        .save_order(args.save_order.unwrap_or(false))
         .stopwords(stop_refs)
-        .regex_pattern(regex_pattern.as_deref().map(|s| s.to_string()))
-        .lowercase(lowercase)
+        .regex_pattern(args.regex_pattern.clone())
+        .lowercase(args.lowercase.unwrap_or(true))
         .replacements(repl_refs)
-        .allow_unicode(allow_unicode);
-    // Respect transliterate_icons if provided by caller and forward it
-    // to the Rust options builder.
-    let builder = builder.transliterate_icons(transliterate_icons);
+        .allow_unicode(args.allow_unicode.unwrap_or(false));
+    let builder = match (args.emoji.as_deref(), args.transliterate_icons) {
+        (Some(_), Some(_)) => {
+            return Err(pyo3::exceptions::PyValueError::new_err(
+                "pass either emoji or transliterate_icons, not both",
+            ));
+        }
+        (Some(name), None) => builder.emoji_policy(emoji_policy(name)?),
+        (None, icons) => builder.emoji_policy(slugify_mod::EmojiPolicy::from_transliterate_icons(
+            icons.unwrap_or(true),
+        )),
+    };
 
-    let opts = builder
+    builder
         .build()
-        .map_err(|e| pyo3::exceptions::PyValueError::new_err(format!("invalid args: {:?}", e)))?;
+        .map_err(|e| pyo3::exceptions::PyValueError::new_err(format!("invalid args: {:?}", e)))
+}
 
-    Ok(slugify_mod::slugify_with_options_public(&opts, text))
+// Pull up to `chunk_size` strings from `source`, then slugify them with the
+// GIL released.
+fn next_chunk(
+    py: Python<'_>,
+    source: &Bound<'_, PyIterator>,
+    opts: &slugify_mod::SlugifyOptions,
+    chunk_size: usize,
+) -> PyResult<Vec<String>> {
+    let mut texts = Vec::with_capacity(chunk_size);
+    for item in source.clone().take(chunk_size) {
+        texts.push(item?.extract::<String>()?);
+    }
+    Ok(py.detach(|| slugify_mod::slugify_batch(&texts, opts)))
+}
+
+/// Lazy iterator returned by `slugify_batch(..., lazy=True)`: converts the
+/// source iterable `chunk_size` items at a time.
+#[pyclass]
+struct SlugIterator {
+    source: Py<PyIterator>,
+    opts: slugify_mod::SlugifyOptions,
+    chunk_size: usize,
+    buffer: VecDeque<String>,
+}
+
+#[pymethods]
+impl SlugIterator {
+    fn __iter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    fn __next__(&mut self, py: Python<'_>) -> PyResult<Option<String>> {
+        if self.buffer.is_empty() {
+            let source = self.source.bind(py);
+            self.buffer
+                .extend(next_chunk(py, source, &self.opts, self.chunk_size)?);
+        }
+        Ok(self.buffer.pop_front())
+    }
+}
+
+/// Slugify every string of `texts` (any iterable, e.g. a generator or a
+/// queryset iterator) with one set of options. Returns a list, or with
+/// `lazy=True` an iterator that converts `chunk_size` items at a time so
+/// huge inputs are never materialized.
+#[pyfunction]
+#[allow(clippy::too_many_arguments)]
+#[pyo3(signature=(
+    texts,
+    *,
+    lazy=false,
+    chunk_size=1024,
+    entities=None,
+    decimal=None,
+    hexadecimal=None,
+    max_length=None,
+    word_boundary=None,
+    separator=None,
+    save_order=None,
+    stopwords=None,
+    regex_pattern=None,
+    lowercase=None,
+    replacements=None,
+    allow_unicode=None,
+    transliterate_icons=None,
+    emoji=None
+))]
+fn slugify_batch(
+    py: Python<'_>,
+    texts: &Bound<'_, PyAny>,
+    lazy: bool,
+    chunk_size: usize,
+    entities: Option<bool>,
+    decimal: Option<bool>,
+    hexadecimal: Option<bool>,
+    max_length: Option<usize>,
+    word_boundary: Option<bool>,
+    separator: Option<String>,
+    save_order: Option<bool>,
+    stopwords: Option<Vec<String>>,
+    regex_pattern: Option<String>,
+    lowercase: Option<bool>,
+    replacements: Option<Vec<(String, String)>>,
+    allow_unicode: Option<bool>,
+    transliterate_icons: Option<bool>,
+    emoji: Option<String>,
+) -> PyResult<Py<PyAny>> {
+    if chunk_size == 0 {
+        return Err(pyo3::exceptions::PyValueError::new_err(
+            "chunk_size must be at least 1",
+        ));
+    }
+    // Options are resolved now, so a lazy iterator keeps the `options()`
+    // scope active at the call even when consumed outside of it.
+    let opts = build_options(PyDefaults {
+        entities,
+        decimal,
+        hexadecimal,
+        max_length,
+        word_boundary,
+        separator,
+        save_order,
+        stopwords,
+        regex_pattern,
+        lowercase,
+        replacements,
+        allow_unicode,
+        transliterate_icons,
+        emoji,
+    })?;
+    let source = texts.try_iter()?;
+    if lazy {
+        let iter = SlugIterator {
+            source: source.unbind(),
+            opts,
+            chunk_size,
+            buffer: VecDeque::new(),
+        };
+        return Ok(Py::new(py, iter)?.into_any());
+    }
+    let mut slugs = Vec::new();
+    loop {
+        let chunk = next_chunk(py, &source, &opts, chunk_size)?;
+        if chunk.is_empty() {
+            break;
+        }
+        slugs.extend(chunk);
+    }
+    Ok(PyList::new(py, slugs)?.into_any().unbind())
+}
+
+/// `slugify_many(texts, *, parallel=True, **options)`
+///
+/// Slugify a list of strings with one set of options and return the list
+/// of slugs. The whole list is converted with the GIL released, so other
+/// Python threads keep running; built with the `parallel` feature, the
+/// items are also spread over the rayon thread pool unless
+/// `parallel=False`.
+#[pyfunction]
+#[pyo3(signature = (texts, *, parallel=true, **options))]
+fn slugify_many(
+    py: Python<'_>,
+    texts: Vec<String>,
+    parallel: bool,
+    options: Option<&Bound<'_, PyDict>>,
+) -> PyResult<Vec<String>> {
+    let opts = build_options(defaults_from_kwargs(options)?)?;
+    Ok(py.detach(|| {
+        if parallel {
+            slugify_mod::slugify_batch(&texts, &opts)
+        } else {
+            texts
+                .iter()
+                .map(|t| slugify_mod::slugify_with_options_public(&opts, t))
+                .collect()
+        }
+    }))
+}
+
+impl From<slugify_mod::SlugifyError> for PyErr {
+    fn from(e: slugify_mod::SlugifyError) -> Self {
+        pyo3::exceptions::PyValueError::new_err(format!("{:?}", e))
+    }
+}
+
+// `PyDefaults` from the `**options` keyword arguments of the classes below.
+fn defaults_from_kwargs(kwargs: Option<&Bound<'_, PyDict>>) -> PyResult<PyDefaults> {
+    let mut d = PyDefaults::default();
+    let Some(kwargs) = kwargs else {
+        return Ok(d);
+    };
+    for (key, value) in kwargs.iter() {
+        if value.is_none() {
+            continue;
+        }
+        match key.extract::<String>()?.as_str() {
+            "entities" => d.entities = Some(value.extract()?),
+            "decimal" => d.decimal = Some(value.extract()?),
+            "hexadecimal" => d.hexadecimal = Some(value.extract()?),
+            "max_length" => d.max_length = Some(value.extract()?),
+            "word_boundary" => d.word_boundary = Some(value.extract()?),
+            "separator" => d.separator = Some(value.extract()?),
+            "save_order" => d.save_order = Some(value.extract()?),
+            "stopwords" => d.stopwords = Some(value.extract()?),
+            "regex_pattern" => d.regex_pattern = Some(value.extract()?),
+            "lowercase" => d.lowercase = Some(value.extract()?),
+            "replacements" => d.replacements = Some(value.extract()?),
+            "allow_unicode" => d.allow_unicode = Some(value.extract()?),
+            "transliterate_icons" => d.transliterate_icons = Some(value.extract()?),
+            "emoji" => d.emoji = Some(value.extract()?),
+            other => {
+                return Err(pyo3::exceptions::PyTypeError::new_err(format!(
+                    "unexpected option: {}",
+                    other
+                )));
+            }
+        }
+    }
+    Ok(d)
+}
+
+fn emoji_policy(name: &str) -> PyResult<slugify_mod::EmojiPolicy> {
+    match name {
+        "transliterate" => Ok(slugify_mod::EmojiPolicy::Transliterate),
+        "remove" => Ok(slugify_mod::EmojiPolicy::Remove),
+        "keep" => Ok(slugify_mod::EmojiPolicy::Keep),
+        "shortcode" => Ok(slugify_mod::EmojiPolicy::Shortcode),
+        other => Err(pyo3::exceptions::PyValueError::new_err(format!(
+            "invalid emoji policy: {} (expected transliterate, remove, keep or shortcode)",
+            other
+        ))),
+    }
+}
+
+fn suffix_strategy(
+    name: &str,
+    suffix_length: usize,
+    salt: Option<&str>,
+) -> PyResult<SuffixStrategy> {
+    match (name, salt) {
+        ("hash", Some(salt)) => Ok(SuffixStrategy::salted_hash(suffix_length, salt)),
+        (_, Some(_)) => Err(pyo3::exceptions::PyValueError::new_err(
+            "salt requires strategy=\"hash\"",
+        )),
+        ("counter", None) => Ok(SuffixStrategy::Counter),
+        ("random", None) => Ok(SuffixStrategy::RandomBase62(suffix_length)),
+        ("hash", None) => Ok(SuffixStrategy::ShortHash(suffix_length)),
+        (other, None) => Err(pyo3::exceptions::PyValueError::new_err(format!(
+            "invalid strategy: {} (expected counter, random or hash)",
+            other
+        ))),
+    }
+}
+
+/// `SlugRegistry(exists=None, strategy="counter", suffix_length=6, salt=None, **options)`
+///
+/// Hands out unique slugs ("my-post", "my-post-2", ...). `exists` is an
+/// optional callable `exists(slug) -> bool` (e.g. a Django ORM query)
+/// consulted for every candidate; the retry loop runs in Rust. With
+/// `strategy="hash"`, `salt` (e.g. a tenant id) keys the hash so tenants
+/// get different suffixes. Options are resolved once, at construction.
+#[pyclass(name = "SlugRegistry")]
+struct PySlugRegistry {
+    inner: SlugRegistry,
+    exists: Option<Py<PyAny>>,
+}
+
+#[pymethods]
+impl PySlugRegistry {
+    #[new]
+    #[pyo3(signature = (exists=None, strategy="counter", suffix_length=6, salt=None, **options))]
+    fn new(
+        exists: Option<Py<PyAny>>,
+        strategy: &str,
+        suffix_length: usize,
+        salt: Option<&str>,
+        options: Option<&Bound<'_, PyDict>>,
+    ) -> PyResult<Self> {
+        let opts = build_options(defaults_from_kwargs(options)?)?;
+        let strategy = suffix_strategy(strategy, suffix_length, salt)?;
+        Ok(PySlugRegistry {
+            inner: SlugRegistry::with_strategy(opts, strategy),
+            exists,
+        })
+    }
+
+    /// Unique slug for `text`; also skips slugs for which `exists` is true.
+    /// Exceptions raised by `exists` propagate.
+    fn slugify(&mut self, py: Python<'_>, text: &str) -> PyResult<String> {
+        let exists = self.exists.as_ref().map(|f| f.bind(py));
+        self.inner.slugify_checked(text, |slug| match exists {
+            Some(f) => f.call1((slug,))?.is_truthy(),
+            None => Ok(false),
+        })
+    }
+
+    /// Mark `slug` as taken; returns False if it already was.
+    fn reserve(&mut self, slug: String) -> bool {
+        self.inner.reserve(slug)
+    }
+
+    /// Forget `slug`; returns False if it was not registered.
+    fn release(&mut self, slug: &str) -> bool {
+        self.inner.release(slug)
+    }
+
+    fn __contains__(&self, slug: &str) -> bool {
+        self.inner.contains(slug)
+    }
+
+    fn __len__(&self) -> usize {
+        self.inner.len()
+    }
+}
+
+/// `unique_slug(text, exists, strategy="counter", suffix_length=6, salt=None, **options)`
+///
+/// One-off unique slug: slugify `text` and add suffixes until the
+/// `exists(slug) -> bool` callable returns False. Nothing is remembered
+/// between calls.
+#[pyfunction]
+#[pyo3(signature = (text, exists, strategy="counter", suffix_length=6, salt=None, **options))]
+fn unique_slug(
+    py: Python<'_>,
+    text: &str,
+    exists: Py<PyAny>,
+    strategy: &str,
+    suffix_length: usize,
+    salt: Option<&str>,
+    options: Option<&Bound<'_, PyDict>>,
+) -> PyResult<String> {
+    let mut registry = PySlugRegistry::new(Some(exists), strategy, suffix_length, salt, options)?;
+    registry.slugify(py, text)
 }
 
 #[pymodule(name = "slugify_rs")]
 fn python_slugify_pi(_py: Python, m: &Bound<PyModule>) -> PyResult<()> {
     m.add_function(wrap_pyfunction!(slugify, m)?)?;
+    m.add_function(wrap_pyfunction!(options, m)?)?;
+    m.add_class::<OptionsScope>()?;
+    m.add_function(wrap_pyfunction!(slugify_batch, m)?)?;
+    m.add_class::<SlugIterator>()?;
+    m.add_function(wrap_pyfunction!(slugify_many, m)?)?;
+    m.add_class::<PySlugRegistry>()?;
+    m.add_function(wrap_pyfunction!(unique_slug, m)?)?;
     Ok(())
 }
@@ -0,0 +1,169 @@
+// JavaScript bindings (wasm-bindgen). Options are parsed once into a
+// `Slugifier`, and batches cross the JS boundary as a single array, so bulk
+// imports in a web worker avoid per-call option parsing and marshaling.
+use js_sys::{Array, Reflect};
+use wasm_bindgen::prelude::*;
+
+use crate::slugifier::Slugifier;
+use crate::slugify::{SlugifyOptions, slugify_with_options_public};
+
+#[wasm_bindgen(typescript_custom_section)]
+const TS_SLUGIFY_OPTIONS: &'static str = r#"
+export interface SlugifyOptions {
+    separator?: string;
+    maxLength?: number;
+    wordBoundary?: boolean;
+    saveOrder?: boolean;
+    lowercase?: boolean;
+    allowUnicode?: boolean;
+    entities?: boolean;
+    decimal?: boolean;
+    hexadecimal?: boolean;
+    stopwords?: string[];
+    replacements?: [string, string][];
+    regexPattern?: string;
+}
+"#;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "SlugifyOptions")]
+    pub type JsSlugifyOptions;
+}
+
+fn get(obj: &JsValue, key: &str) -> Result<Option<JsValue>, JsError> {
+    let value = Reflect::get(obj, &JsValue::from_str(key))
+        .map_err(|_| JsError::new(&format!("cannot read option {}", key)))?;
+    Ok((!value.is_undefined() && !value.is_null()).then_some(value))
+}
+
+fn get_bool(obj: &JsValue, key: &str) -> Result<Option<bool>, JsError> {
+    get(obj, key)?
+        .map(|v| v.as_bool().ok_or_else(|| JsError::new(&format!("{} must be a boolean", key))))
+        .transpose()
+}
+
+fn get_string(obj: &JsValue, key: &str) -> Result<Option<String>, JsError> {
+    get(obj, key)?
+        .map(|v| v.as_string().ok_or_else(|| JsError::new(&format!("{} must be a string", key))))
+        .transpose()
+}
+
+fn strings(value: &JsValue, key: &str) -> Result<Vec<String>, JsError> {
+    Array::from(value)
+        .iter()
+        .map(|v| v.as_string().ok_or_else(|| JsError::new(&format!("{} must hold strings", key))))
+        .collect()
+}
+
+// Builder defaults, overridden by the keys present in `options`.
+fn options_from_js(options: Option<JsSlugifyOptions>) -> Result<SlugifyOptions, JsError> {
+    let mut builder = SlugifyOptions::builder();
+    let Some(options) = options else {
+        return builder.build().map_err(|e| JsError::new(&format!("{:?}", e)));
+    };
+    let obj: &JsValue = options.as_ref();
+    if let Some(v) = get_string(obj, "separator")? {
+        builder = builder.separator(v);
+    }
+    if let Some(v) = get(obj, "maxLength")? {
+        let n = v
+            .as_f64()
+            .filter(|n| *n >= 0.0 && n.fract() == 0.0)
+            .ok_or_else(|| JsError::new("maxLength must be a non-negative integer"))?;
+        builder = builder.max_length(n as usize);
+    }
+    if let Some(v) = get_bool(obj, "wordBoundary")? {
+        builder = builder.word_boundary(v);
+    }
+    if let Some(v) = get_bool(obj, "saveOrder")? {
+        builder = builder.save_order(v);
+    }
+    if let Some(v) = get_bool(obj, "lowercase")? {
+        builder = builder.lowercase(v);
+    }
+    if let Some(v) = get_bool(obj, "allowUnicode")? {
+        builder = builder.allow_unicode(v);
+    }
+    if let Some(v) = get_bool(obj, "entities")? {
+        builder = builder.entities(v);
+    }
+    if let Some(v) = get_bool(obj, "decimal")? {
+        builder = builder.decimal(v);
+    }
+    if let Some(v) = get_bool(obj, "hexadecimal")? {
+        builder = builder.hexadecimal(v);
+    }
+    if let Some(v) = get(obj, "stopwords")? {
+        builder = builder.stopwords(strings(&v, "stopwords")?);
+    }
+    if let Some(v) = get(obj, "replacements")? {
+        let pairs = Array::from(&v)
+            .iter()
+            .map(|pair| match strings(&pair, "replacements")?.as_slice() {
+                [from, to] => Ok((from.clone(), to.clone())),
+                _ => Err(JsError::new("replacements must be [from, to] pairs")),
+            })
+            .collect::<Result<Vec<(String, String)>, JsError>>()?;
+        builder = builder.replacements(pairs);
+    }
+    if let Some(v) = get_string(obj, "regexPattern")? {
+        builder = builder.regex_pattern(Some(v));
+    }
+    builder.build().map_err(|e| JsError::new(&format!("{:?}", e)))
+}
+
+fn slugify_array(slugifier: &Slugifier, texts: &Array) -> Result<Array, JsError> {
+    let out = Array::new_with_length(texts.length());
+    for (i, text) in texts.iter().enumerate() {
+        let text = text
+            .as_string()
+            .ok_or_else(|| JsError::new(&format!("item {} is not a string", i)))?;
+        out.set(i as u32, JsValue::from_str(&slugifier.slugify(&text)));
+    }
+    Ok(out)
+}
+
+/// `slugify(text, options?)`. Parses `options` on every call; prefer a
+/// `Slugifier` for repeated calls.
+#[wasm_bindgen]
+pub fn slugify(text: &str, options: Option<JsSlugifyOptions>) -> Result<String, JsError> {
+    Ok(slugify_with_options_public(&options_from_js(options)?, text))
+}
+
+/// `slugifyBatch(texts, options?)`: one slug per string of `texts`.
+#[wasm_bindgen(js_name = slugifyBatch)]
+pub fn slugify_batch(texts: &Array, options: Option<JsSlugifyOptions>) -> Result<Array, JsError> {
+    slugify_array(&Slugifier::new(options_from_js(options)?), texts)
+}
+
+/// Options parsed and validated once, reusable for any number of calls.
+#[wasm_bindgen(js_name = Slugifier)]
+pub struct JsSlugifier {
+    inner: Slugifier,
+}
+
+#[wasm_bindgen(js_class = Slugifier)]
+impl JsSlugifier {
+    #[wasm_bindgen(constructor)]
+    pub fn new(options: Option<JsSlugifyOptions>) -> Result<JsSlugifier, JsError> {
+        Ok(JsSlugifier {
+            inner: Slugifier::new(options_from_js(options)?),
+        })
+    }
+
+    pub fn slugify(&self, text: &str) -> String {
+        self.inner.slugify(text)
+    }
+
+    #[wasm_bindgen(js_name = slugifyBatch)]
+    pub fn slugify_batch(&self, texts: &Array) -> Result<Array, JsError> {
+        slugify_array(&self.inner, texts)
+    }
+
+    /// Length the slug of `text` will have, for live character counters.
+    #[wasm_bindgen(js_name = projectedLength)]
+    pub fn projected_length(&self, text: &str) -> usize {
+        self.inner.projected_length(text)
+    }
+}
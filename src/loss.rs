@@ -0,0 +1,157 @@
+// Information lost between a title and its slug, for CMS UIs that warn
+// editors before saving: every character the slug leaves out, with its byte
+// offset, and whether it is a letter or digit the transliterator has no
+// ASCII for (a title written entirely in such a script gives an empty
+// slug). Built on `preview`, so the same characters are reported.
+use crate::preview::preview;
+use crate::slugify::{SlugifyOptions, slugify_untruncated};
+
+/// Why a character is missing from the slug.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LossReason {
+    /// Removed by sanitization, stopwords, `max_length`, ...
+    Dropped,
+    /// A letter or digit with no ASCII transliteration (`allow_unicode`
+    /// off).
+    Untransliterable,
+}
+
+/// One character of the input missing from the slug.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LostChar {
+    /// Byte offset in the input.
+    pub offset: usize,
+    pub ch: char,
+    pub reason: LossReason,
+}
+
+/// Characters of the input the slug does not represent, by offset.
+/// Whitespace is never reported.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LossReport {
+    pub lost: Vec<LostChar>,
+}
+
+impl LossReport {
+    /// Whether any letter or digit could not be transliterated.
+    pub fn has_untransliterable(&self) -> bool {
+        self.lost
+            .iter()
+            .any(|lost| lost.reason == LossReason::Untransliterable)
+    }
+}
+
+/// Slugify `text` and report the characters the slug leaves out.
+///
+/// `"Café 𓀀!"` gives the slug `"cafe"`, `'𓀀'` at offset 6 as
+/// `Untransliterable` and `'!'` at offset 10 as `Dropped`.
+pub fn slugify_with_report(text: &str, opts: &SlugifyOptions) -> (String, LossReport) {
+    let preview = preview(text, opts);
+    let mut lost = Vec::new();
+    for range in preview.highlighted_drops {
+        for (i, ch) in text[range.clone()].char_indices() {
+            if ch.is_whitespace() {
+                continue;
+            }
+            let reason = if untransliterable(ch, opts) {
+                LossReason::Untransliterable
+            } else {
+                LossReason::Dropped
+            };
+            lost.push(LostChar {
+                offset: range.start + i,
+                ch,
+                reason,
+            });
+        }
+    }
+    (preview.slug, LossReport { lost })
+}
+
+// Number of characters `slugify_with_report` would list, without
+// classifying them (`strict` / `max_dropped_fraction`).
+pub(crate) fn dropped_count(text: &str, opts: &SlugifyOptions) -> usize {
+    preview(text, opts)
+        .highlighted_drops
+        .into_iter()
+        .map(|range| text[range].chars().filter(|c| !c.is_whitespace()).count())
+        .sum()
+}
+
+// A non-ASCII letter or digit that slugifies to nothing on its own.
+fn untransliterable(ch: char, opts: &SlugifyOptions) -> bool {
+    !opts.allow_unicode
+        && !ch.is_ascii()
+        && ch.is_alphanumeric()
+        && slugify_untruncated(ch.encode_utf8(&mut [0; 4]), opts).is_empty()
+}
+
+#[cfg(test)]
+#[allow(clippy::expect_used)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::slugify::slugify_with_options_public;
+
+    fn lost(text: &str, opts: &SlugifyOptions) -> Vec<(usize, char, LossReason)> {
+        let (slug, report) = slugify_with_report(text, opts);
+        assert_eq!(slug, slugify_with_options_public(opts, text));
+        report
+            .lost
+            .iter()
+            .map(|l| (l.offset, l.ch, l.reason))
+            .collect()
+    }
+
+    #[test]
+    fn test_report() {
+        let opts = SlugifyOptions::builder().build().unwrap();
+        assert_eq!(
+            lost("Café 𓀀!", &opts),
+            vec![
+                (6, '𓀀', LossReason::Untransliterable),
+                (10, '!', LossReason::Dropped),
+            ]
+        );
+        let (slug, report) = slugify_with_report("𓀀𓀁", &opts);
+        assert_eq!(slug, "");
+        assert!(report.has_untransliterable());
+        assert_eq!(report.lost.len(), 2);
+
+        let (slug, report) = slugify_with_report("Hello World", &opts);
+        assert_eq!(slug, "hello-world");
+        assert_eq!(report, LossReport::default());
+    }
+
+    #[test]
+    fn test_report_stopwords_and_unicode() {
+        let opts = SlugifyOptions::builder()
+            .stopwords(["the"])
+            .build()
+            .unwrap();
+        assert_eq!(
+            lost("The fox", &opts),
+            vec![
+                (0, 'T', LossReason::Dropped),
+                (1, 'h', LossReason::Dropped),
+                (2, 'e', LossReason::Dropped),
+            ]
+        );
+        // nothing is transliterated with allow_unicode
+        let opts = SlugifyOptions::builder()
+            .allow_unicode(true)
+            .build()
+            .unwrap();
+        let (slug, report) = slugify_with_report("𓀀 ☃", &opts);
+        assert_eq!(slug, "𓀀");
+        assert!(!report.has_untransliterable());
+        assert_eq!(
+            report.lost,
+            vec![LostChar {
+                offset: 5,
+                ch: '☃',
+                reason: LossReason::Dropped
+            }]
+        );
+    }
+}
@@ -0,0 +1,99 @@
+// Prefix slugs with a tenant/section namespace without breaking `max_length`.
+use regex::Regex;
+
+use crate::slugify::{SlugifyError, SlugifyOptions, slug_pattern, slugify_with_options_public};
+
+/// How the namespace is joined to the slug.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum NamespaceStyle {
+    /// `{ns}{separator}{slug}`, using `opts.separator`.
+    #[default]
+    Separator,
+    /// `{ns}/{slug}`; the namespace may itself contain `/`-separated segments.
+    Path,
+}
+
+/// Slugify `title` with `opts` and prefix it with `namespace`.
+///
+/// The namespace and the slug have independent length budgets: the title is
+/// truncated to `opts.max_length` on its own, and the namespace is never
+/// truncated. The namespace must already be a valid slug under `opts` (each
+/// segment, for `NamespaceStyle::Path`), otherwise
+/// `SlugifyError::InvalidNamespace` is returned. When the title produces an
+/// empty slug the namespace is returned alone.
+pub fn namespaced_slug(
+    namespace: &str,
+    title: &str,
+    opts: &SlugifyOptions,
+    style: NamespaceStyle,
+) -> Result<String, SlugifyError> {
+    validate_namespace(namespace, opts, style)?;
+    let slug = slugify_with_options_public(opts, title);
+    if slug.is_empty() {
+        return Ok(namespace.to_string());
+    }
+    let join = match style {
+        NamespaceStyle::Separator => opts.separator.as_str(),
+        NamespaceStyle::Path => "/",
+    };
+    Ok(format!("{}{}{}", namespace, join, slug))
+}
+
+fn validate_namespace(
+    namespace: &str,
+    opts: &SlugifyOptions,
+    style: NamespaceStyle,
+) -> Result<(), SlugifyError> {
+    let invalid = || SlugifyError::InvalidNamespace(namespace.to_string());
+    let rx = Regex::new(&slug_pattern(opts)).map_err(|_| invalid())?;
+    let valid = match style {
+        NamespaceStyle::Separator => rx.is_match(namespace),
+        NamespaceStyle::Path => namespace.split('/').all(|seg| rx.is_match(seg)),
+    };
+    if valid { Ok(()) } else { Err(invalid()) }
+}
+
+#[cfg(test)]
+#[allow(clippy::expect_used)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_namespaced_slug_styles_and_budgets() {
+        let opts = SlugifyOptions::builder().max_length(10).build().unwrap();
+        assert_eq!(
+            namespaced_slug(
+                "acme",
+                "Hello World Again",
+                &opts,
+                NamespaceStyle::Separator
+            )
+            .unwrap(),
+            "acme-hello-worl"
+        );
+        assert_eq!(
+            namespaced_slug("acme/blog", "Hello World", &opts, NamespaceStyle::Path).unwrap(),
+            "acme/blog/hello-worl"
+        );
+        assert_eq!(
+            namespaced_slug("acme", "!!!", &opts, NamespaceStyle::Separator).unwrap(),
+            "acme"
+        );
+    }
+
+    #[test]
+    fn test_namespaced_slug_rejects_invalid_namespace() {
+        let opts = SlugifyOptions::builder().build().unwrap();
+        for ns in ["Acme", "acme-", "", "a b", "acme/blog"] {
+            let res = namespaced_slug(ns, "Hello", &opts, NamespaceStyle::Separator);
+            assert!(
+                matches!(res, Err(SlugifyError::InvalidNamespace(_))),
+                "{}",
+                ns
+            );
+        }
+        let res = namespaced_slug("acme//blog", "Hello", &opts, NamespaceStyle::Path);
+        assert!(matches!(res, Err(SlugifyError::InvalidNamespace(_))));
+    }
+}
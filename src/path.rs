@@ -0,0 +1,120 @@
+// Slugify file-system style paths segment by segment ("Blog Posts/Intro.md"
+// -> "blog-posts/intro.md"), e.g. to derive URLs from a content tree.
+use crate::slugifier::DEFAULT_OPTIONS;
+use crate::slugify::{SlugifyOptions, slugify_with_options_public};
+
+/// How `slugify_path_with` treats the parts of a path.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PathOptions {
+    /// Keep the extension of the last segment ("Intro.md" -> "intro.md")
+    /// instead of slugifying it into the name ("intro-md"). Defaults to
+    /// `true`.
+    pub keep_extension: bool,
+}
+
+impl Default for PathOptions {
+    fn default() -> Self {
+        PathOptions {
+            keep_extension: true,
+        }
+    }
+}
+
+/// Slugify each `/`-separated segment of `path` with the builder defaults,
+/// keeping the final extension.
+///
+/// ```
+/// assert_eq!(
+///     slugify_rs::path::slugify_path("Blog Posts/Hello Wörld/Intro.md"),
+///     "blog-posts/hello-world/intro.md"
+/// );
+/// ```
+pub fn slugify_path(path: &str) -> String {
+    slugify_path_with(path, &DEFAULT_OPTIONS, &PathOptions::default())
+}
+
+/// Slugify each `/`-separated segment of `path` with `opts` independently
+/// (`max_length` applies per segment). Leading and trailing `/` are kept;
+/// empty segments and segments that slugify to nothing (`.`, `..`, `!!!`)
+/// are dropped.
+pub fn slugify_path_with(path: &str, opts: &SlugifyOptions, path_opts: &PathOptions) -> String {
+    let segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+    let last = segments.len().saturating_sub(1);
+    let slugs: Vec<String> = segments
+        .iter()
+        .enumerate()
+        .map(|(i, segment)| match split_extension(segment) {
+            Some((stem, ext)) if i == last && path_opts.keep_extension => {
+                let stem = slugify_with_options_public(opts, stem);
+                let ext = slugify_with_options_public(opts, ext);
+                match (stem.is_empty(), ext.is_empty()) {
+                    (false, false) => format!("{}.{}", stem, ext),
+                    (false, true) => stem,
+                    (true, _) => String::new(),
+                }
+            }
+            _ => slugify_with_options_public(opts, segment),
+        })
+        .filter(|slug| !slug.is_empty())
+        .collect();
+    let mut out = String::with_capacity(path.len());
+    if path.starts_with('/') {
+        out.push('/');
+    }
+    out.push_str(&slugs.join("/"));
+    if path.ends_with('/') && !slugs.is_empty() {
+        out.push('/');
+    }
+    out
+}
+
+// "name.ext" -> ("name", "ext"); dotfiles (".env") have no extension.
+fn split_extension(segment: &str) -> Option<(&str, &str)> {
+    let (stem, ext) = segment.rsplit_once('.')?;
+    let is_ext = !ext.is_empty() && ext.chars().all(|c| c.is_alphanumeric());
+    (!stem.is_empty() && is_ext).then_some((stem, ext))
+}
+
+#[cfg(test)]
+#[allow(clippy::expect_used)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_slugify_path_segments_and_extension() {
+        assert_eq!(
+            slugify_path("Blog Posts/Hello Wörld/Intro.md"),
+            "blog-posts/hello-world/intro.md"
+        );
+        assert_eq!(
+            slugify_path("/Docs//Getting Started/"),
+            "/docs/getting-started/"
+        );
+        assert_eq!(
+            slugify_path("a/../b/./My File.tar.GZ"),
+            "a/b/my-file-tar.gz"
+        );
+        assert_eq!(slugify_path("Notes/.env"), "notes/env");
+        assert_eq!(slugify_path("Release v1.2"), "release-v1.2");
+        assert_eq!(slugify_path("!!!/"), "");
+        assert_eq!(slugify_path(""), "");
+
+        let opts = SlugifyOptions::builder()
+            .separator("_")
+            .max_length(5)
+            .build()
+            .unwrap();
+        let no_ext = PathOptions {
+            keep_extension: false,
+        };
+        assert_eq!(
+            slugify_path_with("Long Folder Name/Intro.md", &opts, &no_ext),
+            "long/intro"
+        );
+        assert_eq!(
+            slugify_path_with("Long Folder Name/Intro.md", &opts, &PathOptions::default()),
+            "long/intro.md"
+        );
+    }
+}
@@ -0,0 +1,312 @@
+// Steps 1-12 of the slugify pipeline as reorderable `Stage`s. The fixed
+// pipeline runs `BuiltinStage::ALL` in order; a `Pipeline` starts from the
+// same list and lets callers remove stages, move them or insert their own
+// (a profanity filter after `stopwords`, say). Step 13 (final separator,
+// case style, identifier, IDNA) always runs last.
+use std::fmt;
+use std::sync::Arc;
+
+use crate::slugify::{
+    SlugifyError, SlugifyOptions, finish_slug, internal_separator, prepare_input, run_step,
+};
+
+/// What a stage sees besides the text.
+pub struct StageContext<'a> {
+    pub options: &'a SlugifyOptions,
+    /// Separator between words inside the pipeline: `-`, or U+001F with
+    /// `SeparatorScope::Inserted`. The requested separator only replaces it
+    /// after the last stage.
+    pub separator: &'static str,
+}
+
+/// One step of a `Pipeline`: text in, text out.
+///
+/// Implementations must be deterministic; a `Pipeline` shares its stages
+/// between threads.
+pub trait Stage: fmt::Debug + Send + Sync {
+    /// Name used to find the stage in a `PipelineBuilder`.
+    fn name(&self) -> &str;
+    fn apply(&self, text: &str, ctx: &StageContext<'_>) -> String;
+}
+
+/// The stages of the fixed pipeline, in the order of `ALL`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BuiltinStage {
+    /// `replacements`, replacement rules, protected terms, number cleanup,
+    /// abbreviations, source separators, symbols and camel case splitting.
+    Replacements,
+    /// Quotes become separators so they do not merge words.
+    Quotes,
+    /// Custom translations, then NFKC (`allow_unicode`) or transliteration.
+    Normalize,
+    /// Named and numeric character references.
+    Entities,
+    /// Normalization of decoded characters, then lowercasing.
+    Renormalize,
+    /// Quote removal, number cleanup and word segmentation.
+    Cleanup,
+    /// Disallowed characters become separators.
+    Sanitize,
+    /// Duplicate separators are collapsed and the ends trimmed.
+    Collapse,
+    /// Word translations, then stopword removal.
+    Stopwords,
+    /// `replacements` and post replacement rules, applied again.
+    PostReplacements,
+    /// `max_length`.
+    Truncate,
+    /// `rtl_word_order`.
+    WordOrder,
+}
+
+impl BuiltinStage {
+    pub const ALL: [BuiltinStage; 12] = [
+        BuiltinStage::Replacements,
+        BuiltinStage::Quotes,
+        BuiltinStage::Normalize,
+        BuiltinStage::Entities,
+        BuiltinStage::Renormalize,
+        BuiltinStage::Cleanup,
+        BuiltinStage::Sanitize,
+        BuiltinStage::Collapse,
+        BuiltinStage::Stopwords,
+        BuiltinStage::PostReplacements,
+        BuiltinStage::Truncate,
+        BuiltinStage::WordOrder,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            BuiltinStage::Replacements => "replacements",
+            BuiltinStage::Quotes => "quotes",
+            BuiltinStage::Normalize => "normalize",
+            BuiltinStage::Entities => "entities",
+            BuiltinStage::Renormalize => "renormalize",
+            BuiltinStage::Cleanup => "cleanup",
+            BuiltinStage::Sanitize => "sanitize",
+            BuiltinStage::Collapse => "collapse",
+            BuiltinStage::Stopwords => "stopwords",
+            BuiltinStage::PostReplacements => "post_replacements",
+            BuiltinStage::Truncate => "truncate",
+            BuiltinStage::WordOrder => "word_order",
+        }
+    }
+}
+
+impl Stage for BuiltinStage {
+    fn name(&self) -> &str {
+        self.as_str()
+    }
+
+    fn apply(&self, text: &str, ctx: &StageContext<'_>) -> String {
+        // only `strict` runs can fail
+        run_step(*self, text, ctx.options, ctx.separator, false).unwrap_or_default()
+    }
+}
+
+/// A slugify pipeline whose stages can be reordered, removed or extended.
+///
+/// `Pipeline::builder(opts).build()` slugifies like
+/// `slugify_with_options_public`; `compat` and `budget` are not consulted.
+pub struct Pipeline {
+    opts: SlugifyOptions,
+    stages: Vec<Arc<dyn Stage>>,
+}
+
+impl Pipeline {
+    /// Builder starting from the stages of the fixed pipeline.
+    pub fn builder(opts: SlugifyOptions) -> PipelineBuilder {
+        PipelineBuilder {
+            opts,
+            stages: BuiltinStage::ALL
+                .iter()
+                .map(|&stage| Arc::new(stage) as Arc<dyn Stage>)
+                .collect(),
+            unknown: None,
+        }
+    }
+
+    pub fn options(&self) -> &SlugifyOptions {
+        &self.opts
+    }
+
+    /// Names of the stages, in the order they run.
+    pub fn stage_names(&self) -> Vec<&str> {
+        self.stages.iter().map(|stage| stage.name()).collect()
+    }
+
+    pub fn run(&self, text: &str) -> String {
+        let ctx = StageContext {
+            options: &self.opts,
+            separator: internal_separator(&self.opts),
+        };
+        let mut text = prepare_input(text, ctx.separator).into_owned();
+        for stage in &self.stages {
+            text = stage.apply(&text, &ctx);
+        }
+        finish_slug(&text, ctx.separator, &self.opts)
+    }
+}
+
+/// Builder for `Pipeline`. Stages are found by `Stage::name`; a name that
+/// matches no stage makes `build` fail with `SlugifyError::UnknownStage`.
+pub struct PipelineBuilder {
+    opts: SlugifyOptions,
+    stages: Vec<Arc<dyn Stage>>,
+    unknown: Option<String>,
+}
+
+impl PipelineBuilder {
+    /// Remove every stage, to assemble a pipeline from scratch.
+    pub fn clear(mut self) -> Self {
+        self.stages.clear();
+        self
+    }
+
+    /// Add `stage` at the end.
+    pub fn push<S: Stage + 'static>(mut self, stage: S) -> Self {
+        self.stages.push(Arc::new(stage));
+        self
+    }
+
+    pub fn insert_before<S: Stage + 'static>(self, name: &str, stage: S) -> Self {
+        self.insert_at(name, 0, stage)
+    }
+
+    pub fn insert_after<S: Stage + 'static>(self, name: &str, stage: S) -> Self {
+        self.insert_at(name, 1, stage)
+    }
+
+    /// Remove the stage called `name`.
+    pub fn remove(mut self, name: &str) -> Self {
+        match self.position(name) {
+            Some(i) => {
+                self.stages.remove(i);
+            }
+            None => {
+                self.unknown.get_or_insert_with(|| name.to_string());
+            }
+        }
+        self
+    }
+
+    pub fn build(self) -> Result<Pipeline, SlugifyError> {
+        if let Some(name) = self.unknown {
+            return Err(SlugifyError::UnknownStage(name));
+        }
+        Ok(Pipeline {
+            opts: self.opts,
+            stages: self.stages,
+        })
+    }
+
+    fn insert_at<S: Stage + 'static>(mut self, name: &str, offset: usize, stage: S) -> Self {
+        match self.position(name) {
+            Some(i) => self.stages.insert(i + offset, Arc::new(stage)),
+            None => {
+                self.unknown.get_or_insert_with(|| name.to_string());
+            }
+        }
+        self
+    }
+
+    fn position(&self, name: &str) -> Option<usize> {
+        self.stages.iter().position(|stage| stage.name() == name)
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::expect_used)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::slugify::slugify_with_options_public;
+
+    // Replaces listed words with `***`.
+    #[derive(Debug)]
+    struct Profanity(&'static [&'static str]);
+
+    impl Stage for Profanity {
+        fn name(&self) -> &str {
+            "profanity"
+        }
+
+        fn apply(&self, text: &str, ctx: &StageContext<'_>) -> String {
+            text.split(ctx.separator)
+                .map(|w| if self.0.contains(&w) { "***" } else { w })
+                .collect::<Vec<_>>()
+                .join(ctx.separator)
+        }
+    }
+
+    fn options() -> SlugifyOptions {
+        SlugifyOptions::builder()
+            .stopwords(["the", "of"])
+            .max_length(20)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_default_stages_match_slugify() {
+        let pipeline = Pipeline::builder(options()).build().unwrap();
+        for text in [
+            "The Quick Brown Fox of the Year",
+            "foo &amp; bar &#381; 2024",
+            "C'est déjà l'été!",
+            "\u{200F}i love 🦄",
+        ] {
+            assert_eq!(
+                pipeline.run(text),
+                slugify_with_options_public(pipeline.options(), text)
+            );
+        }
+        let names: Vec<&str> = BuiltinStage::ALL.iter().map(|s| s.as_str()).collect();
+        assert_eq!(pipeline.stage_names(), names);
+    }
+
+    #[test]
+    fn test_custom_and_removed_stages() {
+        let text = "The darn quick fox of the year";
+        let pipeline = Pipeline::builder(options())
+            .insert_after("stopwords", Profanity(&["darn"]))
+            .build()
+            .unwrap();
+        assert_eq!(pipeline.run(text), "***-quick-fox-year");
+
+        let pipeline = Pipeline::builder(options())
+            .remove("stopwords")
+            .build()
+            .unwrap();
+        assert_eq!(pipeline.run(text), "the-darn-quick-fox-o");
+
+        // truncating before stopword removal cuts inside the stopwords
+        let pipeline = Pipeline::builder(options())
+            .remove("truncate")
+            .insert_before("stopwords", BuiltinStage::Truncate)
+            .build()
+            .unwrap();
+        assert_eq!(pipeline.run(text), "darn-quick-fox-o");
+
+        let pipeline = Pipeline::builder(options())
+            .clear()
+            .push(BuiltinStage::Normalize)
+            .push(BuiltinStage::Sanitize)
+            .build()
+            .unwrap();
+        assert_eq!(pipeline.run("Ça va?"), "Ca-va-");
+    }
+
+    #[test]
+    fn test_unknown_stage() {
+        for builder in [
+            Pipeline::builder(options()).remove("nope"),
+            Pipeline::builder(options()).insert_before("nope", BuiltinStage::Quotes),
+        ] {
+            assert!(matches!(
+                builder.build(),
+                Err(SlugifyError::UnknownStage(name)) if name == "nope"
+            ));
+        }
+    }
+}
@@ -0,0 +1,37 @@
+// The supported import surface: `use slugify_rs::prelude::*;` brings in
+// the options, the reusable `Slugifier`, the `Slug` newtype, the one-call
+// functions and the `Slugify` extension trait. Items here follow semver:
+// they are only added in minor releases and only removed or changed in
+// major ones. Everything else (pipeline stages, tables, helpers) may move
+// between minor releases.
+pub use crate::slug::Slug;
+pub use crate::slugifier::Slugifier;
+pub use crate::slugify::{
+    Slugify, SlugifyError, SlugifyOptions, SlugifyOptionsBuilder, slugify_default, try_slugify,
+};
+
+#[cfg(test)]
+#[allow(clippy::expect_used)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    // Pins the signatures of the prelude; a change here is a breaking one.
+    #[test]
+    fn test_prelude_surface() {
+        let _: fn(&str) -> String = slugify_default;
+        let _: fn(&str, &SlugifyOptions) -> Result<String, SlugifyError> = try_slugify;
+        let _: fn() -> SlugifyOptionsBuilder = SlugifyOptions::builder;
+        let _: fn(SlugifyOptionsBuilder) -> Result<SlugifyOptions, SlugifyError> =
+            SlugifyOptionsBuilder::build;
+        let _: fn(SlugifyOptions) -> Slugifier = Slugifier::new;
+        let _: fn(&str) -> Result<Slug, SlugifyError> = Slug::new;
+
+        let opts = SlugifyOptions::builder().separator("_").build().unwrap();
+        assert_eq!("Hello World".slugify(), "hello-world");
+        assert_eq!("Hello World".slugify_with(&opts), "hello_world");
+        assert_eq!(try_slugify("Hello World", &opts).unwrap(), "hello_world");
+        assert_eq!(Slugifier::new(opts).slugify("Hello World"), "hello_world");
+        assert_eq!(Slug::new("Hello World").unwrap().as_str(), "hello-world");
+    }
+}
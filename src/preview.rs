@@ -0,0 +1,187 @@
+// Live preview for editors: the slug of a title plus the parts of the title
+// that the slug leaves out, so a UI can underline them before saving.
+//
+// The input is split into whitespace-separated tokens that are slugified on
+// their own; a token whose slug is empty is dropped, and inside a kept token
+// a grapheme is dropped when removing it leaves the token's slug unchanged.
+// When the per-token words line up with the slug of the whole text, tokens
+// cut by `max_length` are dropped as well.
+use std::ops::Range;
+
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::provenance::{align, token_slug_words, tokens};
+use crate::slugify::{
+    SlugifyOptions, internal_words, slugify_untruncated, slugify_with_options_public,
+};
+
+/// Slug of a text and the byte ranges of the text it does not represent.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Preview {
+    /// Same as `slugify_with_options_public(opts, text)`.
+    pub slug: String,
+    /// Sorted, non-overlapping byte ranges of the input that leave no trace
+    /// in the slug: punctuation, stopwords, words cut by `max_length`, ...
+    /// Whitespace between words is never included.
+    pub highlighted_drops: Vec<Range<usize>>,
+}
+
+/// Slugify `text` and report which of its characters the slug drops.
+///
+/// `"Hello, World!"` gives the slug `"hello-world"` and the drops `5..6`
+/// (",") and `12..13` ("!"). Text changed by rules spanning several words
+/// (replacements, protected terms) is reported as kept.
+pub fn preview(text: &str, opts: &SlugifyOptions) -> Preview {
+    let tokens = tokens(text);
+    let mut token_words = Vec::with_capacity(tokens.len());
+    let mut drops = Vec::new();
+    for range in &tokens {
+        let token = &text[range.clone()];
+        let words = token_slug_words(token, opts);
+        if words.is_empty() {
+            drops.push(range.clone());
+        } else {
+            drops.extend(dropped_graphemes(token, range.start, opts));
+        }
+        token_words.push(words);
+    }
+    drops.extend(truncated_tokens(text, opts, &tokens, &token_words));
+    Preview {
+        slug: slugify_with_options_public(opts, text),
+        highlighted_drops: merge(drops),
+    }
+}
+
+// Graphemes of `token` that slugify to nothing and whose removal (or
+// replacement by a space) leaves the token's slug unchanged.
+fn dropped_graphemes(token: &str, offset: usize, opts: &SlugifyOptions) -> Vec<Range<usize>> {
+    let whole = slugify_untruncated(token, opts);
+    token
+        .grapheme_indices(true)
+        .filter(|(_, g)| slugify_untruncated(g, opts).is_empty())
+        .filter(|(i, g)| {
+            let (before, after) = (&token[..*i], &token[i + g.len()..]);
+            slugify_untruncated(&format!("{}{}", before, after), opts) == whole
+                || slugify_untruncated(&format!("{} {}", before, after), opts) == whole
+        })
+        .map(|(i, g)| offset + i..offset + i + g.len())
+        .collect()
+}
+
+// Tokens none of whose words survive `max_length`. Skipped when the
+// per-token words differ from the untruncated words of the whole text.
+fn truncated_tokens(
+    text: &str,
+    opts: &SlugifyOptions,
+    tokens: &[Range<usize>],
+    token_words: &[Vec<String>],
+) -> Vec<Range<usize>> {
+    if opts.max_length == 0 {
+        return Vec::new();
+    }
+    let (Some((full, sep)), Some((kept, _))) = (
+        internal_words(text, opts, false),
+        internal_words(text, opts, true),
+    ) else {
+        return Vec::new();
+    };
+    let flat: Vec<(usize, &str)> = token_words
+        .iter()
+        .enumerate()
+        .flat_map(|(t, words)| words.iter().map(move |w| (t, w.as_str())))
+        .collect();
+    let full: Vec<&str> = full.split(sep).filter(|w| !w.is_empty()).collect();
+    if full.len() != flat.len() || full.iter().zip(&flat).any(|(a, (_, b))| a != b) {
+        return Vec::new();
+    }
+    let kept: Vec<&str> = kept.split(sep).filter(|w| !w.is_empty()).collect();
+    let candidates: Vec<&str> = flat.iter().map(|(_, w)| *w).collect();
+    let mut kept_tokens = vec![false; tokens.len()];
+    for i in align(&kept, &candidates).into_iter().flatten() {
+        kept_tokens[flat[i].0] = true;
+    }
+    tokens
+        .iter()
+        .zip(token_words)
+        .zip(kept_tokens)
+        .filter(|((_, words), kept)| !words.is_empty() && !kept)
+        .map(|((range, _), _)| range.clone())
+        .collect()
+}
+
+// Sort and join overlapping or touching ranges.
+fn merge(mut ranges: Vec<Range<usize>>) -> Vec<Range<usize>> {
+    ranges.sort_by_key(|r| (r.start, r.end));
+    let mut out: Vec<Range<usize>> = Vec::with_capacity(ranges.len());
+    for r in ranges {
+        match out.last_mut() {
+            Some(last) if r.start <= last.end => last.end = last.end.max(r.end),
+            _ => out.push(r),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+#[allow(clippy::expect_used)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    fn drops<'a>(text: &'a str, opts: &SlugifyOptions) -> Vec<&'a str> {
+        let p = preview(text, opts);
+        assert_eq!(p.slug, slugify_with_options_public(opts, text));
+        p.highlighted_drops
+            .iter()
+            .map(|r| &text[r.clone()])
+            .collect()
+    }
+
+    #[test]
+    fn test_preview_punctuation_and_stopwords() {
+        let opts = SlugifyOptions::builder().build().unwrap();
+        let p = preview("Hello, World!", &opts);
+        assert_eq!(p.slug, "hello-world");
+        assert_eq!(p.highlighted_drops, vec![5..6, 12..13]);
+        assert_eq!(
+            drops("C'est l'été -- 10,000 €", &opts),
+            ["'", "'", "--", ","]
+        );
+        assert!(drops("plain words", &opts).is_empty());
+        assert!(drops("", &opts).is_empty());
+
+        let opts = SlugifyOptions::builder()
+            .stopwords(["the", "a"])
+            .build()
+            .unwrap();
+        assert_eq!(
+            drops("The (quick) fox & a dog", &opts),
+            ["The", "(", ")", "&", "a"]
+        );
+    }
+
+    #[test]
+    fn test_preview_max_length() {
+        let opts = SlugifyOptions::builder()
+            .max_length(9)
+            .word_boundary(true)
+            .save_order(true)
+            .build()
+            .unwrap();
+        assert_eq!(drops("quick brown fox!", &opts), ["brown", "fox!"]);
+        let opts = SlugifyOptions::builder()
+            .max_length(9)
+            .word_boundary(true)
+            .build()
+            .unwrap();
+        assert_eq!(drops("quick brown fox", &opts), ["brown"]);
+        // a word cut in the middle is still represented
+        let opts = SlugifyOptions::builder().max_length(8).build().unwrap();
+        assert_eq!(drops("quick brown fox", &opts), ["fox"]);
+    }
+
+    #[test]
+    fn test_merge() {
+        assert_eq!(merge(vec![4..6, 0..2, 1..3, 6..7]), vec![0..3, 4..7]);
+    }
+}
@@ -0,0 +1,277 @@
+// Provenance of slug words: which part of the input each word of a slug
+// came from, for search highlighting and analytics that attribute slug
+// words to title words.
+//
+// The input is split into whitespace-separated tokens slugified on their
+// own. Slug words are matched in order against the words of the tokens;
+// inside a token, runs between graphemes that slugify to nothing ("C'est"
+// -> "C", "est") narrow the source down when their words line up with the
+// token's. `preview` shares the tokenization.
+use std::ops::Range;
+
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::slugify::{
+    SlugifyOptions, internal_words, slugify_untruncated, slugify_with_options_public,
+};
+
+/// One word of a slug and the input it came from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SlugWord {
+    /// The word before `case_style` or `identifier` formatting, e.g. "deja"
+    /// for "Déjà".
+    pub word: String,
+    /// Byte range of the input the word came from; `None` when the word
+    /// comes from a rule spanning several input words (a multi-word
+    /// replacement or protected term) and cannot be traced.
+    pub source: Option<Range<usize>>,
+}
+
+/// A slug and the source of each of its words.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TracedSlug {
+    /// Same as `slugify_with_options_public(opts, text)`.
+    pub slug: String,
+    /// The slug words in order; empty in `compat` modes, which bypass the
+    /// word pipeline.
+    pub words: Vec<SlugWord>,
+}
+
+/// Slugify `text` and map every slug word back to the input.
+///
+/// `"C'est l'été"` gives "c-est-l-ete" with the words "c" (`0..1`), "est"
+/// (`2..5`), "l" (`6..7`) and "ete" (`8..13`). A word made of several runs
+/// ("10,000" -> "10000") points at the whole token.
+pub fn slugify_traced(text: &str, opts: &SlugifyOptions) -> TracedSlug {
+    let slug = slugify_with_options_public(opts, text);
+    let Some((kept, sep)) = internal_words(text, opts, true) else {
+        return TracedSlug {
+            slug,
+            words: Vec::new(),
+        };
+    };
+    let tokens = tokens(text);
+    let mut flat: Vec<(String, Range<usize>)> = Vec::new();
+    for range in &tokens {
+        let words = token_slug_words(&text[range.clone()], opts);
+        let sources = word_sources(text, range.clone(), &words, opts);
+        flat.extend(words.into_iter().zip(sources));
+    }
+    let kept: Vec<&str> = kept.split(sep).filter(|w| !w.is_empty()).collect();
+    let flat_words: Vec<&str> = flat.iter().map(|(w, _)| w.as_str()).collect();
+    let words = kept
+        .iter()
+        .zip(align(&kept, &flat_words))
+        .map(|(word, i)| SlugWord {
+            word: word.to_string(),
+            source: i.map(|i| flat[i].1.clone()),
+        })
+        .collect();
+    TracedSlug { slug, words }
+}
+
+/// A slug word and the byte range of the input it came from.
+pub type WordSpan = (String, Option<Range<usize>>);
+
+/// `slugify_traced` as plain `(word, span)` pairs, for highlighting code
+/// that slices the title with each span (`&text[span]`).
+///
+/// `"Déjà-vu: 10,000 fans"` gives "deja-vu-10000-fans" with the spans
+/// `0..6`, `7..9`, `11..17` and `18..22`. Untraceable words (see
+/// `SlugWord::source`) have no span.
+pub fn slugify_with_spans(text: &str, opts: &SlugifyOptions) -> (String, Vec<WordSpan>) {
+    let traced = slugify_traced(text, opts);
+    let spans = traced
+        .words
+        .into_iter()
+        .map(|w| (w.word, w.source))
+        .collect();
+    (traced.slug, spans)
+}
+
+// Byte ranges of the whitespace-separated tokens of `text`.
+pub(crate) fn tokens(text: &str) -> Vec<Range<usize>> {
+    let mut out = Vec::new();
+    let mut start = None;
+    for (i, c) in text.char_indices() {
+        if c.is_whitespace() {
+            if let Some(s) = start.take() {
+                out.push(s..i);
+            }
+        } else if start.is_none() {
+            start = Some(i);
+        }
+    }
+    if let Some(s) = start {
+        out.push(s..text.len());
+    }
+    out
+}
+
+// Untruncated slug words of one token; a `compat` slug counts as one word.
+pub(crate) fn token_slug_words(token: &str, opts: &SlugifyOptions) -> Vec<String> {
+    match internal_words(token, opts, false) {
+        Some((words, sep)) => words
+            .split(sep)
+            .filter(|w| !w.is_empty())
+            .map(str::to_string)
+            .collect(),
+        None => {
+            let slug = slugify_untruncated(token, opts);
+            if slug.is_empty() {
+                Vec::new()
+            } else {
+                vec![slug]
+            }
+        }
+    }
+}
+
+// For each slug word in `candidates`, the index of the source word it
+// matches. Words are matched in order; a word cut by `max_length` without
+// `word_boundary` matches as a prefix.
+pub(crate) fn align(words: &[&str], candidates: &[&str]) -> Vec<Option<usize>> {
+    let mut j = 0;
+    words
+        .iter()
+        .map(|word| {
+            let found = (j..candidates.len()).find(|&k| candidates[k].starts_with(word));
+            if let Some(k) = found {
+                j = k + 1;
+            }
+            found
+        })
+        .collect()
+}
+
+// Source range of each of `words`, the slug words of the token at `range`.
+fn word_sources(
+    text: &str,
+    range: Range<usize>,
+    words: &[String],
+    opts: &SlugifyOptions,
+) -> Vec<Range<usize>> {
+    let token = &text[range.clone()];
+    let mut runs = Vec::new();
+    let mut start = None;
+    for (i, g) in token.grapheme_indices(true) {
+        if slugify_untruncated(g, opts).is_empty() {
+            if let Some(s) = start.take() {
+                runs.push(s..i);
+            }
+        } else if start.is_none() {
+            start = Some(i);
+        }
+    }
+    if let Some(s) = start {
+        runs.push(s..token.len());
+    }
+    let mut run_words = Vec::new();
+    let mut sources = Vec::new();
+    for run in runs {
+        let ws = token_slug_words(&token[run.clone()], opts);
+        let shifted = range.start + run.start..range.start + run.end;
+        sources.extend(std::iter::repeat_n(shifted, ws.len()));
+        run_words.extend(ws);
+    }
+    if run_words == words {
+        sources
+    } else {
+        vec![range; words.len()]
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::expect_used)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::slugify::Compat;
+
+    fn sources<'a>(text: &'a str, opts: &SlugifyOptions) -> Vec<(String, Option<&'a str>)> {
+        slugify_traced(text, opts)
+            .words
+            .into_iter()
+            .map(|w| (w.word, w.source.map(|r| &text[r])))
+            .collect()
+    }
+
+    #[test]
+    fn test_traced_words() {
+        let opts = SlugifyOptions::builder().build().unwrap();
+        let traced = slugify_traced("C'est l'été", &opts);
+        assert_eq!(traced.slug, "c-est-l-ete");
+        let ranges: Vec<_> = traced.words.iter().map(|w| w.source.clone()).collect();
+        assert_eq!(ranges, [Some(0..1), Some(2..5), Some(6..7), Some(8..13)]);
+        assert_eq!(
+            sources("Déjà-vu: 10,000 fans", &opts),
+            [
+                ("deja".to_string(), Some("Déjà")),
+                ("vu".to_string(), Some("vu")),
+                ("10000".to_string(), Some("10,000")),
+                ("fans".to_string(), Some("fans")),
+            ]
+        );
+        assert!(slugify_traced("", &opts).words.is_empty());
+    }
+
+    #[test]
+    fn test_spans() {
+        let opts = SlugifyOptions::builder().separator("_").build().unwrap();
+        let text = "Déjà-vu: 10,000 fans";
+        let (slug, spans) = slugify_with_spans(text, &opts);
+        assert_eq!(slug, "deja_vu_10000_fans");
+        let spans: Vec<_> = spans.into_iter().map(|(_, span)| span.unwrap()).collect();
+        assert_eq!(spans, [0..6, 7..9, 11..17, 18..22]);
+        assert_eq!(&text[spans[2].clone()], "10,000");
+    }
+
+    #[test]
+    fn test_traced_stopwords_and_truncation() {
+        let opts = SlugifyOptions::builder()
+            .stopwords(["the"])
+            .max_length(10)
+            .separator("_")
+            .build()
+            .unwrap();
+        let traced = slugify_traced("The Quick Brown Fox", &opts);
+        assert_eq!(traced.slug, "quick_brow");
+        assert_eq!(
+            sources("The Quick Brown Fox", &opts),
+            [
+                ("quick".to_string(), Some("Quick")),
+                ("brow".to_string(), Some("Brown")),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_untraceable_words() {
+        let opts = SlugifyOptions::builder()
+            .replacements([("New York", "nyc")])
+            .build()
+            .unwrap();
+        assert_eq!(
+            sources("New York Times", &opts),
+            [
+                ("nyc".to_string(), None),
+                ("times".to_string(), Some("Times"))
+            ]
+        );
+        let opts = SlugifyOptions::builder()
+            .compat(Compat::GitHub)
+            .build()
+            .unwrap();
+        let traced = slugify_traced("Hello World", &opts);
+        assert_eq!(traced.slug, "hello-world");
+        assert!(traced.words.is_empty());
+    }
+
+    #[test]
+    fn test_align() {
+        assert_eq!(
+            align(&["b", "d", "x"], &["a", "b", "c", "dd"]),
+            [Some(1), Some(3), None]
+        );
+    }
+}
@@ -0,0 +1,215 @@
+// Punycode (RFC 3492) and IDNA-style labels ("münchen" -> "xn--mnchen-3ya")
+// so Unicode slugs can be used as hostnames.
+use unicode_normalization::UnicodeNormalization;
+
+use crate::slugify::{DNS_LABEL_MAX_LENGTH, SlugifyError};
+
+const BASE: u32 = 36;
+const T_MIN: u32 = 1;
+const T_MAX: u32 = 26;
+const SKEW: u32 = 38;
+const DAMP: u32 = 700;
+const INITIAL_BIAS: u32 = 72;
+const INITIAL_N: u32 = 128;
+
+/// Punycode encoding of `input` (without the `xn--` prefix).
+pub fn encode(input: &str) -> String {
+    let chars: Vec<u32> = input.chars().map(u32::from).collect();
+    let mut output: String = input.chars().filter(char::is_ascii).collect();
+    let basic = output.len() as u32;
+    if basic > 0 {
+        output.push('-');
+    }
+    let (mut n, mut delta, mut bias, mut handled) = (INITIAL_N, 0u32, INITIAL_BIAS, basic);
+    while (handled as usize) < chars.len() {
+        let m = chars.iter().copied().filter(|&c| c >= n).min().unwrap_or(n);
+        delta = delta.saturating_add((m - n).saturating_mul(handled + 1));
+        n = m;
+        for &c in &chars {
+            if c < n {
+                delta = delta.saturating_add(1);
+            }
+            if c == n {
+                let mut q = delta;
+                let mut k = BASE;
+                loop {
+                    let t = threshold(k, bias);
+                    if q < t {
+                        break;
+                    }
+                    output.push(digit(t + (q - t) % (BASE - t)));
+                    q = (q - t) / (BASE - t);
+                    k += BASE;
+                }
+                output.push(digit(q));
+                bias = adapt(delta, handled + 1, handled == basic);
+                delta = 0;
+                handled += 1;
+            }
+        }
+        delta = delta.saturating_add(1);
+        n += 1;
+    }
+    output
+}
+
+/// Decode Punycode `input` (without the `xn--` prefix); `None` when it is
+/// not valid Punycode. `decode("mnchen-3ya")` gives "münchen".
+pub fn decode(input: &str) -> Option<String> {
+    let (basic, extended) = match input.rfind('-') {
+        Some(i) => (&input[..i], &input[i + 1..]),
+        None => ("", input),
+    };
+    if !basic.is_ascii() {
+        return None;
+    }
+    let mut output: Vec<char> = basic.chars().collect();
+    let (mut n, mut i, mut bias) = (INITIAL_N, 0u32, INITIAL_BIAS);
+    let mut digits = extended.bytes().peekable();
+    while digits.peek().is_some() {
+        let old_i = i;
+        let mut w = 1u32;
+        let mut k = BASE;
+        loop {
+            let d = digit_value(digits.next()?)?;
+            i = i.checked_add(d.checked_mul(w)?)?;
+            let t = threshold(k, bias);
+            if d < t {
+                break;
+            }
+            w = w.checked_mul(BASE - t)?;
+            k += BASE;
+        }
+        let len = output.len() as u32 + 1;
+        bias = adapt(i - old_i, len, old_i == 0);
+        n = n.checked_add(i / len)?;
+        i %= len;
+        output.insert(i as usize, char::from_u32(n)?);
+        i += 1;
+    }
+    Some(output.into_iter().collect())
+}
+
+fn digit_value(b: u8) -> Option<u32> {
+    match b {
+        b'a'..=b'z' => Some(u32::from(b - b'a')),
+        b'A'..=b'Z' => Some(u32::from(b - b'A')),
+        b'0'..=b'9' => Some(u32::from(b - b'0') + 26),
+        _ => None,
+    }
+}
+
+fn threshold(k: u32, bias: u32) -> u32 {
+    if k <= bias {
+        T_MIN
+    } else if k >= bias + T_MAX {
+        T_MAX
+    } else {
+        k - bias
+    }
+}
+
+fn adapt(delta: u32, points: u32, first: bool) -> u32 {
+    let mut delta = if first { delta / DAMP } else { delta / 2 };
+    delta += delta / points;
+    let mut k = 0;
+    while delta > ((BASE - T_MIN) * T_MAX) / 2 {
+        delta /= BASE - T_MIN;
+        k += BASE;
+    }
+    k + (BASE - T_MIN + 1) * delta / (delta + SKEW)
+}
+
+fn digit(d: u32) -> char {
+    match d {
+        0..=25 => char::from(b'a' + d as u8),
+        _ => char::from(b'0' + (d - 26) as u8),
+    }
+}
+
+/// ASCII form of one hostname label: unchanged when already ASCII,
+/// otherwise `xn--` followed by the Punycode of its NFC form. Fails with
+/// `SlugifyError::InvalidLabel` when the result is empty or longer than 63
+/// bytes.
+pub fn to_ascii_label(label: &str) -> Result<String, SlugifyError> {
+    let ascii = if label.is_ascii() {
+        label.to_string()
+    } else {
+        format!("xn--{}", encode(&label.nfc().collect::<String>()))
+    };
+    if ascii.is_empty() || ascii.len() > DNS_LABEL_MAX_LENGTH {
+        return Err(SlugifyError::InvalidLabel(ascii));
+    }
+    Ok(ascii)
+}
+
+/// Like `to_ascii_label`, but drops characters from the end of `label`
+/// (and any `trim` characters left dangling) until the encoded label fits
+/// in 63 bytes. Only an empty label yields an empty string.
+pub(crate) fn fit_label(label: &str, trim: &[char]) -> String {
+    let mut chars: Vec<char> = label.chars().collect();
+    loop {
+        let candidate: String = chars.iter().collect();
+        match to_ascii_label(&candidate) {
+            Ok(ascii) => return ascii,
+            Err(_) if chars.is_empty() => return String::new(),
+            Err(_) => {
+                chars.pop();
+                while chars.last().is_some_and(|c| trim.contains(c)) {
+                    chars.pop();
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::expect_used)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_punycode_reference_vectors() {
+        assert_eq!(encode("münchen"), "mnchen-3ya");
+        assert_eq!(encode("bücher"), "bcher-kva");
+        assert_eq!(encode("пример"), "e1afmkfd");
+        assert_eq!(encode("日本語"), "wgv71a119e");
+        // RFC 3492 section 7.1 (A) Arabic (Egyptian)
+        assert_eq!(
+            encode(
+                "\u{644}\u{64A}\u{647}\u{645}\u{627}\u{628}\u{62A}\u{643}\u{644}\u{645}\u{648}\u{634}\u{639}\u{631}\u{628}\u{64A}\u{61F}"
+            ),
+            "egbpdaj6bu4bxfgehfvwxn"
+        );
+        assert_eq!(encode("abc"), "abc-");
+    }
+
+    #[test]
+    fn test_punycode_decode() {
+        for word in ["münchen", "пример", "日本語", "abc", "c-est-déjà-l-été", ""] {
+            assert_eq!(decode(&encode(word)).as_deref(), Some(word));
+        }
+        assert_eq!(decode("MNCHEN-3YA").as_deref(), Some("MüNCHEN"));
+        assert_eq!(decode("bad!"), None);
+        assert_eq!(decode("zzzzzzzzzzzz"), None);
+    }
+
+    #[test]
+    fn test_labels_and_length_limit() {
+        assert_eq!(to_ascii_label("mu\u{308}nchen").unwrap(), "xn--mnchen-3ya");
+        assert_eq!(to_ascii_label("plain-ascii").unwrap(), "plain-ascii");
+        assert!(matches!(
+            to_ascii_label(""),
+            Err(SlugifyError::InvalidLabel(_))
+        ));
+        let long = "ü".repeat(60);
+        assert!(matches!(
+            to_ascii_label(&long),
+            Err(SlugifyError::InvalidLabel(_))
+        ));
+        let fitted = fit_label(&format!("{}-x", long), &['-']);
+        assert!(fitted.len() <= DNS_LABEL_MAX_LENGTH);
+        assert!(fitted.starts_with("xn--"));
+    }
+}
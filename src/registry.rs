@@ -0,0 +1,283 @@
+// In-memory registry handing out unique slugs: "my-post", "my-post-2",
+// "my-post-3", ... with a configurable suffix scheme.
+use std::collections::HashSet;
+use std::collections::hash_map::RandomState;
+use std::hash::BuildHasher;
+
+use crate::fingerprint::Fnv1a;
+use crate::slugifier::Slugifier;
+use crate::slugify::{SlugifyError, SlugifyOptions};
+use crate::suffix::join_with_suffix_by;
+
+const BASE62: &[u8] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
+
+/// How the suffix of a colliding slug is generated.
+///
+/// Base62 suffixes contain uppercase letters; pick `Counter` when slugs
+/// must stay lowercase. Lengths are clamped to `1..=10` characters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SuffixStrategy {
+    /// `my-post-2`, `my-post-3`, ...
+    #[default]
+    Counter,
+    /// `n` random base62 characters: `my-post-x7Kq`.
+    RandomBase62(usize),
+    /// `n` base62 characters of a hash of the slug and the attempt number,
+    /// so the same collisions always produce the same suffixes.
+    ShortHash(usize),
+    /// `ShortHash` keyed with a per-tenant salt: the same collisions give
+    /// the same suffixes within a tenant and unrelated suffixes across
+    /// tenants. Build it with `SuffixStrategy::salted_hash`.
+    SaltedHash { len: usize, salt: u64 },
+}
+
+impl SuffixStrategy {
+    /// `SaltedHash` of `len` characters for the tenant identified by `salt`
+    /// (a tenant id or a secret per tenant).
+    pub fn salted_hash(len: usize, salt: &str) -> Self {
+        let mut h = Fnv1a::new();
+        h.write_str(salt);
+        SuffixStrategy::SaltedHash {
+            len,
+            salt: h.finish(),
+        }
+    }
+
+    /// Suffix for the `attempt`-th collision of `slug` (starting at 1).
+    pub fn suffix(&self, slug: &str, attempt: u64) -> String {
+        match *self {
+            SuffixStrategy::Counter => (attempt + 1).to_string(),
+            SuffixStrategy::RandomBase62(len) => {
+                base62(RandomState::new().hash_one((slug, attempt)), len)
+            }
+            SuffixStrategy::ShortHash(len) => short_hash(None, slug, attempt, len),
+            SuffixStrategy::SaltedHash { len, salt } => short_hash(Some(salt), slug, attempt, len),
+        }
+    }
+}
+
+// Unsalted hashes keep their original input so existing suffixes do not
+// change.
+fn short_hash(salt: Option<u64>, slug: &str, attempt: u64, len: usize) -> String {
+    let mut h = Fnv1a::new();
+    if let Some(salt) = salt {
+        h.write(&salt.to_le_bytes());
+    }
+    h.write_str(slug);
+    h.write(&attempt.to_le_bytes());
+    base62(h.finish(), len)
+}
+
+// A u64 holds 10 full base62 digits.
+fn base62(mut n: u64, len: usize) -> String {
+    (0..len.clamp(1, 10))
+        .map(|_| {
+            let c = BASE62[(n % 62) as usize] as char;
+            n /= 62;
+            c
+        })
+        .collect()
+}
+
+// `slug` with the suffix for its `attempt`-th collision, within
+// `opts.max_length`.
+pub(crate) fn suffixed(
+    slug: &str,
+    strategy: SuffixStrategy,
+    attempt: u64,
+    opts: &SlugifyOptions,
+) -> Result<String, SlugifyError> {
+    let suffix = strategy.suffix(slug, attempt);
+    join_with_suffix_by(
+        slug,
+        &suffix,
+        &opts.separator,
+        opts.max_length,
+        opts.length_unit,
+        opts.word_boundary,
+    )
+}
+
+/// Hands out slugs that are unique within the registry.
+///
+/// The first slug for a title is returned as-is; later collisions get a
+/// suffix from the `SuffixStrategy`, joined with `opts.separator` and kept
+/// within `opts.max_length` by shortening the stem (see
+/// `suffix::join_with_suffix`). Slugs that already exist elsewhere (e.g. in
+/// a database) can be registered up front with `reserve`.
+pub struct SlugRegistry {
+    slugifier: Slugifier,
+    strategy: SuffixStrategy,
+    taken: HashSet<String>,
+}
+
+impl SlugRegistry {
+    pub fn new(opts: SlugifyOptions) -> Self {
+        SlugRegistry::with_strategy(opts, SuffixStrategy::default())
+    }
+
+    pub fn with_strategy(opts: SlugifyOptions, strategy: SuffixStrategy) -> Self {
+        SlugRegistry {
+            slugifier: Slugifier::new(opts),
+            strategy,
+            taken: HashSet::new(),
+        }
+    }
+
+    /// Build a registry with the builder defaults and counter suffixes.
+    pub fn with_defaults() -> Result<Self, SlugifyError> {
+        Ok(SlugRegistry::new(SlugifyOptions::builder().build()?))
+    }
+
+    /// Slugify `text` and return a slug not handed out or reserved before,
+    /// registering it. Fails with `SlugifyError::SuffixTooLong` when
+    /// `max_length` leaves no room for the suffix.
+    pub fn slugify(&mut self, text: &str) -> Result<String, SlugifyError> {
+        self.slugify_checked(text, |_| Ok::<_, SlugifyError>(false))
+    }
+
+    /// `slugify`, additionally skipping every candidate for which
+    /// `taken_elsewhere` returns `true` (e.g. a database lookup). An error
+    /// from the check aborts the search and is returned as is.
+    pub fn slugify_checked<E: From<SlugifyError>>(
+        &mut self,
+        text: &str,
+        mut taken_elsewhere: impl FnMut(&str) -> Result<bool, E>,
+    ) -> Result<String, E> {
+        let slug = self.slugifier.slugify(text);
+        let mut candidate = slug.clone();
+        let mut attempt = 0;
+        while self.taken.contains(&candidate) || taken_elsewhere(&candidate)? {
+            attempt += 1;
+            candidate = suffixed(&slug, self.strategy, attempt, self.slugifier.options())?;
+        }
+        self.taken.insert(candidate.clone());
+        Ok(candidate)
+    }
+
+    /// Mark `slug` as taken. Returns `false` if it already was.
+    pub fn reserve(&mut self, slug: impl Into<String>) -> bool {
+        self.taken.insert(slug.into())
+    }
+
+    /// Forget `slug` so it can be handed out again. Returns `false` if it
+    /// was not registered.
+    pub fn release(&mut self, slug: &str) -> bool {
+        self.taken.remove(slug)
+    }
+
+    pub fn contains(&self, slug: &str) -> bool {
+        self.taken.contains(slug)
+    }
+
+    pub fn len(&self) -> usize {
+        self.taken.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.taken.is_empty()
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::expect_used)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_counter_suffixes() {
+        let mut reg = SlugRegistry::with_defaults().unwrap();
+        assert_eq!(reg.slugify("My Post").unwrap(), "my-post");
+        assert_eq!(reg.slugify("my post!").unwrap(), "my-post-2");
+        assert_eq!(reg.slugify("My  Post").unwrap(), "my-post-3");
+        assert!(reg.reserve("other"));
+        assert_eq!(reg.slugify("Other").unwrap(), "other-2");
+        assert!(reg.release("my-post-2"));
+        assert_eq!(reg.slugify("My Post").unwrap(), "my-post-2");
+        assert_eq!(reg.len(), 5);
+    }
+
+    #[test]
+    fn test_suffix_respects_max_length() {
+        let opts = SlugifyOptions::builder().max_length(8).build().unwrap();
+        let mut reg = SlugRegistry::new(opts);
+        assert_eq!(reg.slugify("My Post").unwrap(), "my-post");
+        assert_eq!(reg.slugify("My Post").unwrap(), "my-pos-2");
+        let opts = SlugifyOptions::builder().max_length(4).build().unwrap();
+        let mut reg = SlugRegistry::with_strategy(opts, SuffixStrategy::ShortHash(6));
+        reg.slugify("abcd").unwrap();
+        assert!(matches!(
+            reg.slugify("abcd"),
+            Err(SlugifyError::SuffixTooLong { .. })
+        ));
+    }
+
+    #[test]
+    fn test_hash_and_random_suffixes() {
+        let hash = SuffixStrategy::ShortHash(6);
+        assert_eq!(hash.suffix("my-post", 1), hash.suffix("my-post", 1));
+        assert_ne!(hash.suffix("my-post", 1), hash.suffix("my-post", 2));
+        assert_eq!(hash.suffix("my-post", 1).len(), 6);
+        assert_eq!(SuffixStrategy::RandomBase62(40).suffix("x", 1).len(), 10);
+
+        let mut reg = SlugRegistry::with_strategy(
+            SlugifyOptions::builder().build().unwrap(),
+            SuffixStrategy::RandomBase62(4),
+        );
+        assert_eq!(reg.slugify("Hello").unwrap(), "hello");
+        let second = reg.slugify("Hello").unwrap();
+        let suffix = second.strip_prefix("hello-").unwrap();
+        assert_eq!(suffix.len(), 4);
+        assert!(suffix.bytes().all(|b| b.is_ascii_alphanumeric()));
+    }
+
+    #[test]
+    fn test_salted_hash_suffixes() {
+        let acme = SuffixStrategy::salted_hash(6, "acme");
+        let globex = SuffixStrategy::salted_hash(6, "globex");
+        assert_eq!(acme, SuffixStrategy::salted_hash(6, "acme"));
+        assert_eq!(acme.suffix("my-post", 1), acme.suffix("my-post", 1));
+        assert_ne!(acme.suffix("my-post", 1), globex.suffix("my-post", 1));
+        assert_ne!(
+            acme.suffix("my-post", 1),
+            SuffixStrategy::ShortHash(6).suffix("my-post", 1)
+        );
+        assert_eq!(globex.suffix("my-post", 2).len(), 6);
+        // unsalted suffixes are unchanged
+        assert_eq!(SuffixStrategy::ShortHash(6).suffix("my-post", 1), "k5i86D");
+    }
+
+    #[test]
+    fn test_slugify_checked_consults_external_check() {
+        let mut reg = SlugRegistry::with_defaults().unwrap();
+        let db = ["my-post", "my-post-2"];
+        let mut asked = Vec::new();
+        let slug = reg
+            .slugify_checked("My Post", |s| {
+                asked.push(s.to_string());
+                Ok::<_, SlugifyError>(db.contains(&s))
+            })
+            .unwrap();
+        assert_eq!(slug, "my-post-3");
+        assert_eq!(asked, ["my-post", "my-post-2", "my-post-3"]);
+        // handed-out slugs are skipped without asking again
+        let slug = reg.slugify_checked("My Post", |s| Ok::<_, SlugifyError>(db.contains(&s)));
+        assert_eq!(slug.unwrap(), "my-post-4");
+
+        #[derive(Debug, PartialEq)]
+        enum DbError {
+            Down,
+            Slug,
+        }
+        impl From<SlugifyError> for DbError {
+            fn from(_: SlugifyError) -> Self {
+                DbError::Slug
+            }
+        }
+        assert_eq!(reg.slugify_checked("Other", |_| Err(DbError::Down)), Err(DbError::Down));
+        assert!(!reg.contains("other"));
+        let mut tiny = SlugRegistry::new(SlugifyOptions::builder().max_length(2).build().unwrap());
+        assert_eq!(tiny.slugify_checked("ab", |_| Ok(true)), Err(DbError::Slug));
+    }
+}
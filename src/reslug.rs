@@ -0,0 +1,300 @@
+// Decide whether a title edit should change an existing slug, so CMSs can
+// share one policy instead of re-implementing it, and renormalize slugs
+// stored under older options.
+use std::collections::BTreeSet;
+
+use crate::punycode;
+use crate::slugify::{
+    CaseStyle, RtlWordOrder, SlugifyOptions, logical_word_order, slugify_with_options_public,
+    split_words,
+};
+
+/// Similarity below which `reslug_if_needed` changes the slug.
+pub const DEFAULT_RESLUG_THRESHOLD: f64 = 0.5;
+
+/// Outcome of `reslug_if_needed`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ReslugDecision {
+    /// Keep the current slug; the edit was minor (or the slug was customized).
+    Keep { slug: String },
+    /// Switch to `slug`. `redirect_from` holds the old slug callers should
+    /// redirect to the new one (`None` when there was no old slug).
+    Change {
+        slug: String,
+        redirect_from: Option<String>,
+    },
+}
+
+impl ReslugDecision {
+    /// The slug to use after the edit.
+    pub fn slug(&self) -> &str {
+        match self {
+            ReslugDecision::Keep { slug } | ReslugDecision::Change { slug, .. } => slug,
+        }
+    }
+}
+
+/// `reslug_with_threshold` with `DEFAULT_RESLUG_THRESHOLD`.
+pub fn reslug_if_needed(
+    old_title: &str,
+    new_title: &str,
+    old_slug: &str,
+    opts: &SlugifyOptions,
+) -> ReslugDecision {
+    reslug_with_threshold(
+        old_title,
+        new_title,
+        old_slug,
+        opts,
+        DEFAULT_RESLUG_THRESHOLD,
+    )
+}
+
+/// Decide whether renaming `old_title` to `new_title` should replace
+/// `old_slug`.
+///
+/// The slugs of both titles are compared word by word (Jaccard similarity
+/// over the words split on `opts.separator`, in `0.0..=1.0`). The slug
+/// changes when the similarity is below `threshold`. Rules, in order:
+/// - an empty `old_slug` always takes the new slug (no redirect);
+/// - an `old_slug` that is not what `old_title` slugifies to was customized
+///   by hand and is kept;
+/// - a new title that slugifies to nothing keeps the old slug.
+pub fn reslug_with_threshold(
+    old_title: &str,
+    new_title: &str,
+    old_slug: &str,
+    opts: &SlugifyOptions,
+    threshold: f64,
+) -> ReslugDecision {
+    let new_slug = slugify_with_options_public(opts, new_title);
+    let keep = || ReslugDecision::Keep {
+        slug: old_slug.to_string(),
+    };
+    if old_slug.is_empty() {
+        return ReslugDecision::Change {
+            slug: new_slug,
+            redirect_from: None,
+        };
+    }
+    if new_slug.is_empty() || new_slug == old_slug {
+        return keep();
+    }
+    let generated = slugify_with_options_public(opts, old_title);
+    if generated != old_slug {
+        return keep();
+    }
+    if word_similarity(&generated, &new_slug, &opts.separator) >= threshold {
+        return keep();
+    }
+    ReslugDecision::Change {
+        slug: new_slug,
+        redirect_from: Some(old_slug.to_string()),
+    }
+}
+
+/// Normalize an existing slug under `opts`, e.g. a legacy slug during a
+/// content migration: separators are collapsed and converted ("my--post_"
+/// -> "my-post"), and `max_length`, stopwords and the other options are
+/// applied again.
+///
+/// For any text `x`, `reslugify(&slugify(x), opts) == slugify(x)`, so slugs
+/// that are already normalized are left alone. Plain `slugify` only has
+/// that property for separated styles: camel and Pascal slugs have lost
+/// their word boundaries ("helloWorld" slugifies to "helloworld"), IDNA
+/// labels are Punycode and visual RTL order would be reversed again, so
+/// `reslugify` undoes those forms first. User `replacements` whose output
+/// matches again ("a" -> "ab") are applied again as well.
+pub fn reslugify(existing_slug: &str, opts: &SlugifyOptions) -> String {
+    let mut text = existing_slug.to_string();
+    if opts.idna {
+        text = text
+            .split('.')
+            .map(|label| {
+                label
+                    .strip_prefix("xn--")
+                    .and_then(punycode::decode)
+                    .unwrap_or_else(|| label.to_string())
+            })
+            .collect::<Vec<_>>()
+            .join(".");
+    }
+    if opts.rtl_word_order == RtlWordOrder::Visual {
+        let sep = match opts.case_style {
+            CaseStyle::Snake | CaseStyle::ScreamingSnake => "_",
+            _ => opts.separator.as_str(),
+        };
+        if !sep.is_empty() {
+            text = logical_word_order(&text, sep);
+        }
+    }
+    if matches!(opts.case_style, CaseStyle::Camel | CaseStyle::Pascal) {
+        text = split_case_words(&text);
+    }
+    slugify_with_options_public(opts, &text)
+}
+
+// Put a space before every uppercase letter that follows another
+// character: "johnSIphone15" -> "john S Iphone15".
+fn split_case_words(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 8);
+    for c in s.chars() {
+        if c.is_uppercase() && !out.is_empty() {
+            out.push(' ');
+        }
+        out.push(c);
+    }
+    out
+}
+
+fn word_similarity(a: &str, b: &str, separator: &str) -> f64 {
+    let a: BTreeSet<&str> = split_words(a, separator).collect();
+    let b: BTreeSet<&str> = split_words(b, separator).collect();
+    let union = a.union(&b).count();
+    if union == 0 {
+        return 1.0;
+    }
+    a.intersection(&b).count() as f64 / union as f64
+}
+
+#[cfg(test)]
+#[allow(clippy::expect_used)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::slugify::{Compat, SlugifyOptionsBuilder};
+
+    #[test]
+    fn test_reslug_keeps_slug_on_minor_edit() {
+        let opts = SlugifyOptions::builder().build().unwrap();
+        let d = reslug_if_needed(
+            "Ten tips for Rust",
+            "Ten tips for Rust!",
+            "ten-tips-for-rust",
+            &opts,
+        );
+        assert_eq!(d.slug(), "ten-tips-for-rust");
+        // 4 of 5 words shared
+        let d = reslug_if_needed(
+            "Ten tips for Rust",
+            "Ten great tips for Rust",
+            "ten-tips-for-rust",
+            &opts,
+        );
+        assert!(matches!(d, ReslugDecision::Keep { .. }));
+        // customized slugs are never replaced
+        let d = reslug_if_needed("Ten tips", "Something else", "my-tips", &opts);
+        assert_eq!(d.slug(), "my-tips");
+    }
+
+    #[test]
+    fn test_reslug_changes_slug_with_redirect() {
+        let opts = SlugifyOptions::builder().build().unwrap();
+        let d = reslug_if_needed("Draft", "Launch announcement", "draft", &opts);
+        assert_eq!(
+            d,
+            ReslugDecision::Change {
+                slug: "launch-announcement".to_string(),
+                redirect_from: Some("draft".to_string()),
+            }
+        );
+        let d = reslug_if_needed("", "Hello", "", &opts);
+        assert_eq!(
+            d,
+            ReslugDecision::Change {
+                slug: "hello".to_string(),
+                redirect_from: None,
+            }
+        );
+        // a stricter threshold turns a minor edit into a change
+        let d = reslug_with_threshold(
+            "Ten tips for Rust",
+            "Ten great tips for Rust",
+            "ten-tips-for-rust",
+            &opts,
+            0.9,
+        );
+        assert_eq!(d.slug(), "ten-great-tips-for-rust");
+    }
+
+    #[test]
+    fn test_reslugify_normalizes_legacy_slugs() {
+        let opts = SlugifyOptions::builder()
+            .stopwords(["the"])
+            .max_length(12)
+            .word_boundary(true)
+            .build()
+            .unwrap();
+        assert_eq!(reslugify("my--post_", &opts), "my-post");
+        assert_eq!(reslugify("-The_Quick-Brown-Fox-", &opts), "quick-brown");
+        assert_eq!(reslugify("", &opts), "");
+
+        let camel = SlugifyOptions::builder()
+            .case_style(CaseStyle::Camel)
+            .build()
+            .unwrap();
+        assert_eq!(reslugify("helloWorld", &camel), "helloWorld");
+        assert_eq!(
+            slugify_with_options_public(&camel, "helloWorld"),
+            "helloworld"
+        );
+        assert_eq!(reslugify("hello-world", &camel), "helloWorld");
+
+        let idna = SlugifyOptions::builder()
+            .allow_unicode(true)
+            .idna(true)
+            .build()
+            .unwrap();
+        assert_eq!(reslugify("xn--mnchen-3ya", &idna), "xn--mnchen-3ya");
+        assert_eq!(reslugify("München", &idna), "xn--mnchen-3ya");
+    }
+
+    #[test]
+    fn test_reslugify_fixes_slugify_output() {
+        let build = |f: fn(SlugifyOptionsBuilder) -> SlugifyOptionsBuilder| {
+            f(SlugifyOptions::builder()).build().unwrap()
+        };
+        let option_sets = [
+            build(|b| b),
+            build(|b| b.separator("_")),
+            build(|b| b.separator("")),
+            build(|b| b.max_length(10)),
+            build(|b| b.max_length(12).word_boundary(true).save_order(true)),
+            build(|b| b.stopwords(["the", "a"])),
+            build(|b| b.lowercase(false)),
+            build(|b| b.allow_unicode(true)),
+            build(|b| b.case_style(CaseStyle::Camel)),
+            build(|b| b.case_style(CaseStyle::Pascal).allow_unicode(true)),
+            build(|b| b.case_style(CaseStyle::ScreamingSnake)),
+            build(|b| b.max_length(8).preserve_trailing_number(true)),
+            build(|b| b.allow_unicode(true).idna(true)),
+            build(|b| b.allow_unicode(true).rtl_word_order(RtlWordOrder::Visual)),
+            build(|b| {
+                b.allow_unicode(true)
+                    .case_style(CaseStyle::Snake)
+                    .rtl_word_order(RtlWordOrder::Visual)
+            }),
+            build(|b| b.compat(Compat::Django)),
+        ];
+        let inputs = [
+            "Hello World",
+            "C'est déjà l'été!",
+            "The Quick Brown Fox jumps over the lazy dog",
+            "  a -- b __ c  ",
+            "John's iPhone 15 Pro Max",
+            "Ünïcode Straße",
+            "東京「タワー」",
+            "2 שלום עולם hello 3",
+            "&2.ל _東",
+            "-- legacy__slug --",
+        ];
+        for opts in &option_sets {
+            for text in inputs {
+                let slug = slugify_with_options_public(opts, text);
+                assert_eq!(reslugify(&slug, opts), slug, "{:?}", text);
+                let once = reslugify(text, opts);
+                assert_eq!(reslugify(&once, opts), once, "{:?}", text);
+            }
+        }
+    }
+}
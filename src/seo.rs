@@ -0,0 +1,233 @@
+// SEO metadata derived from one title: the URL slug, a `<title>` text that
+// fits search result snippets, and a file name for exported assets (social
+// images, PDFs). The slug, the file name and the word count come from the
+// same slug words, so they never disagree.
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::entities::{self, EntityOptions};
+use crate::slugify::{
+    SlugifyOptions, finish_slug, internal_words, slugify_with_options_public, split_words,
+};
+
+/// Maximum length of `SeoMetadata::meta_title`, in characters; longer
+/// titles are cut in search result snippets.
+pub const META_TITLE_MAX_LENGTH: usize = 60;
+
+// Usual limit of file name length on common file systems.
+const FILENAME_MAX_BYTES: usize = 255;
+
+// Device names Windows reserves regardless of extension.
+const RESERVED_FILENAMES: &[&str] = &[
+    "aux", "com1", "com2", "com3", "com4", "com5", "com6", "com7", "com8", "com9", "con", "lpt1",
+    "lpt2", "lpt3", "lpt4", "lpt5", "lpt6", "lpt7", "lpt8", "lpt9", "nul", "prn",
+];
+
+/// Slug, meta title and file name of one title.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SeoMetadata {
+    /// Same as `slugify_with_options_public(opts, title)`.
+    pub slug: String,
+    /// The title with references decoded and whitespace collapsed, cut to
+    /// `META_TITLE_MAX_LENGTH` characters on a word boundary.
+    pub meta_title: String,
+    /// The slug words joined with the separator (`-` when the separator is
+    /// empty or not allowed in file names), without characters reserved
+    /// on common file systems and within 255 bytes. Empty when the slug is.
+    pub filename: String,
+    /// Number of words in the slug.
+    pub word_count: usize,
+}
+
+/// Build the slug, meta title and file name of `title` in one go.
+///
+/// ```
+/// use slugify_rs::slugify::SlugifyOptions;
+/// use slugify_rs::seo::seo_metadata;
+///
+/// let opts = SlugifyOptions::builder().separator("/").build().unwrap();
+/// let seo = seo_metadata("Tom &amp; Jerry:   The Movie", &opts);
+/// assert_eq!(seo.slug, "tom/jerry/the/movie");
+/// assert_eq!(seo.meta_title, "Tom & Jerry: The Movie");
+/// assert_eq!(seo.filename, "tom-jerry-the-movie");
+/// assert_eq!(seo.word_count, 4);
+/// ```
+pub fn seo_metadata(title: &str, opts: &SlugifyOptions) -> SeoMetadata {
+    let (slug, words): (String, Vec<String>) = match internal_words(title, opts, true) {
+        Some((words, sep)) => (
+            finish_slug(&words, sep, opts),
+            split_words(&words, sep).map(str::to_string).collect(),
+        ),
+        // `compat` modes have no internal words; split the finished slug
+        None => {
+            let slug = slugify_with_options_public(opts, title);
+            let words = split_words(&slug, &opts.separator)
+                .map(str::to_string)
+                .collect();
+            (slug, words)
+        }
+    };
+    SeoMetadata {
+        meta_title: meta_title(title, opts),
+        filename: filename(&words, &opts.separator),
+        word_count: words.len(),
+        slug,
+    }
+}
+
+/// `title` as shown in a `<title>` tag: references decoded as `opts`
+/// decodes them, control characters and whitespace runs replaced by one
+/// space, and cut to `META_TITLE_MAX_LENGTH` characters at the last space
+/// that fits (or inside the first word when none does). Punctuation left
+/// dangling at the cut (`,`, `:`, `-`, ...) is removed.
+pub fn meta_title(title: &str, opts: &SlugifyOptions) -> String {
+    let decoded = entities::decode(title, &EntityOptions::from(opts));
+    let words: Vec<&str> = decoded
+        .split(|c: char| c.is_whitespace() || c.is_control())
+        .filter(|w| !w.is_empty())
+        .collect();
+    let mut out = String::new();
+    let mut len = 0;
+    for word in words {
+        let word_len = word.chars().count();
+        let needed = word_len + usize::from(!out.is_empty());
+        if len + needed > META_TITLE_MAX_LENGTH {
+            if out.is_empty() {
+                out = cut_graphemes(word, META_TITLE_MAX_LENGTH, |g| g.chars().count());
+            }
+            let kept = out.trim_end_matches(|c| c == ' ' || is_dangling(c)).len();
+            if kept > 0 {
+                out.truncate(kept);
+            }
+            break;
+        }
+        if !out.is_empty() {
+            out.push(' ');
+        }
+        out.push_str(word);
+        len += needed;
+    }
+    out
+}
+
+fn is_dangling(c: char) -> bool {
+    matches!(c, ',' | ';' | ':' | '-' | '–' | '—' | '|' | '/' | '(' | '[')
+}
+
+// Longest prefix of `s` made of whole graphemes whose total `size` is at
+// most `max`.
+fn cut_graphemes(s: &str, max: usize, size: impl Fn(&str) -> usize) -> String {
+    let mut out = String::new();
+    let mut total = 0;
+    for g in s.graphemes(true) {
+        total += size(g);
+        if total > max {
+            break;
+        }
+        out.push_str(g);
+    }
+    out
+}
+
+// Slug words joined into a file name; see `SeoMetadata::filename`.
+fn filename(words: &[String], separator: &str) -> String {
+    let separator = if separator.is_empty() || separator.chars().any(is_reserved_char) {
+        "-"
+    } else {
+        separator
+    };
+    let mut out = String::new();
+    for word in words {
+        let word: String = word.chars().filter(|&c| !is_reserved_char(c)).collect();
+        if word.is_empty() {
+            continue;
+        }
+        let needed = if out.is_empty() { 0 } else { separator.len() } + word.len();
+        if out.len() + needed > FILENAME_MAX_BYTES {
+            if out.is_empty() {
+                out = cut_graphemes(&word, FILENAME_MAX_BYTES, str::len);
+            }
+            break;
+        }
+        if !out.is_empty() {
+            out.push_str(separator);
+        }
+        out.push_str(&word);
+    }
+    // Windows drops trailing dots and spaces
+    out.truncate(out.trim_end_matches(['.', ' ']).len());
+    let stem = out.split('.').next().unwrap_or_default();
+    if RESERVED_FILENAMES.contains(&stem.to_ascii_lowercase().as_str()) {
+        out.insert(stem.len(), '_');
+    }
+    out
+}
+
+fn is_reserved_char(c: char) -> bool {
+    c.is_control() || matches!(c, '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|')
+}
+
+#[cfg(test)]
+#[allow(clippy::expect_used)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::slugify::{CaseStyle, Compat};
+
+    #[test]
+    fn test_seo_metadata_consistent() {
+        let opts = SlugifyOptions::builder().build().unwrap();
+        let title = "C'est déjà l'été: 10 things to do in Paris this summer (2025 edition)";
+        let seo = seo_metadata(title, &opts);
+        assert_eq!(seo.slug, slugify_with_options_public(&opts, title));
+        assert_eq!(seo.filename, seo.slug);
+        assert_eq!(seo.word_count, seo.slug.split('-').count());
+        assert_eq!(
+            seo.meta_title,
+            "C'est déjà l'été: 10 things to do in Paris this summer (2025"
+        );
+
+        let opts = SlugifyOptions::builder()
+            .case_style(CaseStyle::Camel)
+            .max_length(12)
+            .build()
+            .unwrap();
+        let seo = seo_metadata("Hello big world", &opts);
+        assert_eq!(seo.slug, "helloBigWorl");
+        assert_eq!(seo.filename, "hello-big-worl");
+        assert_eq!(seo.word_count, 3);
+
+        let opts = SlugifyOptions::builder()
+            .compat(Compat::GitHub)
+            .build()
+            .unwrap();
+        let seo = seo_metadata("Hello World", &opts);
+        assert_eq!((seo.filename.as_str(), seo.word_count), ("hello-world", 2));
+
+        let seo = seo_metadata("!!!", &SlugifyOptions::builder().build().unwrap());
+        assert_eq!((seo.slug.as_str(), seo.filename.as_str()), ("", ""));
+        assert_eq!(seo.word_count, 0);
+    }
+
+    #[test]
+    fn test_meta_title() {
+        let opts = SlugifyOptions::builder().build().unwrap();
+        assert_eq!(meta_title("  Short\ttitle\n", &opts), "Short title");
+        let long = format!("{} tail", "word ".repeat(12));
+        assert_eq!(meta_title(&long, &opts).chars().count(), 59);
+        assert_eq!(meta_title(&"x".repeat(80), &opts), "x".repeat(60));
+        let dangling = format!("{}, and more words", "a".repeat(56));
+        assert_eq!(meta_title(&dangling, &opts), "a".repeat(56));
+    }
+
+    #[test]
+    fn test_filename() {
+        let words = |ws: &[&str]| ws.iter().map(|w| w.to_string()).collect::<Vec<_>>();
+        assert_eq!(filename(&words(&["con"]), "-"), "con_");
+        assert_eq!(filename(&words(&["aux.txt"]), "-"), "aux_.txt");
+        assert_eq!(filename(&words(&["a:b", "c."]), ":"), "ab-c");
+        assert_eq!(filename(&words(&["ab", "cd"]), ""), "ab-cd");
+        let long = words(&[&"a".repeat(200), &"b".repeat(100)]);
+        assert_eq!(filename(&long, "-"), "a".repeat(200));
+        assert_eq!(filename(&words(&[&"é".repeat(200)]), "-").len(), 254);
+    }
+}
@@ -0,0 +1,432 @@
+// `Slug` newtype: a string that is known to be a valid slug.
+use std::fmt;
+use std::str::FromStr;
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+use crate::slugifier::{DEFAULT_OPTIONS, DEFAULT_SLUGIFIER};
+use crate::slugify::{
+    Compat, SlugifyError, SlugifyOptions, slug_is_lowercase, slug_pattern, slug_separator,
+    slugify_with_options_public, word_class,
+};
+
+#[allow(clippy::expect_used)]
+static DEFAULT_SLUG_PATTERN: Lazy<Regex> =
+    Lazy::new(|| Regex::new(&slug_pattern(&DEFAULT_OPTIONS)).expect("default slug pattern"));
+
+/// A non-empty slug without leading, trailing or repeated separators whose
+/// words only use the allowed characters (`[a-z0-9]` separated by `-` for
+/// the default options). GitHub anchors (`Compat::GitHub`) may have any
+/// number of `-` anywhere.
+///
+/// A `Slug` can only be obtained by slugifying text (`new`, `with_options`)
+/// or by validating an existing slug (`parse`, `parse_with`, `TryFrom`,
+/// `FromStr`), so functions taking a `Slug` need not re-check it. With the
+/// `serde` feature it serializes as a plain string and deserialization
+/// validates like `parse`.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Slug(String);
+
+impl Slug {
+    /// Slugify `text` with the default options. Fails with
+    /// `SlugifyError::InvalidSlug` when nothing sluggable is left ("!!!").
+    pub fn new(text: &str) -> Result<Self, SlugifyError> {
+        Slug::checked(DEFAULT_SLUGIFIER.slugify(text), &DEFAULT_SLUG_PATTERN)
+    }
+
+    /// Slugify `text` with `opts`. A custom `regex_pattern` only guarantees a
+    /// non-empty slug.
+    pub fn with_options(text: &str, opts: &SlugifyOptions) -> Result<Self, SlugifyError> {
+        let rx = pattern_for(opts)?;
+        Slug::checked(slugify_with_options_public(opts, text), &rx)
+    }
+
+    /// Accept `s` only if it is already a valid slug under the default
+    /// options: `parse("my-post")` succeeds, `parse("My Post")` does not.
+    pub fn parse(s: &str) -> Result<Self, SlugifyError> {
+        Slug::checked(s.to_string(), &DEFAULT_SLUG_PATTERN)
+    }
+
+    /// Like `parse`, validating against the slugs `opts` can produce (see
+    /// `slug_pattern`).
+    pub fn parse_with(s: &str, opts: &SlugifyOptions) -> Result<Self, SlugifyError> {
+        Slug::checked(s.to_string(), &pattern_for(opts)?)
+    }
+
+    fn checked(s: String, rx: &Regex) -> Result<Self, SlugifyError> {
+        if rx.is_match(&s) {
+            Ok(Slug(s))
+        } else {
+            Err(SlugifyError::InvalidSlug(s))
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    pub fn into_string(self) -> String {
+        self.0
+    }
+}
+
+fn pattern_for(opts: &SlugifyOptions) -> Result<Regex, SlugifyError> {
+    let pattern = slug_pattern(opts);
+    Regex::new(&pattern).map_err(|_| SlugifyError::InvalidRegex(pattern))
+}
+
+impl fmt::Display for Slug {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl FromStr for Slug {
+    type Err = SlugifyError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Slug::parse(s)
+    }
+}
+
+impl TryFrom<&str> for Slug {
+    type Error = SlugifyError;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        Slug::parse(s)
+    }
+}
+
+impl TryFrom<String> for Slug {
+    type Error = SlugifyError;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        Slug::checked(s, &DEFAULT_SLUG_PATTERN)
+    }
+}
+
+impl AsRef<str> for Slug {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<Slug> for String {
+    fn from(slug: Slug) -> Self {
+        slug.0
+    }
+}
+
+/// Everything that keeps a string from being a slug under some options, for
+/// form validation that points at the problem; see `validate_slug`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SlugReport {
+    /// Byte offset and value of each character that slug words cannot
+    /// contain (uppercase with `lowercase`, non-ASCII without
+    /// `allow_unicode`, spaces, punctuation, ...). Always empty with a
+    /// custom `regex_pattern`.
+    pub invalid_chars: Vec<(usize, char)>,
+    /// Length in `length_unit`, when it exceeds `max_length`.
+    pub too_long: Option<usize>,
+    /// The string is empty.
+    pub empty: bool,
+    /// The string starts with the separator.
+    pub leading_separator: bool,
+    /// The string ends with the separator.
+    pub trailing_separator: bool,
+    /// The separator appears twice in a row somewhere.
+    pub repeated_separator: bool,
+}
+
+impl SlugReport {
+    /// True when no problem was found.
+    pub fn is_valid(&self) -> bool {
+        *self == SlugReport::default()
+    }
+}
+
+/// Check `s` against the slugs `opts` can produce and report every problem
+/// found instead of stopping at the first one.
+///
+/// The character rules are those of `slug_pattern`; in addition the length
+/// is checked against `max_length`. `validate_slug("My--Post-", &opts)` with
+/// the default options reports the invalid `M` (0) and `P` (4), the
+/// repeated separator and the trailing separator.
+pub fn validate_slug(s: &str, opts: &SlugifyOptions) -> SlugReport {
+    let sep = slug_separator(opts);
+    let mut report = SlugReport {
+        empty: s.is_empty(),
+        ..SlugReport::default()
+    };
+    let length = opts.length_unit.measure(s);
+    if opts.max_length > 0 && length > opts.max_length {
+        report.too_long = Some(length);
+    }
+    // a punycode label (`idna`) has its own alphabet and may repeat `-`
+    if opts.idna && opts.compat == Compat::None && s.starts_with("xn--") {
+        let lowercase = slug_is_lowercase(opts);
+        let ace = |c: char| {
+            c.is_ascii_lowercase() || (!lowercase && c.is_ascii_uppercase()) || c.is_ascii_digit()
+        };
+        report.invalid_chars = s
+            .char_indices()
+            .filter(|&(_, c)| !ace(c) && c != '-' && !sep.contains(c))
+            .collect();
+        return report;
+    }
+    if !sep.is_empty() {
+        report.leading_separator = s.starts_with(sep);
+        report.trailing_separator = s.len() > sep.len() && s.ends_with(sep);
+        report.repeated_separator = s.contains(&sep.repeat(2));
+    }
+    // the classes are fixed strings known to compile
+    #[allow(clippy::expect_used)]
+    let word = word_class(opts)
+        .map(|class| Regex::new(&format!("^{}$", class)).expect("word class pattern"));
+    let Some(word) = word else {
+        return report;
+    };
+    let mut i = 0;
+    while i < s.len() {
+        if !sep.is_empty() && s[i..].starts_with(sep) {
+            i += sep.len();
+            continue;
+        }
+        let Some(c) = s[i..].chars().next() else {
+            break;
+        };
+        if !word.is_match(c.encode_utf8(&mut [0; 4])) {
+            report.invalid_chars.push((i, c));
+        }
+        i += c.len_utf8();
+    }
+    report
+}
+
+/// True when `s` is a slug `opts` can produce; see `validate_slug`.
+pub fn is_valid_slug(s: &str, opts: &SlugifyOptions) -> bool {
+    validate_slug(s, opts).is_valid()
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for Slug {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.0)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Slug {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        Slug::try_from(s).map_err(|e| serde::de::Error::custom(format!("{:?}", e)))
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::expect_used)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::slugify::{CaseStyle, IdentifierStyle, Separator, SeparatorScope};
+
+    #[test]
+    fn test_slug_constructors_enforce_invariants() {
+        let slug = Slug::new("Hello, World!").unwrap();
+        assert_eq!(slug.as_str(), "hello-world");
+        assert_eq!(slug.to_string(), "hello-world");
+        assert_eq!("hello-world".parse::<Slug>().unwrap(), slug);
+        assert!(matches!(Slug::new("!!!"), Err(SlugifyError::InvalidSlug(_))));
+        for bad in ["", "-a", "a-", "a--b", "Hello", "a b", "é"] {
+            assert!(Slug::parse(bad).is_err(), "{:?}", bad);
+            assert!(Slug::try_from(bad.to_string()).is_err(), "{:?}", bad);
+        }
+
+        let opts = SlugifyOptions::builder().separator("_").lowercase(false).build().unwrap();
+        let slug = Slug::with_options("Hello World", &opts).unwrap();
+        assert_eq!(String::from(slug), "Hello_World");
+        assert!(Slug::parse_with("Hello_World", &opts).is_ok());
+        assert!(Slug::parse_with("hello-world", &opts).is_err());
+    }
+
+    #[test]
+    fn test_validate_slug() {
+        let opts = SlugifyOptions::builder().build().unwrap();
+        assert!(is_valid_slug("my-post-2", &opts));
+        let report = validate_slug("My--Post-", &opts);
+        assert_eq!(report.invalid_chars, [(0, 'M'), (4, 'P')]);
+        assert!(report.repeated_separator && report.trailing_separator);
+        assert!(!report.leading_separator && !report.empty);
+        assert!(!report.is_valid());
+        assert_eq!(
+            validate_slug("-é b", &opts).invalid_chars,
+            [(1, 'é'), (3, ' ')]
+        );
+        assert!(validate_slug("", &opts).empty);
+        // a lone separator is leading, not trailing as well
+        let report = validate_slug("-", &opts);
+        assert!(report.leading_separator && !report.trailing_separator);
+
+        let opts = SlugifyOptions::builder()
+            .separator("__")
+            .allow_unicode(true)
+            .max_length(6)
+            .build()
+            .unwrap();
+        let report = validate_slug("été__à_b", &opts);
+        assert_eq!(report.invalid_chars, [(9, '_')]);
+        assert_eq!(report.too_long, Some(8));
+        assert!(is_valid_slug("été__à", &opts));
+
+        // a custom pattern only rules out empty and separator problems
+        let opts = SlugifyOptions::builder()
+            .regex_pattern(Some(r"[^A-Z]+"))
+            .build()
+            .unwrap();
+        assert!(is_valid_slug("ANY THING", &opts));
+        assert!(!is_valid_slug("a-", &opts));
+    }
+
+    #[test]
+    fn test_case_styles_round_trip() {
+        for (style, slug) in [
+            (CaseStyle::Camel, "helloWorld2"),
+            (CaseStyle::Pascal, "HelloWorld2"),
+            (CaseStyle::Snake, "hello_world_2"),
+            (CaseStyle::ScreamingSnake, "HELLO_WORLD_2"),
+        ] {
+            let opts = SlugifyOptions::builder().case_style(style).build().unwrap();
+            let made = Slug::with_options("Hello World 2", &opts);
+            assert_eq!(made.unwrap().as_str(), slug);
+            assert!(Slug::parse_with(slug, &opts).is_ok());
+            assert!(is_valid_slug(slug, &opts));
+        }
+        let opts = SlugifyOptions::builder()
+            .case_style(CaseStyle::Snake)
+            .build()
+            .unwrap();
+        assert!(!is_valid_slug("Hello_World", &opts));
+        assert!(!is_valid_slug("hello-world", &opts));
+    }
+
+    #[test]
+    fn test_identifiers_round_trip() {
+        let opts = SlugifyOptions::builder()
+            .identifier(IdentifierStyle::python())
+            .build()
+            .unwrap();
+        for (text, ident) in [("2nd class", "_2nd_class"), ("class", "class_")] {
+            assert_eq!(Slug::with_options(text, &opts).unwrap().as_str(), ident);
+            assert!(is_valid_slug(ident, &opts));
+        }
+        assert_eq!(slug_pattern(&opts), "^.+$");
+    }
+
+    #[test]
+    fn test_idna_round_trip() {
+        for sep in ["-", "_"] {
+            let opts = SlugifyOptions::builder()
+                .allow_unicode(true)
+                .idna(true)
+                .separator(sep)
+                .build()
+                .unwrap();
+            for text in ["München", "München Stadt", "plain words"] {
+                let slug = Slug::with_options(text, &opts).unwrap();
+                assert!(is_valid_slug(slug.as_str(), &opts), "{:?}", slug);
+            }
+            assert!(!is_valid_slug("xn--Mnchen-3ya", &opts));
+        }
+        let opts = SlugifyOptions::builder().idna(true).build().unwrap();
+        assert!(Slug::parse_with("xn--mnchen-3ya", &opts).is_ok());
+        assert!(!is_valid_slug("mnchen--3ya", &opts));
+        let opts = SlugifyOptions::builder()
+            .allow_unicode(true)
+            .lowercase(false)
+            .idna(true)
+            .build()
+            .unwrap();
+        assert!(Slug::with_options("München Stadt", &opts).is_ok());
+    }
+
+    #[test]
+    fn test_github_anchor_round_trip() {
+        let opts = SlugifyOptions::github_anchor();
+        for (heading, anchor) in [
+            ("Foo & Bar_baz!", "foo--bar_baz"),
+            ("  Leading space", "--leading-space"),
+            ("Déjà vu", "déjà-vu"),
+        ] {
+            assert_eq!(Slug::with_options(heading, &opts).unwrap().as_str(), anchor);
+            assert!(validate_slug(anchor, &opts).is_valid());
+        }
+        assert_eq!(validate_slug("Foo", &opts).invalid_chars, [(0, 'F')]);
+        assert!(!is_valid_slug("a b", &opts));
+    }
+
+    #[test]
+    fn test_django_round_trip() {
+        for allow_unicode in [false, true] {
+            let opts = SlugifyOptions::builder()
+                .compat(Compat::Django)
+                .allow_unicode(allow_unicode)
+                .build()
+                .unwrap();
+            let slug = Slug::with_options("foo_bar baz  a__b", &opts).unwrap();
+            assert_eq!(slug.as_str(), "foo_bar-baz-a__b");
+            assert!(is_valid_slug(slug.as_str(), &opts));
+            assert!(!is_valid_slug("foo--bar", &opts));
+            assert!(!is_valid_slug("Foo_bar", &opts));
+        }
+    }
+
+    #[test]
+    fn test_inserted_scope_round_trip() {
+        let opts = SlugifyOptions::builder()
+            .separator("_")
+            .separator_scope(SeparatorScope::Inserted)
+            .build()
+            .unwrap();
+        let slug = Slug::with_options("a-b c", &opts).unwrap();
+        assert_eq!(slug.as_str(), "a-b_c");
+        assert!(is_valid_slug(slug.as_str(), &opts));
+        assert!(!is_valid_slug("a-b__c", &opts));
+        // every hyphen is rewritten by default
+        let opts = SlugifyOptions::builder().separator("_").build().unwrap();
+        assert!(!is_valid_slug("a-b_c", &opts));
+    }
+
+    #[test]
+    fn test_separator_presets_round_trip() {
+        let presets = [
+            Separator::Hyphen,
+            Separator::Underscore,
+            Separator::Dot,
+            Separator::None,
+            Separator::Custom("~".to_string()),
+        ];
+        for sep in presets {
+            for scope in [SeparatorScope::All, SeparatorScope::Inserted] {
+                let opts = SlugifyOptions::builder()
+                    .separator(sep.clone())
+                    .separator_scope(scope)
+                    .build()
+                    .unwrap();
+                let slug = Slug::with_options("a-b c  d--e Hello World", &opts);
+                assert!(slug.is_ok(), "{:?} {:?}: {:?}", sep, scope, slug);
+            }
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_slug_deserialize_validates() {
+        use serde::Deserialize;
+        use serde::de::value::{Error, StrDeserializer};
+
+        let ok = Slug::deserialize(StrDeserializer::<Error>::new("my-post")).unwrap();
+        assert_eq!(ok.as_str(), "my-post");
+        assert!(Slug::deserialize(StrDeserializer::<Error>::new("My Post")).is_err());
+    }
+}
@@ -0,0 +1,253 @@
+// Reusable slugifier holding validated options, plus a shared default
+// instance for callers who don't want to manage one.
+use once_cell::sync::Lazy;
+use std::cell::RefCell;
+use std::sync::{Arc, PoisonError, RwLock};
+
+use crate::slugify::{
+    SlugifyError, SlugifyOptions, projected_length, slugify_batch, slugify_with_options_public,
+    try_slugify_with_options,
+};
+
+/// Options built once from the builder defaults.
+///
+/// Building can only fail for an invalid regex, and the defaults carry none.
+#[allow(clippy::expect_used)]
+pub static DEFAULT_OPTIONS: Lazy<SlugifyOptions> = Lazy::new(|| {
+    SlugifyOptions::builder()
+        .build()
+        .expect("default SlugifyOptions must build")
+});
+
+/// `Slugifier` with the builder defaults, used by `slugify_fast`.
+#[allow(clippy::expect_used)]
+pub static DEFAULT_SLUGIFIER: Lazy<Slugifier> =
+    Lazy::new(|| Slugifier::with_defaults().expect("default SlugifyOptions must build"));
+
+/// Slugifies many strings with one set of options.
+///
+/// Regexes and dictionaries are compiled once when the options are built
+/// instead of on every call. A `Slugifier` is `Send + Sync` and only takes
+/// `&self`, so a single instance can be shared across threads (e.g. in a
+/// `static` or an `Arc`).
+pub struct Slugifier {
+    opts: SlugifyOptions,
+}
+
+impl Slugifier {
+    pub fn new(opts: SlugifyOptions) -> Self {
+        Slugifier { opts }
+    }
+
+    /// Build a `Slugifier` with the builder defaults.
+    pub fn with_defaults() -> Result<Self, SlugifyError> {
+        Ok(Slugifier::new(SlugifyOptions::builder().build()?))
+    }
+
+    pub fn options(&self) -> &SlugifyOptions {
+        &self.opts
+    }
+
+    pub fn slugify(&self, text: &str) -> String {
+        slugify_with_options_public(&self.opts, text)
+    }
+
+    /// See `slugify::slugify_batch`.
+    pub fn slugify_batch<S: AsRef<str> + Sync>(&self, texts: &[S]) -> Vec<String> {
+        slugify_batch(texts, &self.opts)
+    }
+
+    /// See `slugify::try_slugify_with_options`.
+    pub fn try_slugify(&self, text: &str) -> Result<String, SlugifyError> {
+        try_slugify_with_options(&self.opts, text)
+    }
+
+    /// See `slugify::projected_length`.
+    pub fn projected_length(&self, text: &str) -> usize {
+        projected_length(text, &self.opts)
+    }
+}
+
+impl From<SlugifyOptions> for Slugifier {
+    fn from(opts: SlugifyOptions) -> Self {
+        Slugifier::new(opts)
+    }
+}
+
+// Process-wide policy installed by `set_default_options`.
+static GLOBAL_SLUGIFIER: RwLock<Option<Arc<Slugifier>>> = RwLock::new(None);
+
+thread_local! {
+    // Innermost `with_default_options` scope last.
+    static SCOPED_SLUGIFIERS: RefCell<Vec<Arc<Slugifier>>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Install `opts` as the process-wide options used by `slugify_fast`,
+/// returning the previously installed slugifier (`None` when the builder
+/// defaults were in effect).
+///
+/// Scopes opened with `with_default_options` take precedence on their
+/// thread. Intended to be called once at startup by a framework.
+pub fn set_default_options(opts: SlugifyOptions) -> Option<Arc<Slugifier>> {
+    replace_global(Some(Arc::new(Slugifier::new(opts))))
+}
+
+/// Remove the options installed by `set_default_options`, so
+/// `slugify_fast` goes back to the builder defaults.
+pub fn reset_default_options() -> Option<Arc<Slugifier>> {
+    replace_global(None)
+}
+
+fn replace_global(next: Option<Arc<Slugifier>>) -> Option<Arc<Slugifier>> {
+    let mut global = GLOBAL_SLUGIFIER
+        .write()
+        .unwrap_or_else(PoisonError::into_inner);
+    std::mem::replace(&mut *global, next)
+}
+
+/// Run `f` with `opts` as the options used by `slugify_fast` on the
+/// current thread.
+///
+/// Scopes nest: the innermost one wins, and leaving a scope (also by
+/// panic) restores the enclosing one, so no override outlives its
+/// closure. Other threads, including threads spawned inside `f`, are not
+/// affected.
+pub fn with_default_options<R>(opts: SlugifyOptions, f: impl FnOnce() -> R) -> R {
+    struct PopOnDrop;
+    impl Drop for PopOnDrop {
+        fn drop(&mut self) {
+            SCOPED_SLUGIFIERS.with(|stack| stack.borrow_mut().pop());
+        }
+    }
+    SCOPED_SLUGIFIERS.with(|stack| stack.borrow_mut().push(Arc::new(Slugifier::new(opts))));
+    let _pop = PopOnDrop;
+    f()
+}
+
+/// Run `f` with the slugifier `slugify_fast` currently uses on this
+/// thread: the innermost `with_default_options` scope, else the
+/// `set_default_options` policy, else `DEFAULT_SLUGIFIER`.
+pub fn with_current_slugifier<R>(f: impl FnOnce(&Slugifier) -> R) -> R {
+    let scoped = SCOPED_SLUGIFIERS.with(|stack| stack.borrow().last().cloned());
+    if let Some(s) = scoped {
+        return f(&s);
+    }
+    let global = GLOBAL_SLUGIFIER
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .clone();
+    match global {
+        Some(s) => f(&s),
+        None => f(&DEFAULT_SLUGIFIER),
+    }
+}
+
+/// Slugify `text` with the current default options (see
+/// `with_current_slugifier`); `DEFAULT_SLUGIFIER` unless a policy was set.
+///
+/// Without a policy, same output as `slugify_default`, without rebuilding
+/// the options on each call. The shared instance is initialized on first use and is safe to call
+/// from any thread: the options are immutable and the lookup tables in
+/// `special` (Aho-Corasick automatons, regexes) are `Sync` statics that are
+/// only ever read.
+///
+/// ```
+/// assert_eq!(slugify_rs::slugify_fast("Hello World"), "hello-world");
+/// ```
+pub fn slugify_fast(text: &str) -> String {
+    with_current_slugifier(|s| s.slugify(text))
+}
+
+#[cfg(test)]
+#[allow(clippy::expect_used)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::slugify::slugify_default;
+
+    // Tests that read or install the process-wide policy run one at a time.
+    static GLOBAL_LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
+
+    fn separator(sep: &str) -> SlugifyOptions {
+        SlugifyOptions::builder().separator(sep).build().unwrap()
+    }
+
+    #[test]
+    fn test_slugify_fast_matches_default() {
+        let _lock = GLOBAL_LOCK.lock().unwrap_or_else(PoisonError::into_inner);
+        for text in ["Hello World!", "C'est déjà l'été", "I ♥ 🚀", ""] {
+            assert_eq!(slugify_fast(text), slugify_default(text));
+        }
+        assert_eq!(DEFAULT_OPTIONS.separator, "-");
+    }
+
+    #[test]
+    fn test_slugifier_is_shareable_across_threads() {
+        let opts = SlugifyOptions::builder().separator("_").build().unwrap();
+        let s = std::sync::Arc::new(Slugifier::new(opts));
+        let handles: Vec<_> = (0..4)
+            .map(|i| {
+                let s = s.clone();
+                std::thread::spawn(move || s.slugify(&format!("Post {}", i)))
+            })
+            .collect();
+        let out: Vec<String> = handles.into_iter().map(|h| h.join().unwrap()).collect();
+        assert_eq!(out, vec!["post_0", "post_1", "post_2", "post_3"]);
+        assert_eq!(Slugifier::with_defaults().unwrap().slugify("A B"), "a-b");
+    }
+
+    #[test]
+    fn test_slugify_batch_keeps_order() {
+        let s = Slugifier::new(separator("_"));
+        let texts: Vec<String> = (0..1000).map(|i| format!("Item {}", i)).collect();
+        let slugs = s.slugify_batch(&texts);
+        assert_eq!(slugs.len(), 1000);
+        assert_eq!(slugs[0], "item_0");
+        assert_eq!(slugs[999], "item_999");
+        assert_eq!(
+            crate::slugify::slugify_batch(&["A B", "C D"], &DEFAULT_OPTIONS),
+            vec!["a-b", "c-d"]
+        );
+        assert!(s.slugify_batch::<&str>(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_nested_default_option_scopes() {
+        let _lock = GLOBAL_LOCK.lock().unwrap_or_else(PoisonError::into_inner);
+        assert_eq!(slugify_fast("a b"), "a-b");
+        with_default_options(separator("_"), || {
+            assert_eq!(slugify_fast("a b"), "a_b");
+            with_default_options(separator("."), || {
+                assert_eq!(slugify_fast("a b"), "a.b");
+                assert_eq!(with_current_slugifier(|s| s.options().separator.clone()), ".");
+            });
+            assert_eq!(slugify_fast("a b"), "a_b");
+            // other threads never see this thread's scope
+            let other = std::thread::spawn(|| slugify_fast("a b")).join().unwrap();
+            assert_eq!(other, "a-b");
+        });
+        assert_eq!(slugify_fast("a b"), "a-b");
+
+        let result = std::panic::catch_unwind(|| {
+            with_default_options(separator("_"), || panic!("inside scope"))
+        });
+        assert!(result.is_err());
+        assert_eq!(slugify_fast("a b"), "a-b");
+    }
+
+    #[test]
+    fn test_global_default_options() {
+        let _lock = GLOBAL_LOCK.lock().unwrap_or_else(PoisonError::into_inner);
+        assert!(set_default_options(separator("_")).is_none());
+        assert_eq!(slugify_fast("a b"), "a_b");
+        let other = std::thread::spawn(|| slugify_fast("a b")).join().unwrap();
+        assert_eq!(other, "a_b");
+        with_default_options(separator("."), || assert_eq!(slugify_fast("a b"), "a.b"));
+        assert_eq!(slugify_fast("a b"), "a_b");
+        let previous = set_default_options(separator("~")).unwrap();
+        assert_eq!(previous.options().separator, "_");
+        assert_eq!(reset_default_options().unwrap().options().separator, "~");
+        assert_eq!(slugify_fast("a b"), "a-b");
+        assert!(reset_default_options().is_none());
+    }
+}
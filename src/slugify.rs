@@ -1,22 +1,35 @@
 use deunicode::deunicode;
-use html_escape::decode_html_entities;
 use once_cell::sync::Lazy;
 use regex::Regex;
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 use unicode_normalization::UnicodeNormalization;
 use unicode_segmentation::UnicodeSegmentation;
 
 /// Default separator used by slugify
 pub const DEFAULT_SEPARATOR: &str = "-";
 
+// Internal separator used with `SeparatorScope::Inserted`, so separators
+// inserted by the pipeline can be told apart from hyphens kept from the
+// source text. Stripped from the input before processing.
+const INSERTED_SEPARATOR: &str = "\u{1F}";
+
 // `special` is now a crate-level module in `src/special.rs`.
+use crate::charset::{CharAction, CharClassifier, CharSet};
+use crate::pipeline::BuiltinStage;
+pub use crate::entities::EntityPolicy;
 pub use crate::special::apply_pre_translations;
+use crate::special::{Locale, StExpansion, TranslationTable, apply_translations_with};
+use crate::transliterate::{Deunicode, Transliterator};
 
 // Regex patterns (compiled once)
 #[allow(clippy::unwrap_used)]
 pub static CHAR_ENTITY_PATTERN: Lazy<Regex> = Lazy::new(|| {
-    // Match named entities like &amp; &nbsp; etc. Full name-to-codepoint map
-    // is handled by `html_escape::decode_html_entities` when decoding the
-    // entire string. This pattern is kept if you want manual replace logic.
+    // Match named entities like &amp; &nbsp; etc. Names are looked up by
+    // `entities::named_entity`.
     Regex::new(r"&([A-Za-z0-9]+);").unwrap()
 });
 
@@ -33,17 +46,100 @@ pub static DISALLOWED_CHARS_PATTERN: Lazy<Regex> =
 #[allow(clippy::unwrap_used)]
 pub static DISALLOWED_UNICODE_CHARS_PATTERN: Lazy<Regex> =
     Lazy::new(|| Regex::new(r"[\W_]+").unwrap());
+// Leading or trailing database IDs / SKUs such as "12345 – ", " (#987)" or
+// " SKU-0042". Bare numbers need five or more digits so that years survive.
+// Used by `strip_numeric_ids` unless a custom pattern is given.
+#[allow(clippy::unwrap_used)]
+pub static NUMERIC_ID_PATTERN: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"^\s*[(\[]?(?:(?:[A-Za-z]{1,4}-|#)\d{3,}|\d{5,})[)\]]?\s*[-–—:|/.]?\s+|\s+[-–—:|/.]?\s*[(\[]?(?:(?:[A-Za-z]{1,4}-|#)\d{3,}|\d{5,})[)\]]?\s*$",
+    )
+    .unwrap()
+});
 #[allow(clippy::unwrap_used)]
 pub static DUPLICATE_DASH_PATTERN: Lazy<Regex> = Lazy::new(|| Regex::new(r"-{2,}").unwrap());
 
 // Note: Python used lookbehind/lookahead for numbers ("," between digits).
-// Rust's `regex` crate doesn't support lookarounds, so we implement a helper
-// `remove_commas_between_digits` below and use it instead of a regex.
+// Rust's `regex` crate doesn't support lookarounds, so numbers are cleaned
+// up by `NumericCleanup::apply` below instead of a regex.
+
+/// Slugify `text` with the default options (`SlugifyOptions::builder()`
+/// defaults). This is the simplest entrypoint:
+///
+/// ```
+/// assert_eq!(slugify_rs::slugify_default("C'est déjà l'été!"), "c-est-deja-l-ete");
+/// ```
+///
+/// Never panics: should the default options ever fail to build, the slug
+/// is empty. `try_slugify_default` reports that error instead.
+pub fn slugify_default(text: &str) -> String {
+    try_slugify_default(text).unwrap_or_default()
+}
+
+/// `slugify_default`, returning the error when the default options cannot
+/// be built.
+pub fn try_slugify_default(text: &str) -> Result<String, SlugifyError> {
+    let opts = SlugifyOptions::builder().build()?;
+    Ok(slugify_with_options(text, &opts))
+}
+
+/// Method-call access to the pipeline, implemented for `str`, `String` and
+/// `Cow<str>`:
+///
+/// ```
+/// use slugify_rs::Slugify;
+/// assert_eq!("Hello World".slugify(), "hello-world");
+/// ```
+pub trait Slugify {
+    /// Same as `slugify_default(self)`.
+    fn slugify(&self) -> String;
+    /// Same as `slugify_with_options_public(opts, self)`.
+    fn slugify_with(&self, opts: &SlugifyOptions) -> String;
+}
+
+impl Slugify for str {
+    fn slugify(&self) -> String {
+        slugify_default(self)
+    }
+
+    fn slugify_with(&self, opts: &SlugifyOptions) -> String {
+        slugify_with_options(self, opts)
+    }
+}
+
+impl Slugify for String {
+    fn slugify(&self) -> String {
+        self.as_str().slugify()
+    }
+
+    fn slugify_with(&self, opts: &SlugifyOptions) -> String {
+        self.as_str().slugify_with(opts)
+    }
+}
+
+impl Slugify for Cow<'_, str> {
+    fn slugify(&self) -> String {
+        self.as_ref().slugify()
+    }
+
+    fn slugify_with(&self, opts: &SlugifyOptions) -> String {
+        self.as_ref().slugify_with(opts)
+    }
+}
 
 /// Public convenience wrapper that mirrors the original API. It builds a
 /// `SlugifyOptions` from the long argument list and delegates to the
 /// internal `slugify_with_options` implementation. This keeps external
 /// callers working while the internal pipeline uses a single options struct.
+///
+/// An invalid `regex_pattern` is silently ignored. Build the options with
+/// `SlugifyOptions::builder()` (which reports it as
+/// `SlugifyError::InvalidRegex`) and call `try_slugify` instead.
+///
+/// # Panics
+///
+/// If the options cannot be built even without `regex_pattern`.
+#[deprecated(note = "build options with `SlugifyOptions::builder()` and use `try_slugify`")]
 #[allow(clippy::too_many_arguments)]
 pub fn slugify(
     text: &str,
@@ -105,6 +201,10 @@ pub fn slugify(
 // Configuration struct for slugify to improve readability and avoid long argument lists.
 pub struct SlugifyOptions {
     pub entities: bool,
+    /// Handling of named references while `entities` is on.
+    pub entity_policy: EntityPolicy,
+    /// Handling of separators decoded from numeric references.
+    pub decoded_separators: DecodedSeparators,
     pub decimal: bool,
     pub hexadecimal: bool,
     pub max_length: usize,
@@ -113,15 +213,822 @@ pub struct SlugifyOptions {
     pub save_order: bool,
     pub stopwords: Vec<String>,
     pub regex_pattern: Option<Regex>,
+    /// Callback deciding what sanitization does with each character,
+    /// instead of a regex or character set.
+    pub char_classifier: Option<CharClassifier>,
+    /// Characters kept by sanitization when `regex_pattern` is not set.
+    pub allowed_chars: Option<CharSet>,
+    /// Characters kept on top of the default set (`allowed_extra_chars`),
+    /// with the disallowed-characters pattern built from them.
+    pub allowed_extra_chars: Vec<char>,
+    pub disallowed_pattern: Option<Regex>,
     pub lowercase: bool,
+    /// Whether `lowercase` applies before or after transliteration.
+    pub case_fold_stage: CaseFoldStage,
+    /// Characters outside the UTS #39 identifier set, with `allow_unicode`.
+    pub restricted_chars: RestrictedChars,
     pub replacements: Vec<(String, String)>,
+    /// Rules applied after `replacements` at their stage, with their
+    /// compiled matcher.
+    pub replacement_rules: Vec<(ReplacementRule, Regex)>,
     pub allow_unicode: bool,
-    pub transliterate_icons: bool,
+    pub emoji_policy: EmojiPolicy,
+    /// Match stopwords on word stems ("running" matches "run"). Requires
+    /// the `stemming` feature.
+    pub stopword_stemming: bool,
+    /// Language of the stemmer used when `stopword_stemming` is set.
+    pub stemming_locale: Locale,
+    /// Per-word dictionary applied to slug words before stopword removal.
+    /// Keys and values are stored in their normalized (slug) form.
+    pub word_translations: HashMap<String, String>,
+    /// Abbreviation table expanded before sanitization; empty disables the
+    /// stage (the default).
+    pub abbreviations: Vec<(String, String)>,
+    /// Whole tokens replaced verbatim before sanitization ("C++" -> "cpp"),
+    /// user terms first; see `special::apply_protected_terms`.
+    pub protected_terms: Vec<(String, String)>,
+    /// Pattern whose matches are removed from the raw input (leading and
+    /// trailing IDs / SKUs); `None` disables the stage (the default).
+    pub numeric_id_pattern: Option<Regex>,
+    /// Which hyphens `separator` replaces in the final slug.
+    pub separator_scope: SeparatorScope,
+    /// Where separators are inserted between letters and digits.
+    pub digit_boundaries: DigitBoundaries,
+    /// Split camelCase / PascalCase words before sanitization.
+    pub split_camel_case: bool,
+    /// Word boundaries inserted in text written without spaces, with
+    /// `allow_unicode`.
+    pub word_segmentation: WordSegmentation,
+    /// Where truncation and stopword removal find words.
+    pub word_breaks: WordBreaks,
+    /// Keep a trailing numeric word ("report-2024") when truncating, by
+    /// trimming earlier words instead.
+    pub preserve_trailing_number: bool,
+    /// Count the final `separator` rather than the internal one against
+    /// `max_length`.
+    pub strict_max_length: bool,
+    /// What `max_length` counts.
+    pub length_unit: LengthUnit,
+    /// End truncated slugs with a hash of the whole slug
+    /// (`TruncationStrategy::CutWithHash`).
+    pub truncation_hash: bool,
+    /// Work allowed per `try_slugify_with_options` call; `None` (the
+    /// default) is unlimited.
+    pub budget: Option<Budget>,
+    /// Make `try_slugify_with_options` fail on an empty slug, or on one
+    /// that drops more than `max_dropped_fraction` of the input.
+    pub strict: bool,
+    /// Share (0.0 to 1.0) of the input's non-whitespace characters `strict`
+    /// lets the slug drop; 1.0 (the default) only rejects empty slugs.
+    pub max_dropped_fraction: f64,
+    /// Per-character handling of separators found in the source text;
+    /// empty by default.
+    pub source_separators: Vec<(char, SourceSeparator)>,
+    /// Whether CJK quotation marks separate words or are dropped.
+    pub cjk_quotes: CjkQuotes,
+    /// Characters trimmed from the ends of the slug after sanitization.
+    pub edge_trim: TrimSet,
+    /// Apply `replacements` to NFC text and only on grapheme cluster
+    /// boundaries, so a rule for "e" leaves "e\u{301}" alone.
+    pub grapheme_replacements: bool,
+    /// Thousands separators / decimal point / sign handling for numbers.
+    pub numeric_cleanup: NumericCleanup,
+    /// Backend turning non-ASCII text into ASCII when `allow_unicode` is
+    /// off. Defaults to `transliterate::Deunicode`.
+    pub transliterator: Arc<dyn Transliterator>,
+    /// Order of right-to-left words in the slug.
+    pub rtl_word_order: RtlWordOrder,
+    /// Caller-supplied translations ("ß" -> "ss") applied before
+    /// normalization and transliteration; empty by default.
+    pub custom_translations: TranslationTable,
+    /// Reproduce another slugifier's algorithm instead of the pipeline.
+    pub compat: Compat,
+    /// Encode non-ASCII labels (split on `.`) as IDNA `xn--` Punycode so
+    /// the slug can be used as a hostname.
+    pub idna: bool,
+    /// Produce a programming-language identifier instead of a URL slug.
+    pub identifier: Option<IdentifierStyle>,
+    /// Joining and casing of the words in the final step.
+    pub case_style: CaseStyle,
+    /// Locale whose words replace symbols such as `&`, `%` or `€` ("and",
+    /// "percent", "euro"); `None` leaves symbols to sanitization (the
+    /// default).
+    pub symbol_locale: Option<Locale>,
+}
+
+/// Where a separator is inserted between letters and digits inside a word.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DigitBoundaries {
+    /// Words are left intact: "mp3" and "4k" stay as-is (historical behavior).
+    #[default]
+    Keep,
+    /// Letters followed by digits are split: "gpt4" -> "gpt-4", "4k" stays.
+    BeforeDigits,
+    /// Digits followed by letters are split: "4k" -> "4-k", "gpt4" stays.
+    AfterDigits,
+    /// Both transitions are split: "mp3player" -> "mp-3-player".
+    Both,
+}
+
+/// How `allow_unicode` slugs find words in text written without spaces
+/// (Chinese, Japanese, Thai, ...), which otherwise becomes a single word.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum WordSegmentation {
+    /// Only spaces and punctuation separate words (the default).
+    #[default]
+    Off,
+    /// Unicode (UAX #29) word boundaries: every Han ideograph and hiragana
+    /// becomes a word and katakana runs stay together ("日本語のテキスト"
+    /// -> "日-本-語-の-テキスト"). Thai, Lao, Khmer and Myanmar need a
+    /// dictionary and are left as they are.
+    Unicode,
+    /// Dictionary-based boundaries from ICU4X for Chinese, Japanese, Thai,
+    /// Lao, Khmer and Myanmar ("こんにちは世界" -> "こんにちは-世界").
+    /// Needs the `segmenter` feature.
+    Dictionary,
+}
+
+/// Where `word_boundary` truncation and stopword removal find words in a
+/// slug.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum WordBreaks {
+    /// Only at separators (the default).
+    #[default]
+    Separator,
+    /// Also at Unicode (UAX #29) word boundaries inside separated words,
+    /// so a Chinese or Japanese slug written without spaces is cut between
+    /// words ("日本語のテキスト" -> "日本語の", not "日本語のテ") and
+    /// stopwords such as "の" are found inside it. Thai, Lao, Khmer and
+    /// Myanmar words are left whole, as with `WordSegmentation::Unicode`.
+    Unicode,
+}
+
+/// How a character used as a separator in source titles (`/`, `|`, `·`,
+/// `:`, `—`, ...) is handled before sanitization. Characters without an
+/// entry are sanitized like any other punctuation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SourceSeparator {
+    /// Becomes a word separator: "Rock/Pop" -> "rock-pop".
+    Separator,
+    /// Becomes a word of its own: `Word("or")` gives "rock-or-pop".
+    Word(String),
+    /// Is dropped, joining its neighbours: "AC/DC" -> "acdc".
+    Remove,
+}
+
+/// Work allowed for one `try_slugify_with_options` call.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Budget {
+    /// Wall-clock time, checked before each pipeline stage. Not available
+    /// on `wasm32-unknown-unknown`, which has no clock: `build()` fails
+    /// with `SlugifyError::FeatureDisabled` there.
+    Time(Duration),
+    /// Deterministic cost units: each stage costs the byte length of its
+    /// input, stages running a custom regex `REGEX_STEP_COST` per byte.
+    /// The charge is made before the stage runs, so an input too large for
+    /// the budget is rejected without running it.
+    Steps(u64),
+}
+
+/// Cost per input byte of a stage running a custom regex
+/// (`regex_pattern`, `numeric_id_pattern`) under `Budget::Steps`; other
+/// stages cost 1.
+pub const REGEX_STEP_COST: u64 = 4;
+
+// `Instant::now` panics on targets without a clock.
+const HAS_CLOCK: bool = !cfg!(all(target_arch = "wasm32", target_os = "unknown"));
+
+// Tracks the `Budget` of one pipeline run.
+struct Meter {
+    budget: Option<Budget>,
+    start: Option<Instant>,
+    steps: u64,
+}
+
+impl Meter {
+    fn new(budget: Option<Budget>) -> Self {
+        let start = match budget {
+            Some(Budget::Time(_)) if HAS_CLOCK => Some(Instant::now()),
+            _ => None,
+        };
+        Meter {
+            budget,
+            start,
+            steps: 0,
+        }
+    }
+
+    // Charge a stage about to process `input` at `cost` per byte.
+    fn charge(&mut self, input: &str, cost: u64) -> Result<(), SlugifyError> {
+        let exceeded = match (self.budget, self.start) {
+            (Some(Budget::Steps(max)), _) => {
+                self.steps = self.steps.saturating_add(input.len() as u64 * cost);
+                self.steps > max
+            }
+            (Some(Budget::Time(limit)), Some(start)) => start.elapsed() > limit,
+            _ => false,
+        };
+        if exceeded {
+            return Err(SlugifyError::BudgetExceeded);
+        }
+        Ok(())
+    }
+}
+
+/// What happens to CJK quotation marks (`「」`, `『』`, ...) in the input.
+/// Other CJK punctuation (`、。（）！？`) always separates words.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CjkQuotes {
+    /// Quotes separate words: "東京「タワー」" -> "東京-タワー".
+    #[default]
+    Separator,
+    /// Quotes are dropped, joining the quoted text to its neighbours:
+    /// "東京「タワー」" -> "東京タワー".
+    Remove,
+}
+
+/// What happens to emoji in the input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum EmojiPolicy {
+    /// Emoji are dropped.
+    Remove,
+    /// Emoji become their English name: "🚀" -> "rocket" (ASCII mode).
+    #[default]
+    Transliterate,
+    /// Emoji become `:shortcode:` tokens: "🚀" -> ":rocket:" (ASCII mode).
+    /// The colons only survive when a custom `regex_pattern` allows them;
+    /// otherwise they act as separators and the shortcode remains.
+    Shortcode,
+    /// Emoji are kept as-is when `allow_unicode` is set; without it they
+    /// cannot appear in an ASCII slug and are transliterated.
+    Keep,
+}
+
+impl EmojiPolicy {
+    /// Policy matching the legacy `transliterate_icons` flag.
+    pub fn from_transliterate_icons(v: bool) -> Self {
+        if v {
+            EmojiPolicy::Transliterate
+        } else {
+            EmojiPolicy::Remove
+        }
+    }
+}
+
+/// What happens to whitespace, punctuation and control characters decoded
+/// from numeric references (`&#32;`, `&#45;`, `&#x2014;`, ...) when
+/// `decimal` / `hexadecimal` decoding is on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DecodedSeparators {
+    /// They split words like the same characters typed literally:
+    /// "foo&#32;bar" -> "foo-bar".
+    #[default]
+    Split,
+    /// They are dropped, joining their neighbours: "foo&#32;bar" -> "foobar".
+    Strip,
+}
+
+/// When `lowercase` folds the text relative to custom translations and
+/// transliteration.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CaseFoldStage {
+    /// Lowercase first, so translation tables only need lowercase keys
+    /// (`("ä", "ae")` also covers "Ä").
+    BeforeTranslit,
+    /// Lowercase the transliterated text, so uppercase keys such as
+    /// `("Ü", "Ue")` still match.
+    #[default]
+    AfterTranslit,
+}
+
+/// Handling of characters outside the UTS #39 "Allowed" identifier set
+/// (invisible joiners, obsolete and limited-use scripts, ...) in Unicode
+/// slugs. Needs the `unicode_security` feature unless `Allow`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RestrictedChars {
+    /// Keep them.
+    #[default]
+    Allow,
+    /// Remove them; `preview::preview` reports them as dropped.
+    Strip,
+    /// `try_slugify_with_options` fails with
+    /// `SlugifyError::RestrictedCharacter`; the infallible functions strip.
+    Error,
+}
+
+// UTS #39 General Security Profile: may `c` appear in a Unicode slug?
+#[cfg(feature = "unicode_security")]
+fn identifier_allowed(c: char) -> bool {
+    unicode_security::GeneralSecurityProfile::identifier_allowed(c)
+}
+
+#[cfg(not(feature = "unicode_security"))]
+fn identifier_allowed(_: char) -> bool {
+    true
+}
+
+/// Separator placed between the words of the slug.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum Separator {
+    /// `-` (the default).
+    #[default]
+    Hyphen,
+    /// `_`, e.g. for identifiers: "hello_world".
+    Underscore,
+    /// `.`, e.g. for dotted keys: "hello.world".
+    Dot,
+    /// Words are concatenated: "helloworld" (hashtags, identifiers).
+    /// Stopwords and truncation still see the individual words, and
+    /// `max_length` counts only word characters.
+    None,
+    /// Any other string. Not validated; see `separator_str` for a checked
+    /// alternative.
+    Custom(String),
+}
+
+impl Separator {
+    pub fn as_str(&self) -> &str {
+        match self {
+            Separator::Hyphen => "-",
+            Separator::Underscore => "_",
+            Separator::Dot => ".",
+            Separator::None => "",
+            Separator::Custom(s) => s,
+        }
+    }
+}
+
+impl From<&str> for Separator {
+    /// Maps `-`, `_`, `.` and the empty string to their presets.
+    fn from(s: &str) -> Self {
+        match s {
+            "-" => Separator::Hyphen,
+            "_" => Separator::Underscore,
+            "." => Separator::Dot,
+            "" => Separator::None,
+            other => Separator::Custom(other.to_string()),
+        }
+    }
+}
+
+impl From<String> for Separator {
+    fn from(s: String) -> Self {
+        Separator::from(s.as_str())
+    }
+}
+
+impl From<&String> for Separator {
+    fn from(s: &String) -> Self {
+        Separator::from(s.as_str())
+    }
+}
+
+/// Alternative slug algorithms reproducing other implementations exactly.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Compat {
+    /// This crate's pipeline (python-slugify semantics).
+    #[default]
+    None,
+    /// GitHub's heading anchors (github-slugger): lowercase (unless
+    /// `lowercase(false)`), drop punctuation, symbols and control
+    /// characters except `-` and `_`, and turn each space into `-` without
+    /// collapsing or trimming. Other options are ignored.
+    GitHub,
+    /// Django's `django.utils.text.slugify`, byte for byte; honours
+    /// `allow_unicode` and ignores every other option.
+    Django,
+}
+
+/// How the words of the slug are joined and cased in the final step.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CaseStyle {
+    /// Words joined with `separator`, cased per `lowercase` ("hello-world").
+    #[default]
+    Separated,
+    /// "helloWorldFoo"
+    Camel,
+    /// "HelloWorldFoo"
+    Pascal,
+    /// "hello_world_foo"
+    Snake,
+    /// "HELLO_WORLD_FOO"
+    ScreamingSnake,
+}
+
+impl CaseStyle {
+    // Separator the style joins words with, if it imposes one.
+    fn separator(self) -> Option<&'static str> {
+        match self {
+            CaseStyle::Separated => None,
+            CaseStyle::Camel | CaseStyle::Pascal => Some(""),
+            CaseStyle::Snake | CaseStyle::ScreamingSnake => Some("_"),
+        }
+    }
+}
+
+/// When a `ReplacementRule` runs: on the raw input, on the assembled slug
+/// (before truncation), or both like plain `replacements`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ReplacementStage {
+    Pre,
+    Post,
+    #[default]
+    Both,
+}
+
+/// A replacement with matching controls (see
+/// `SlugifyOptionsBuilder::replacement_rules`).
+///
+/// ```
+/// use slugify_rs::slugify::{ReplacementRule, SlugifyOptions, slugify_with_options_public};
+/// let opts = SlugifyOptions::builder()
+///     .replacement_rules([ReplacementRule::new("or", "ou").whole_word(true)])
+///     .build()
+///     .unwrap();
+/// assert_eq!(slugify_with_options_public(&opts, "World or Else"), "world-ou-else");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReplacementRule {
+    /// Literal text to find; empty patterns never match.
+    pub pattern: String,
+    pub replacement: String,
+    /// Only match when not preceded or followed by a letter or digit.
+    pub whole_word: bool,
+    /// Match regardless of case (Unicode simple case folding).
+    pub case_insensitive: bool,
+    pub stage: ReplacementStage,
+}
+
+impl ReplacementRule {
+    /// Case-sensitive substring rule applied at both stages.
+    pub fn new<A: Into<String>, B: Into<String>>(pattern: A, replacement: B) -> Self {
+        ReplacementRule {
+            pattern: pattern.into(),
+            replacement: replacement.into(),
+            whole_word: false,
+            case_insensitive: false,
+            stage: ReplacementStage::Both,
+        }
+    }
+    pub fn whole_word(mut self, v: bool) -> Self {
+        self.whole_word = v;
+        self
+    }
+    pub fn case_insensitive(mut self, v: bool) -> Self {
+        self.case_insensitive = v;
+        self
+    }
+    pub fn stage(mut self, stage: ReplacementStage) -> Self {
+        self.stage = stage;
+        self
+    }
+
+    // An escaped literal is valid, but may exceed the regex size limit.
+    fn compile(&self) -> Result<Regex, SlugifyError> {
+        let pattern = if self.case_insensitive {
+            format!("(?i){}", regex::escape(&self.pattern))
+        } else {
+            regex::escape(&self.pattern)
+        };
+        Regex::new(&pattern).map_err(|_| SlugifyError::InvalidRegex(self.pattern.clone()))
+    }
+}
+
+// Apply the rules of `rules` that run at the given stage, one after the
+// other. Whole-word matches are checked against the neighbouring chars
+// because `\b` does not handle patterns that start or end with punctuation.
+fn apply_replacement_rules(input: &str, rules: &[(ReplacementRule, Regex)], post: bool) -> String {
+    let mut out = input.to_string();
+    for (rule, rx) in rules {
+        let runs = match rule.stage {
+            ReplacementStage::Pre => !post,
+            ReplacementStage::Post => post,
+            ReplacementStage::Both => true,
+        };
+        if !runs || rule.pattern.is_empty() {
+            continue;
+        }
+        let mut replaced = String::with_capacity(out.len());
+        let mut last = 0;
+        for m in rx.find_iter(&out) {
+            if rule.whole_word {
+                let before = out[..m.start()].chars().next_back();
+                let after = out[m.end()..].chars().next();
+                if before.is_some_and(char::is_alphanumeric)
+                    || after.is_some_and(char::is_alphanumeric)
+                {
+                    continue;
+                }
+            }
+            replaced.push_str(&out[last..m.start()]);
+            replaced.push_str(&rule.replacement);
+            last = m.end();
+        }
+        replaced.push_str(&out[last..]);
+        out = replaced;
+    }
+    out
+}
+
+/// Turns slugs into programming-language identifiers (see
+/// `SlugifyOptionsBuilder::identifier`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IdentifierStyle {
+    /// Prepended when the identifier would start with a digit. Defaults to
+    /// `_`.
+    pub digit_prefix: String,
+    /// Words that cannot be used as identifiers; a slug equal to one of
+    /// them (case-sensitive) gets `reserved_suffix` appended.
+    pub reserved: Vec<String>,
+    /// Defaults to `_` ("class" -> "class_").
+    pub reserved_suffix: String,
+}
+
+impl Default for IdentifierStyle {
+    fn default() -> Self {
+        IdentifierStyle {
+            digit_prefix: "_".to_string(),
+            reserved: Vec::new(),
+            reserved_suffix: "_".to_string(),
+        }
+    }
+}
+
+impl IdentifierStyle {
+    fn with_keywords(keywords: &[&str]) -> Self {
+        IdentifierStyle {
+            reserved: keywords.iter().map(|k| k.to_string()).collect(),
+            ..IdentifierStyle::default()
+        }
+    }
+
+    /// Avoids `special::RUST_KEYWORDS`.
+    pub fn rust() -> Self {
+        IdentifierStyle::with_keywords(crate::special::RUST_KEYWORDS)
+    }
+
+    /// Avoids `special::PYTHON_KEYWORDS`.
+    pub fn python() -> Self {
+        IdentifierStyle::with_keywords(crate::special::PYTHON_KEYWORDS)
+    }
+
+    /// Avoids `special::C_KEYWORDS`.
+    pub fn c() -> Self {
+        IdentifierStyle::with_keywords(crate::special::C_KEYWORDS)
+    }
+
+    // `slug` (joined with `_`) as an identifier: characters that cannot
+    // appear in one become `_`, then the digit prefix and the reserved
+    // suffix are applied. Empty slugs stay empty.
+    fn apply(&self, slug: &str, allow_unicode: bool) -> String {
+        let mut ident: String = slug
+            .chars()
+            .map(|c| {
+                let ok = if allow_unicode {
+                    c.is_alphanumeric()
+                } else {
+                    c.is_ascii_alphanumeric()
+                };
+                if ok { c } else { '_' }
+            })
+            .collect();
+        if ident.starts_with(|c: char| c.is_numeric()) {
+            ident.insert_str(0, &self.digit_prefix);
+        }
+        if self.reserved.contains(&ident) {
+            ident.push_str(&self.reserved_suffix);
+        }
+        ident
+    }
+}
+
+/// Order of the words of right-to-left scripts (Arabic, Hebrew, ...) in the
+/// slug.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RtlWordOrder {
+    /// Words keep their reading order: "שלום עולם" -> "שלום-עולם", which
+    /// most renderers display right to left (historical behavior).
+    #[default]
+    Logical,
+    /// Words are stored in the order they appear on screen, left to right:
+    /// runs of RTL words are reversed, and a title starting with an RTL word
+    /// is laid out right to left with its LTR runs kept in reading order.
+    Visual,
+}
+
+/// Characters trimmed from both ends of the slug after sanitization.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum TrimSet {
+    /// Only `-` (historical behavior).
+    #[default]
+    Hyphen,
+    /// `-` plus the given characters, e.g. `_` left at the edges by a
+    /// custom `regex_pattern`.
+    Chars(Vec<char>),
+    /// Everything that is not a letter or a digit.
+    NonAlphanumeric,
+}
+
+impl TrimSet {
+    /// Whether `c` is trimmed from the ends of the slug.
+    pub fn matches(&self, c: char) -> bool {
+        match self {
+            TrimSet::Hyphen => c == '-',
+            TrimSet::Chars(chars) => c == '-' || chars.contains(&c),
+            TrimSet::NonAlphanumeric => !c.is_alphanumeric(),
+        }
+    }
+}
+
+/// How slugs longer than `max_length` are shortened.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TruncationStrategy {
+    /// Cut anywhere, even inside a word (historical behavior).
+    #[default]
+    Cut,
+    /// Keep whole words, skipping a word that does not fit to try the
+    /// next ones (python-slugify's `word_boundary`).
+    WordBoundary,
+    /// Keep whole words and stop at the first one that does not fit.
+    WordBoundaryKeepOrder,
+    /// Cut so that the separator and an 8-character hash of the whole
+    /// slug fit, then append the hash: two long titles sharing their
+    /// first `max_length - 9` characters still get different slugs.
+    /// Slugs within `max_length` are left alone.
+    CutWithHash,
+}
+
+/// What `max_length` counts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LengthUnit {
+    /// Unicode scalar values. Cuts inside a word keep `max_length` whole
+    /// grapheme clusters, so a combining mark or an emoji sequence is
+    /// never split.
+    #[default]
+    Chars,
+    /// Extended grapheme clusters: "e\u{301}" and "👍🏽" count as one.
+    Graphemes,
+    /// UTF-8 bytes, for database columns limited in bytes.
+    Bytes,
+}
+
+impl LengthUnit {
+    /// Length of `s` in this unit.
+    pub fn measure(self, s: &str) -> usize {
+        match self {
+            LengthUnit::Chars => s.chars().count(),
+            LengthUnit::Graphemes => s.graphemes(true).count(),
+            LengthUnit::Bytes => s.len(),
+        }
+    }
+
+    /// Longest prefix of `s` made of whole grapheme clusters whose length
+    /// is at most `max`; `Chars` takes the first `max` clusters.
+    pub fn prefix(self, s: &str, max: usize) -> &str {
+        if self == LengthUnit::Chars {
+            return &s[..first_n_chars(s, max).len()];
+        }
+        let mut total = 0;
+        let mut end = 0;
+        for (i, g) in s.grapheme_indices(true) {
+            total += self.measure(g);
+            if total > max {
+                break;
+            }
+            end = i + g.len();
+        }
+        &s[..end]
+    }
+}
+
+/// Clean-up applied to numbers before sanitization.
+///
+/// The default removes commas between digits ("1,000" -> "1000"), which is
+/// the historical behavior.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NumericCleanup {
+    /// Characters removed between two digits, e.g. `,`, `.`, ` ` or `'` for
+    /// "1,000", "1.000", "1 000" and "1'000".
+    pub thousands_separators: Vec<char>,
+    /// Decimal mark that is never removed, even when it is also listed in
+    /// `thousands_separators`; like other punctuation it then becomes a
+    /// separator ("2,5" -> "2-5").
+    pub decimal_point: Option<char>,
+    /// Keep the `+` of a signed number as the word "plus" ("+33" ->
+    /// "plus-33") instead of dropping it during sanitization.
+    pub keep_leading_plus: bool,
+}
+
+impl Default for NumericCleanup {
+    fn default() -> Self {
+        NumericCleanup {
+            thousands_separators: vec![','],
+            decimal_point: None,
+            keep_leading_plus: false,
+        }
+    }
+}
+
+impl NumericCleanup {
+    /// Strip `,`, `.`, space and `'` thousands separators while keeping the
+    /// given decimal mark: `international('.')` turns "1,234.5" into
+    /// "1234.5", `international(',')` turns "1.234,5" into "1234,5".
+    pub fn international(decimal_point: char) -> Self {
+        NumericCleanup {
+            thousands_separators: vec![',', '.', ' ', '\''],
+            decimal_point: Some(decimal_point),
+            keep_leading_plus: false,
+        }
+    }
+
+    pub fn apply(&self, s: &str) -> String {
+        let mut out = String::with_capacity(s.len());
+        let mut prev: Option<char> = None;
+        let mut chars = s.chars().peekable();
+        while let Some(c) = chars.next() {
+            let next_is_digit = chars.peek().is_some_and(|n| n.is_ascii_digit());
+            let prev_is_digit = prev.is_some_and(|p| p.is_ascii_digit());
+            if prev_is_digit
+                && next_is_digit
+                && self.thousands_separators.contains(&c)
+                && self.decimal_point != Some(c)
+            {
+                // skip the separator
+            } else if self.keep_leading_plus
+                && c == '+'
+                && next_is_digit
+                && !prev.is_some_and(char::is_alphanumeric)
+            {
+                out.push_str(" plus ");
+            } else {
+                out.push(c);
+            }
+            prev = Some(c);
+        }
+        out
+    }
+}
+
+/// Which hyphens are rewritten to a custom `separator`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SeparatorScope {
+    /// Every hyphen in the slug, including hyphens kept from the source
+    /// text (historical behavior).
+    #[default]
+    All,
+    /// Only separators inserted by the pipeline; hyphens inside source words
+    /// that survive sanitization (e.g. with a custom `regex_pattern`) are
+    /// kept as `-`.
+    Inserted,
 }
 
 #[derive(Debug)]
 pub enum SlugifyError {
     InvalidRegex(String),
+    /// An `allowed_chars` set is empty, ends with a lone `\` or has a
+    /// reversed range ("z-a").
+    InvalidCharSet(String),
+    /// `max_length` is too small to hold the separator, the suffix and at
+    /// least one character of the stem.
+    SuffixTooLong { max_length: usize, required: usize },
+    /// An option was requested that needs a cargo feature which is not
+    /// enabled in this build, or that the target does not support.
+    FeatureDisabled(&'static str),
+    /// A namespace passed to `namespace::namespaced_slug` is not itself a
+    /// valid slug under the given options.
+    InvalidNamespace(String),
+    /// A separator given to `separator_char` / `separator_str` is empty or
+    /// contains whitespace, control characters, letters or digits, or
+    /// non-ASCII characters while `allow_unicode` is off.
+    InvalidSeparator(String),
+    /// A string given to `Slug::parse` (or produced by `Slug::new`) is not
+    /// a valid slug.
+    InvalidSlug(String),
+    /// The `custom_translations` table could not be compiled.
+    InvalidTranslations(String),
+    /// A hostname label is empty or longer than 63 bytes once encoded
+    /// (`punycode::to_ascii_label`).
+    InvalidLabel(String),
+    /// A named reference unknown to the entity backend, with
+    /// `EntityPolicy::Error` (e.g. `"&bogus;"`).
+    UnknownEntity(String),
+    /// A character outside the UTS #39 identifier set, with
+    /// `RestrictedChars::Error`.
+    RestrictedCharacter(char),
+    /// The call ran out of its `budget`.
+    BudgetExceeded,
+    /// `strict` is on and the slug is empty (e.g. a title of only emoji
+    /// with `EmojiPolicy::Remove`).
+    EmptyResult,
+    /// `strict` is on and the slug drops more than `max_dropped_fraction`
+    /// of the input's non-whitespace characters.
+    UnmappableInput {
+        dropped: usize,
+        total: usize,
+    },
+    /// A `PipelineBuilder` was asked to remove a stage, or insert next to
+    /// one, that it does not have.
+    UnknownStage(String),
 }
 
 impl SlugifyOptions {
@@ -152,6 +1059,8 @@ impl SlugifyOptions {
 
         Ok(SlugifyOptions {
             entities,
+            entity_policy: EntityPolicy::default(),
+            decoded_separators: DecodedSeparators::default(),
             decimal,
             hexadecimal,
             max_length,
@@ -160,13 +1069,51 @@ impl SlugifyOptions {
             save_order,
             stopwords: stopwords.iter().map(|s| s.to_string()).collect(),
             regex_pattern: regex_compiled,
+            char_classifier: None,
+            allowed_chars: None,
+            allowed_extra_chars: Vec::new(),
+            disallowed_pattern: None,
             lowercase,
+            case_fold_stage: CaseFoldStage::default(),
+            restricted_chars: RestrictedChars::default(),
             replacements: replacements
                 .iter()
                 .map(|(a, b)| (a.to_string(), b.to_string()))
                 .collect(),
+            replacement_rules: Vec::new(),
             allow_unicode,
-            transliterate_icons,
+            emoji_policy: EmojiPolicy::from_transliterate_icons(transliterate_icons),
+            stopword_stemming: false,
+            stemming_locale: Locale::default(),
+            word_translations: HashMap::new(),
+            abbreviations: Vec::new(),
+            protected_terms: Vec::new(),
+            numeric_id_pattern: None,
+            separator_scope: SeparatorScope::default(),
+            digit_boundaries: DigitBoundaries::default(),
+            split_camel_case: false,
+            word_segmentation: WordSegmentation::default(),
+            word_breaks: WordBreaks::default(),
+            preserve_trailing_number: false,
+            strict_max_length: false,
+            length_unit: LengthUnit::default(),
+            truncation_hash: false,
+            budget: None,
+            strict: false,
+            max_dropped_fraction: 1.0,
+            source_separators: Vec::new(),
+            cjk_quotes: CjkQuotes::default(),
+            edge_trim: TrimSet::default(),
+            grapheme_replacements: false,
+            symbol_locale: None,
+            numeric_cleanup: NumericCleanup::default(),
+            transliterator: Arc::new(Deunicode),
+            rtl_word_order: RtlWordOrder::default(),
+            custom_translations: TranslationTable::default(),
+            compat: Compat::default(),
+            idna: false,
+            identifier: None,
+            case_style: CaseStyle::default(),
         })
     }
 
@@ -174,42 +1121,176 @@ impl SlugifyOptions {
     pub fn builder() -> SlugifyOptionsBuilder {
         SlugifyOptionsBuilder::default()
     }
+
+    /// Options reproducing GitHub's markdown heading ids (`Compat::GitHub`):
+    /// "Foo & Bar_baz!" -> "foo--bar_baz".
+    #[allow(clippy::expect_used)]
+    pub fn github_anchor() -> SlugifyOptions {
+        SlugifyOptions::builder()
+            .compat(Compat::GitHub)
+            .build()
+            .expect("github_anchor options must build")
+    }
+
+    /// Options producing RFC 1123 DNS labels (Kubernetes resource names):
+    /// at most `DNS_LABEL_MAX_LENGTH` lowercase ASCII alphanumerics and `-`,
+    /// starting and ending with an alphanumeric. Truncation keeps whole
+    /// words where possible. Inputs without any letter or digit give an
+    /// empty string, which is not a valid label; check with `is_dns_label`.
+    #[allow(clippy::expect_used)]
+    pub fn dns_label() -> SlugifyOptions {
+        SlugifyOptions::builder()
+            .max_length(DNS_LABEL_MAX_LENGTH)
+            .word_boundary(true)
+            .separator(Separator::Hyphen)
+            .lowercase(true)
+            .allow_unicode(false)
+            .build()
+            .expect("dns_label options must build")
+    }
+}
+
+/// Maximum length of an RFC 1123 DNS label.
+pub const DNS_LABEL_MAX_LENGTH: usize = 63;
+
+/// Whether `s` is a valid RFC 1123 DNS label: 1 to 63 characters of
+/// `[a-z0-9-]`, starting and ending with an alphanumeric.
+pub fn is_dns_label(s: &str) -> bool {
+    let alnum = |c: u8| c.is_ascii_lowercase() || c.is_ascii_digit();
+    let bytes = s.as_bytes();
+    match (bytes.first(), bytes.last()) {
+        (Some(&first), Some(&last)) => {
+            bytes.len() <= DNS_LABEL_MAX_LENGTH
+                && alnum(first)
+                && alnum(last)
+                && bytes.iter().all(|&c| alnum(c) || c == b'-')
+        }
+        _ => false,
+    }
 }
 
 /// Builder for `SlugifyOptions` to avoid long argument lists and improve ergonomics.
 #[derive(Debug, Clone)]
 pub struct SlugifyOptionsBuilder {
     entities: bool,
+    entity_policy: EntityPolicy,
+    decoded_separators: DecodedSeparators,
     decimal: bool,
     hexadecimal: bool,
     max_length: usize,
     word_boundary: bool,
     separator: String,
+    validate_separator: bool,
     save_order: bool,
     stopwords: Vec<String>,
     regex_pattern: Option<String>,
+    char_classifier: Option<CharClassifier>,
+    allowed_chars: Option<String>,
+    allowed_extra_chars: Vec<char>,
     lowercase: bool,
+    case_fold_stage: CaseFoldStage,
+    restricted_chars: RestrictedChars,
     replacements: Vec<(String, String)>,
+    replacement_rules: Vec<ReplacementRule>,
     allow_unicode: bool,
-    transliterate_icons: bool,
+    emoji_policy: EmojiPolicy,
+    stopword_stemming: bool,
+    stemming_locale: Locale,
+    word_translations: HashMap<String, String>,
+    expand_abbreviations: bool,
+    st_expansion: StExpansion,
+    custom_abbreviations: Vec<(String, String)>,
+    protected_terms: Vec<(String, String)>,
+    builtin_protected_terms: bool,
+    strip_numeric_ids: bool,
+    numeric_id_pattern: Option<String>,
+    separator_scope: SeparatorScope,
+    digit_boundaries: DigitBoundaries,
+    split_camel_case: bool,
+    word_segmentation: WordSegmentation,
+    word_breaks: WordBreaks,
+    preserve_trailing_number: bool,
+    strict_max_length: bool,
+    length_unit: LengthUnit,
+    truncation_hash: bool,
+    budget: Option<Budget>,
+    strict: bool,
+    max_dropped_fraction: f64,
+    source_separators: Vec<(char, SourceSeparator)>,
+    cjk_quotes: CjkQuotes,
+    edge_trim: TrimSet,
+    grapheme_replacements: bool,
+    symbol_locale: Option<Locale>,
+    numeric_cleanup: NumericCleanup,
+    transliterator: Arc<dyn Transliterator>,
+    rtl_word_order: RtlWordOrder,
+    custom_translations: Vec<(String, String)>,
+    compat: Compat,
+    idna: bool,
+    identifier: Option<IdentifierStyle>,
+    case_style: CaseStyle,
 }
 
 impl Default for SlugifyOptionsBuilder {
     fn default() -> Self {
         SlugifyOptionsBuilder {
             entities: true,
+            entity_policy: EntityPolicy::default(),
+            decoded_separators: DecodedSeparators::default(),
             decimal: true,
             hexadecimal: true,
             max_length: 0,
             word_boundary: false,
             separator: DEFAULT_SEPARATOR.to_string(),
+            validate_separator: false,
             save_order: false,
             stopwords: Vec::new(),
             regex_pattern: None,
+            char_classifier: None,
+            allowed_chars: None,
+            allowed_extra_chars: Vec::new(),
             lowercase: true,
+            case_fold_stage: CaseFoldStage::default(),
+            restricted_chars: RestrictedChars::default(),
             replacements: Vec::new(),
+            replacement_rules: Vec::new(),
             allow_unicode: false,
-            transliterate_icons: true,
+            emoji_policy: EmojiPolicy::default(),
+            stopword_stemming: false,
+            stemming_locale: Locale::default(),
+            word_translations: HashMap::new(),
+            expand_abbreviations: false,
+            st_expansion: StExpansion::default(),
+            custom_abbreviations: Vec::new(),
+            protected_terms: Vec::new(),
+            builtin_protected_terms: false,
+            strip_numeric_ids: false,
+            numeric_id_pattern: None,
+            separator_scope: SeparatorScope::default(),
+            digit_boundaries: DigitBoundaries::default(),
+            split_camel_case: false,
+            word_segmentation: WordSegmentation::default(),
+            word_breaks: WordBreaks::default(),
+            preserve_trailing_number: false,
+            strict_max_length: false,
+            length_unit: LengthUnit::default(),
+            truncation_hash: false,
+            budget: None,
+            strict: false,
+            max_dropped_fraction: 1.0,
+            source_separators: Vec::new(),
+            cjk_quotes: CjkQuotes::default(),
+            edge_trim: TrimSet::default(),
+            grapheme_replacements: false,
+            symbol_locale: None,
+            numeric_cleanup: NumericCleanup::default(),
+            transliterator: Arc::new(Deunicode),
+            rtl_word_order: RtlWordOrder::default(),
+            custom_translations: Vec::new(),
+            compat: Compat::default(),
+            idna: false,
+            identifier: None,
+            case_style: CaseStyle::default(),
         }
     }
 }
@@ -227,6 +1308,12 @@ impl SlugifyOptionsBuilder {
         self.hexadecimal = v;
         self
     }
+    /// Whether whitespace and punctuation decoded from numeric references
+    /// split words (default) or are stripped.
+    pub fn decoded_separators(mut self, v: DecodedSeparators) -> Self {
+        self.decoded_separators = v;
+        self
+    }
     pub fn max_length(mut self, v: usize) -> Self {
         self.max_length = v;
         self
@@ -235,14 +1322,50 @@ impl SlugifyOptionsBuilder {
         self.word_boundary = v;
         self
     }
-    pub fn separator<S: Into<String>>(mut self, s: S) -> Self {
+    /// Separator between words, as a `Separator` preset or any string.
+    pub fn separator<S: Into<Separator>>(mut self, s: S) -> Self {
+        self.separator = s.into().as_str().to_string();
+        self.validate_separator = false;
+        self
+    }
+    /// Single-character separator, validated by `build()` (see
+    /// `separator_str`).
+    pub fn separator_char(self, c: char) -> Self {
+        self.separator_str(c.to_string())
+    }
+    /// Separator validated by `build()`: it must be non-empty, printable,
+    /// free of whitespace, letters and digits, and ASCII unless
+    /// `allow_unicode` is set. Invalid values make
+    /// `build()` return `SlugifyError::InvalidSeparator`.
+    pub fn separator_str<S: Into<String>>(mut self, s: S) -> Self {
         self.separator = s.into();
+        self.validate_separator = true;
         self
     }
     pub fn save_order(mut self, v: bool) -> Self {
         self.save_order = v;
         self
     }
+    /// How slugs longer than `max_length` are shortened. Sets
+    /// `word_boundary` and `save_order` (see `TruncationStrategy`); the
+    /// default is `TruncationStrategy::Cut`.
+    pub fn truncation_strategy(mut self, strategy: TruncationStrategy) -> Self {
+        self.truncation_hash = strategy == TruncationStrategy::CutWithHash;
+        match strategy {
+            TruncationStrategy::Cut | TruncationStrategy::CutWithHash => {
+                self.word_boundary = false;
+            }
+            TruncationStrategy::WordBoundary => {
+                self.word_boundary = true;
+                self.save_order = false;
+            }
+            TruncationStrategy::WordBoundaryKeepOrder => {
+                self.word_boundary = true;
+                self.save_order = true;
+            }
+        }
+        self
+    }
     pub fn stopwords<I, S>(mut self, words: I) -> Self
     where
         I: IntoIterator<Item = S>,
@@ -255,10 +1378,56 @@ impl SlugifyOptionsBuilder {
         self.regex_pattern = pat.map(|s| s.into());
         self
     }
+    /// Decide character by character what sanitization does, without
+    /// regex syntax: `f` returns a `CharAction` (`Keep`, `Separator`,
+    /// `Drop` or `Replace`) for every character left after
+    /// transliteration. Ignored when `regex_pattern` is set; takes
+    /// precedence over `allowed_chars` and `allowed_extra_chars`.
+    pub fn char_classifier<F>(mut self, f: F) -> Self
+    where
+        F: Fn(char) -> CharAction + Send + Sync + 'static,
+    {
+        self.char_classifier = Some(CharClassifier::new(f));
+        self
+    }
+    /// Keep only the characters of `set` (see `charset::CharSet`) and
+    /// replace the others with the separator, without a regex:
+    /// `allowed_chars(Some("a-z0-9._"))` behaves like
+    /// `regex_pattern(Some("[^a-z0-9._]+"))`, and `Some("^/?#")` only
+    /// replaces `/`, `?` and `#`. Ignored when `regex_pattern` is set.
+    pub fn allowed_chars<S: Into<String>>(mut self, set: Option<S>) -> Self {
+        self.allowed_chars = set.map(|s| s.into());
+        self
+    }
+    /// Keep the characters of `chars` in addition to letters, digits and
+    /// `-`: `allowed_extra_chars("._~+")` turns "v1.2 C++" into
+    /// "v1.2-c++" instead of "v1-2-c". Ignored when `regex_pattern` or
+    /// `allowed_chars` is set. Built-in protected terms spelled only with
+    /// kept characters are not rewritten ("C++" stays "c++").
+    pub fn allowed_extra_chars(mut self, chars: &str) -> Self {
+        self.allowed_extra_chars = chars.chars().collect();
+        self
+    }
     pub fn lowercase(mut self, v: bool) -> Self {
         self.lowercase = v;
         self
     }
+    /// Lowercase before or after custom translations and transliteration
+    /// (default `CaseFoldStage::AfterTranslit`). Only used with
+    /// `lowercase(true)`.
+    pub fn case_fold_stage(mut self, stage: CaseFoldStage) -> Self {
+        self.case_fold_stage = stage;
+        self
+    }
+    /// With `allow_unicode`, strip or reject characters outside the UTS #39
+    /// "Allowed" identifier set, such as "a\u{200D}b" (zero-width joiner),
+    /// so public slugs cannot hide invisible or restricted characters.
+    /// Anything but `RestrictedChars::Allow` (the default) needs the
+    /// `unicode_security` feature.
+    pub fn restricted_chars(mut self, policy: RestrictedChars) -> Self {
+        self.restricted_chars = policy;
+        self
+    }
     pub fn replacements<I, A, B>(mut self, reps: I) -> Self
     where
         I: IntoIterator<Item = (A, B)>,
@@ -271,447 +1440,3177 @@ impl SlugifyOptionsBuilder {
             .collect();
         self
     }
+    /// Replacements with whole-word, case-insensitive and stage controls,
+    /// applied in order after the plain `replacements` of each stage.
+    pub fn replacement_rules<I: IntoIterator<Item = ReplacementRule>>(mut self, rules: I) -> Self {
+        self.replacement_rules = rules.into_iter().collect();
+        self
+    }
     pub fn allow_unicode(mut self, v: bool) -> Self {
         self.allow_unicode = v;
         self
     }
-    pub fn transliterate_icons(mut self, v: bool) -> Self {
-        self.transliterate_icons = v;
+    /// Legacy flag: `true` is `EmojiPolicy::Transliterate`, `false` is
+    /// `EmojiPolicy::Remove`.
+    #[deprecated(note = "use `emoji_policy` instead")]
+    pub fn transliterate_icons(self, v: bool) -> Self {
+        self.emoji_policy(EmojiPolicy::from_transliterate_icons(v))
+    }
+    /// How emoji are handled. Defaults to `EmojiPolicy::Transliterate`.
+    pub fn emoji_policy(mut self, policy: EmojiPolicy) -> Self {
+        self.emoji_policy = policy;
         self
     }
-
-    /// Build the `SlugifyOptions`, validating the regex if present.
-    pub fn build(self) -> Result<SlugifyOptions, SlugifyError> {
-        let regex_compiled = if let Some(pat) = self.regex_pattern.as_deref() {
-            match Regex::new(pat) {
-                Ok(r) => Some(r),
-                Err(_) => return Err(SlugifyError::InvalidRegex(pat.to_string())),
-            }
-        } else {
-            None
-        };
-
-        Ok(SlugifyOptions {
-            entities: self.entities,
-            decimal: self.decimal,
-            hexadecimal: self.hexadecimal,
-            max_length: self.max_length,
-            word_boundary: self.word_boundary,
-            separator: self.separator,
-            save_order: self.save_order,
-            stopwords: self.stopwords,
-            regex_pattern: regex_compiled,
-            lowercase: self.lowercase,
-            replacements: self.replacements,
-            allow_unicode: self.allow_unicode,
-            transliterate_icons: self.transliterate_icons,
-        })
+    /// How named references are handled while `entities` is on. Defaults
+    /// to `EntityPolicy::Decode`.
+    pub fn entity_policy(mut self, policy: EntityPolicy) -> Self {
+        self.entity_policy = policy;
+        self
     }
-}
-
-// New internal API that takes the options struct. Keeps behavior identical.
-fn slugify_with_options(input: &str, opts: &SlugifyOptions) -> String {
-    // 1. Apply user replacements first (match python-slugify behavior).
-    // Note: pre-translations are available via `crate::special::apply_pre_translations`
-    // but are NOT applied by default to preserve original Python semantics.
-    let after_replacements = apply_replacements(input, &opts.replacements);
-
-    // 2. Replace quotes with separator early to avoid merging words
-    let after_quotes = QUOTE_PATTERN
-        .replace_all(&after_replacements, DEFAULT_SEPARATOR)
-        .to_string();
+    /// Compare stopwords by stem instead of exact text. Building fails with
+    /// `SlugifyError::FeatureDisabled` unless the `stemming` feature is on.
+    pub fn stopword_stemming(mut self, v: bool) -> Self {
+        self.stopword_stemming = v;
+        self
+    }
+    pub fn stemming_locale(mut self, locale: Locale) -> Self {
+        self.stemming_locale = locale;
+        self
+    }
+    /// Translate whole slug words through `dict` (e.g. French -> English).
+    ///
+    /// Keys and values are normalized like the slug itself when the options
+    /// are built, so `("école", "School")` matches the slug word `ecole` and
+    /// yields `school`. Keys are single words; values may expand to several
+    /// words, or to nothing to drop the word.
+    pub fn word_translations<I, A, B>(mut self, dict: I) -> Self
+    where
+        I: IntoIterator<Item = (A, B)>,
+        A: AsRef<str>,
+        B: AsRef<str>,
+    {
+        self.word_translations = dict
+            .into_iter()
+            .map(|(a, b)| (a.as_ref().to_string(), b.as_ref().to_string()))
+            .collect();
+        self
+    }
+    /// Expand known abbreviations ("Dr." -> "doctor", see
+    /// `special::ABBREVIATIONS`) before sanitization. Off by default.
+    pub fn expand_abbreviations(mut self, v: bool) -> Self {
+        self.expand_abbreviations = v;
+        self
+    }
+    /// Choose whether "St." expands to "saint" (default) or "street".
+    pub fn st_expansion(mut self, st: StExpansion) -> Self {
+        self.st_expansion = st;
+        self
+    }
+    /// Extra abbreviations, taking precedence over the built-in ones. Only
+    /// used when `expand_abbreviations(true)` is set.
+    pub fn abbreviations<I, A, B>(mut self, entries: I) -> Self
+    where
+        I: IntoIterator<Item = (A, B)>,
+        A: AsRef<str>,
+        B: AsRef<str>,
+    {
+        self.custom_abbreviations = entries
+            .into_iter()
+            .map(|(a, b)| (a.as_ref().to_string(), b.as_ref().to_string()))
+            .collect();
+        self
+    }
+    /// Tokens replaced as a whole before sanitization, e.g.
+    /// `[("K8s", "kubernetes")]`. They take precedence over the built-in
+    /// `special::TECH_TERMS` ("C++" -> "cpp", "C#" -> "c-sharp", ...) that
+    /// `builtin_protected_terms(true)` adds.
+    pub fn protected_terms<I, A, B>(mut self, terms: I) -> Self
+    where
+        I: IntoIterator<Item = (A, B)>,
+        A: AsRef<str>,
+        B: AsRef<str>,
+    {
+        self.protected_terms = terms
+            .into_iter()
+            .map(|(a, b)| (a.as_ref().to_string(), b.as_ref().to_string()))
+            .collect();
+        self
+    }
+    /// Include the built-in `special::TECH_TERMS` (default false). Has no
+    /// effect without the `tech_terms` feature, which only makes the
+    /// dictionary available so enabling it never changes existing slugs.
+    pub fn builtin_protected_terms(mut self, v: bool) -> Self {
+        self.builtin_protected_terms = v;
+        self
+    }
+    /// Strip leading/trailing numeric IDs or SKUs from the input
+    /// ("12345 – Blue Widget" -> "blue-widget"). Off by default.
+    pub fn strip_numeric_ids(mut self, v: bool) -> Self {
+        self.strip_numeric_ids = v;
+        self
+    }
+    /// Replace `NUMERIC_ID_PATTERN` with a custom regex; every match is
+    /// removed when `strip_numeric_ids(true)` is set.
+    pub fn numeric_id_pattern<S: Into<String>>(mut self, pat: Option<S>) -> Self {
+        self.numeric_id_pattern = pat.map(|s| s.into());
+        self
+    }
+    /// Choose whether a custom separator replaces every hyphen or only the
+    /// separators inserted by the pipeline. See `SeparatorScope`.
+    pub fn separator_scope(mut self, scope: SeparatorScope) -> Self {
+        self.separator_scope = scope;
+        self
+    }
+    /// Insert separators at letter/digit transitions ("GPT4" -> "gpt-4").
+    /// Defaults to `DigitBoundaries::Keep`.
+    pub fn digit_boundaries(mut self, placement: DigitBoundaries) -> Self {
+        self.digit_boundaries = placement;
+        self
+    }
+    /// With `allow_unicode`, insert separators at word boundaries inside text
+    /// written without spaces so Chinese, Japanese or Thai titles are not
+    /// one giant word. `WordSegmentation::Dictionary` needs the `segmenter`
+    /// feature. Defaults to `WordSegmentation::Off`.
+    pub fn word_segmentation(mut self, segmentation: WordSegmentation) -> Self {
+        self.word_segmentation = segmentation;
+        self
+    }
+    /// Let `word_boundary` truncation and stopword removal also split
+    /// words at Unicode word boundaries (`WordBreaks::Unicode`) without
+    /// inserting separators there, unlike `word_segmentation`. Truncation
+    /// then keeps words in order, ignoring `save_order`. Defaults to
+    /// `WordBreaks::Separator`.
+    pub fn word_breaks(mut self, breaks: WordBreaks) -> Self {
+        self.word_breaks = breaks;
+        self
+    }
+    /// Insert word boundaries inside camelCase and PascalCase words before
+    /// sanitization: "iPhone13ProMax" -> "i-phone-13-pro-max",
+    /// "parseHTMLDocument" -> "parse-html-document". A run of capitals is
+    /// kept together as an acronym ("HTMLParser" -> "html-parser"), and
+    /// letters and digits are split apart. HTML entities are left intact.
+    /// Off by default.
+    pub fn split_camel_case(mut self, enabled: bool) -> Self {
+        self.split_camel_case = enabled;
+        self
+    }
+    /// Keep the final word when it is a number ("annual-report-2024") and
+    /// truncation would drop it; earlier words are shortened instead.
+    pub fn preserve_trailing_number(mut self, v: bool) -> Self {
+        self.preserve_trailing_number = v;
+        self
+    }
+    /// Apply `max_length` to the slug as returned, after a multi-character
+    /// `separator` has replaced the internal "-". Without it "a b" with
+    /// `separator("__")` and `max_length(3)` gives "a__b" (4 chars); with it,
+    /// "a". Off by default for compatibility.
+    pub fn strict_max_length(mut self, enabled: bool) -> Self {
+        self.strict_max_length = enabled;
+        self
+    }
+    /// Count `max_length` in chars (the default), grapheme clusters or
+    /// UTF-8 bytes. `LengthUnit::Bytes` fits slugs into byte-limited
+    /// columns such as MySQL `VARBINARY(n)`; cuts never split a grapheme
+    /// cluster, so a `Bytes` or `Graphemes` slug may come out shorter than
+    /// the limit.
+    pub fn length_unit(mut self, unit: LengthUnit) -> Self {
+        self.length_unit = unit;
+        self
+    }
+    /// Abort `try_slugify_with_options` with `SlugifyError::BudgetExceeded`
+    /// once a call exceeds `budget`, so pathological input (huge titles,
+    /// costly custom regexes) cannot stall a request. The check runs
+    /// between pipeline stages; the infallible functions ignore it.
+    pub fn budget(mut self, budget: Budget) -> Self {
+        self.budget = Some(budget);
+        self
+    }
+    /// Make `try_slugify_with_options` fail with `SlugifyError::EmptyResult`
+    /// when the slug is empty, and with `SlugifyError::UnmappableInput`
+    /// when it drops more than `max_dropped_fraction` of the input (see
+    /// `loss::slugify_with_report`). The infallible functions ignore it.
+    pub fn strict(mut self, v: bool) -> Self {
+        self.strict = v;
+        self
+    }
+    /// Share of the input's non-whitespace characters a `strict` slug may
+    /// drop, clamped to 0.0..=1.0: 0.5 rejects "日本 🦄🦄🦄" (unicorns
+    /// dropped with `EmojiPolicy::Remove`). Defaults to 1.0.
+    pub fn max_dropped_fraction(mut self, fraction: f64) -> Self {
+        self.max_dropped_fraction = fraction.clamp(0.0, 1.0);
+        self
+    }
+    /// Handle `c` in the source text as `action` instead of sanitizing it
+    /// like other punctuation (e.g. `'|'` -> `SourceSeparator::Word("or")`).
+    /// Setting the same character again replaces the previous action.
+    pub fn source_separator(mut self, c: char, action: SourceSeparator) -> Self {
+        self.source_separators.retain(|(k, _)| *k != c);
+        self.source_separators.push((c, action));
+        self
+    }
+    /// Drop CJK quotation marks (`CjkQuotes::Remove`) instead of treating
+    /// them as separators like the rest of `special::CJK_PUNCTUATION`.
+    /// Defaults to `CjkQuotes::Separator`; `source_separator` entries win.
+    pub fn cjk_quotes(mut self, quotes: CjkQuotes) -> Self {
+        self.cjk_quotes = quotes;
+        self
+    }
+    /// Characters trimmed from both ends of the slug once disallowed
+    /// characters have been replaced. Defaults to `TrimSet::Hyphen`.
+    pub fn edge_trim(mut self, set: TrimSet) -> Self {
+        self.edge_trim = set;
+        self
+    }
+    /// Match `replacements` against NFC-normalized text and only where the
+    /// match starts and ends on a grapheme cluster boundary, so rules
+    /// behave the same on decomposed (NFD) input. Off by default.
+    pub fn grapheme_replacements(mut self, v: bool) -> Self {
+        self.grapheme_replacements = v;
+        self
+    }
+    /// Expand `&`, `@`, `%`, `#` (before a digit), `€`, `$`, `£` and `+`
+    /// to words of `locale` ("Q&A" -> "q-and-a" in English, "q-et-a" in
+    /// French). See `special::symbol_words`. Off (`None`) by default.
+    pub fn symbol_locale(mut self, locale: Option<Locale>) -> Self {
+        self.symbol_locale = locale;
+        self
+    }
+    /// How thousands separators, decimal points and leading `+` signs in
+    /// numbers are handled. Defaults to removing commas between digits.
+    pub fn numeric_cleanup(mut self, cleanup: NumericCleanup) -> Self {
+        self.numeric_cleanup = cleanup;
+        self
+    }
+    /// Transliteration backend used when `allow_unicode` is off:
+    /// `Deunicode` (default), `AnyAscii` (`any_ascii` feature), `Icu`
+    /// (`icu` feature) or a custom `Transliterator`.
+    pub fn transliterator<T: Transliterator + 'static>(mut self, t: T) -> Self {
+        self.transliterator = Arc::new(t);
+        self
+    }
+    /// Keep right-to-left words in logical (reading) order, or lay them out
+    /// in visual order. Only matters with `allow_unicode`. Defaults to
+    /// `RtlWordOrder::Logical`.
+    pub fn rtl_word_order(mut self, order: RtlWordOrder) -> Self {
+        self.rtl_word_order = order;
+        self
+    }
+    /// Translate characters or sequences before normalization and
+    /// transliteration, e.g. `[("ß", "ss"), ("ø", "oe")]`. Longer keys win;
+    /// the table is compiled once by `build()`.
+    pub fn custom_translations<I, A, B>(mut self, table: I) -> Self
+    where
+        I: IntoIterator<Item = (A, B)>,
+        A: AsRef<str>,
+        B: AsRef<str>,
+    {
+        self.custom_translations = table
+            .into_iter()
+            .map(|(a, b)| (a.as_ref().to_string(), b.as_ref().to_string()))
+            .collect();
+        self
+    }
+    /// Replace the pipeline with another slugifier's algorithm. See
+    /// `Compat` for which options each mode honours.
+    pub fn compat(mut self, compat: Compat) -> Self {
+        self.compat = compat;
+        self
+    }
+    /// With `allow_unicode`, encode each `.`-separated label of the slug as
+    /// IDNA Punycode ("münchen" -> "xn--mnchen-3ya"). Labels whose encoding
+    /// exceeds 63 bytes are shortened until it fits. Off by default.
+    pub fn idna(mut self, enabled: bool) -> Self {
+        self.idna = enabled;
+        self
+    }
+    /// Produce identifiers (`IdentifierStyle::python()`, `rust()`, `c()` or
+    /// a custom style): words are joined with `_` whatever `separator` says,
+    /// remaining hyphens and other non-word characters become `_`, a
+    /// leading digit gets `digit_prefix` and reserved words get
+    /// `reserved_suffix`. "2nd Class" -> "_2nd_class".
+    pub fn identifier(mut self, style: IdentifierStyle) -> Self {
+        self.identifier = Some(style);
+        self
+    }
+    /// Emit `camelCase`, `PascalCase`, `snake_case` or `SCREAMING_SNAKE`
+    /// instead of separator-joined words. Every style but `Separated`
+    /// replaces `separator` and `lowercase`; `max_length` then counts the
+    /// joined result.
+    pub fn case_style(mut self, style: CaseStyle) -> Self {
+        self.case_style = style;
+        self
+    }
+
+    /// Build the `SlugifyOptions`, validating the regex if present.
+    pub fn build(self) -> Result<SlugifyOptions, SlugifyError> {
+        if self.stopword_stemming && !cfg!(feature = "stemming") {
+            return Err(SlugifyError::FeatureDisabled("stemming"));
+        }
+        if self.restricted_chars != RestrictedChars::Allow && !cfg!(feature = "unicode_security") {
+            return Err(SlugifyError::FeatureDisabled("unicode_security"));
+        }
+        if self.word_segmentation == WordSegmentation::Dictionary && !cfg!(feature = "segmenter") {
+            return Err(SlugifyError::FeatureDisabled("segmenter"));
+        }
+        if matches!(self.budget, Some(Budget::Time(_))) && !HAS_CLOCK {
+            return Err(SlugifyError::FeatureDisabled("Budget::Time"));
+        }
+        if self.validate_separator && !is_valid_separator(&self.separator, self.allow_unicode) {
+            return Err(SlugifyError::InvalidSeparator(self.separator));
+        }
+        let regex_compiled = if let Some(pat) = self.regex_pattern.as_deref() {
+            match Regex::new(pat) {
+                Ok(r) => Some(r),
+                Err(_) => return Err(SlugifyError::InvalidRegex(pat.to_string())),
+            }
+        } else {
+            None
+        };
+        let allowed_chars = self
+            .allowed_chars
+            .as_deref()
+            .map(CharSet::parse)
+            .transpose()?;
+        let disallowed_pattern = disallowed_pattern(&self.allowed_extra_chars, self.allow_unicode);
+
+        let word_translations = self
+            .word_translations
+            .iter()
+            .map(|(k, v)| {
+                let norm = |w: &str| {
+                    normalize_word(
+                        w,
+                        self.allow_unicode,
+                        self.emoji_policy,
+                        self.transliterator.as_ref(),
+                        self.lowercase,
+                    )
+                };
+                (norm(k), norm(v))
+            })
+            .filter(|(k, _)| !k.is_empty())
+            .collect();
+
+        let numeric_id_pattern = match (self.strip_numeric_ids, self.numeric_id_pattern.as_deref())
+        {
+            (false, _) => None,
+            (true, None) => Some(NUMERIC_ID_PATTERN.clone()),
+            (true, Some(pat)) => match Regex::new(pat) {
+                Ok(r) => Some(r),
+                Err(_) => return Err(SlugifyError::InvalidRegex(pat.to_string())),
+            },
+        };
+
+        let custom_translations =
+            TranslationTable::new(self.custom_translations.iter().map(|(a, b)| (a, b)))
+                .map_err(|e| SlugifyError::InvalidTranslations(e.to_string()))?;
+
+        let abbreviations = if self.expand_abbreviations {
+            let mut table = self.custom_abbreviations.clone();
+            table.extend(
+                crate::special::abbreviations(self.st_expansion)
+                    .into_iter()
+                    .map(|(a, b)| (a.to_string(), b.to_string())),
+            );
+            table
+        } else {
+            Vec::new()
+        };
+
+        #[cfg_attr(not(feature = "tech_terms"), allow(unused_mut))]
+        let mut protected_terms = self.protected_terms;
+        #[cfg(feature = "tech_terms")]
+        if self.builtin_protected_terms {
+            // terms made only of allowed characters already survive as is
+            let kept = |term: &str| {
+                term.chars()
+                    .all(|c| c.is_alphanumeric() || self.allowed_extra_chars.contains(&c))
+            };
+            protected_terms.extend(
+                crate::special::TECH_TERMS
+                    .iter()
+                    .filter(|(term, _)| !kept(term))
+                    .map(|(a, b)| (a.to_string(), b.to_string())),
+            );
+        }
+
+        let replacement_rules = self
+            .replacement_rules
+            .into_iter()
+            .map(|rule| {
+                let rx = rule.compile()?;
+                Ok((rule, rx))
+            })
+            .collect::<Result<Vec<_>, SlugifyError>>()?;
+
+        Ok(SlugifyOptions {
+            entities: self.entities,
+            entity_policy: self.entity_policy,
+            decoded_separators: self.decoded_separators,
+            decimal: self.decimal,
+            hexadecimal: self.hexadecimal,
+            max_length: self.max_length,
+            word_boundary: self.word_boundary,
+            // identifiers always join words with `_`
+            separator: match (self.identifier.is_some(), self.case_style.separator()) {
+                (true, _) => "_".to_string(),
+                (false, Some(sep)) => sep.to_string(),
+                (false, None) => self.separator,
+            },
+            case_style: self.case_style,
+            save_order: self.save_order,
+            stopwords: self.stopwords,
+            regex_pattern: regex_compiled,
+            char_classifier: self.char_classifier,
+            allowed_chars,
+            allowed_extra_chars: self.allowed_extra_chars,
+            disallowed_pattern,
+            lowercase: self.lowercase,
+            case_fold_stage: self.case_fold_stage,
+            restricted_chars: self.restricted_chars,
+            replacements: self.replacements,
+            replacement_rules,
+            allow_unicode: self.allow_unicode,
+            emoji_policy: self.emoji_policy,
+            stopword_stemming: self.stopword_stemming,
+            stemming_locale: self.stemming_locale,
+            word_translations,
+            abbreviations,
+            protected_terms,
+            numeric_id_pattern,
+            separator_scope: self.separator_scope,
+            digit_boundaries: self.digit_boundaries,
+            split_camel_case: self.split_camel_case,
+            word_segmentation: self.word_segmentation,
+            word_breaks: self.word_breaks,
+            preserve_trailing_number: self.preserve_trailing_number,
+            strict_max_length: self.strict_max_length,
+            length_unit: self.length_unit,
+            truncation_hash: self.truncation_hash,
+            budget: self.budget,
+            strict: self.strict,
+            max_dropped_fraction: self.max_dropped_fraction,
+            source_separators: self.source_separators,
+            cjk_quotes: self.cjk_quotes,
+            edge_trim: self.edge_trim,
+            grapheme_replacements: self.grapheme_replacements,
+            symbol_locale: self.symbol_locale,
+            numeric_cleanup: self.numeric_cleanup,
+            transliterator: self.transliterator,
+            rtl_word_order: self.rtl_word_order,
+            custom_translations,
+            compat: self.compat,
+            idna: self.idna,
+            identifier: self.identifier,
+        })
+    }
+}
+
+// A separator must not be mistaken for part of a word, nor be stripped or
+// mangled by consumers of the slug (whitespace, control characters).
+fn is_valid_separator(separator: &str, allow_unicode: bool) -> bool {
+    !separator.is_empty()
+        && separator.chars().all(|c| {
+            !c.is_control()
+                && !c.is_whitespace()
+                && !c.is_alphanumeric()
+                && (allow_unicode || c.is_ascii())
+        })
+}
+
+// New internal API that takes the options struct. Keeps behavior identical.
+fn slugify_with_options(input: &str, opts: &SlugifyOptions) -> String {
+    // only `strict` runs can fail
+    slugify_pipeline(input, opts, false, true).unwrap_or_default()
+}
+
+// The pipeline without `max_length`, for callers that join several slugs and
+// truncate the result with `truncate_joined` (`stream`).
+pub(crate) fn slugify_untruncated(input: &str, opts: &SlugifyOptions) -> String {
+    slugify_pipeline(input, opts, false, false).unwrap_or_default()
+}
 
-    // 3. Normalize / transliterate according to `allow_unicode`
-    let normalized = normalize_text(&after_quotes, opts.allow_unicode, opts.transliterate_icons);
+// Slug words of `input` joined with the internal separator, before step 13
+// (separator, case style, identifier); `None` in `compat` modes. Used to
+// trace slug words back to the input (`preview`).
+pub(crate) fn internal_words(
+    input: &str,
+    opts: &SlugifyOptions,
+    truncate: bool,
+) -> Option<(String, &'static str)> {
+    match pipeline_words(input, opts, false, truncate) {
+        Ok(PipelineOutput::Words(words, sep)) => Some((words, sep)),
+        _ => None,
+    }
+}
+
+// `max_length` truncation of a finished slug whose words are joined with
+// `opts.separator` (which must not be empty).
+pub(crate) fn truncate_joined(slug: &str, opts: &SlugifyOptions) -> String {
+    if opts.max_length == 0 {
+        return slug.to_string();
+    }
+    truncate_slug(slug, opts, &opts.separator)
+}
+
+// With `strict`, input rejected by a policy (`EntityPolicy::Error`) is an
+// error; otherwise such policies fall back to their lenient variant.
+// `truncate` is off when the caller applies `max_length` itself.
+fn slugify_pipeline(
+    input: &str,
+    opts: &SlugifyOptions,
+    strict: bool,
+    truncate: bool,
+) -> Result<String, SlugifyError> {
+    Ok(match pipeline_words(input, opts, strict, truncate)? {
+        PipelineOutput::Done(slug) => slug,
+        PipelineOutput::Words(words, sep) => finish_slug(&words, sep, opts),
+    })
+}
+
+// Result of steps 1-12: the slug of a `compat` mode, or the words joined
+// with the internal separator, still waiting for step 13.
+enum PipelineOutput {
+    Done(String),
+    Words(String, &'static str),
+}
+
+fn pipeline_words(
+    input: &str,
+    opts: &SlugifyOptions,
+    strict: bool,
+    truncate: bool,
+) -> Result<PipelineOutput, SlugifyError> {
+    match opts.compat {
+        Compat::None => {}
+        Compat::GitHub => {
+            return Ok(PipelineOutput::Done(crate::compat::github_anchor(
+                input,
+                opts.lowercase,
+            )));
+        }
+        Compat::Django => {
+            return Ok(PipelineOutput::Done(crate::compat::django_slugify(
+                input,
+                opts.allow_unicode,
+            )));
+        }
+    }
+    let sep = internal_separator(opts);
+    let mut text = prepare_input(input, sep).into_owned();
+    let mut meter = Meter::new(if strict { opts.budget } else { None });
+    for step in BuiltinStage::ALL {
+        if step == BuiltinStage::Truncate && !truncate {
+            continue;
+        }
+        if let Some(cost) = step_cost(step, opts) {
+            meter.charge(&text, cost)?;
+        }
+        text = run_step(step, &text, opts, sep, strict)?;
+    }
+    Ok(PipelineOutput::Words(text, sep))
+}
 
-    // 4. Optionally decode named entities
-    let decoded_named = if opts.entities {
-        decode_named_entities(&normalized)
+// Input as the first step sees it: without the character used as internal
+// separator, and without directional marks, which are invisible and would
+// otherwise become separators inside words.
+pub(crate) fn prepare_input<'a>(input: &'a str, sep: &str) -> std::borrow::Cow<'a, str> {
+    let input = if sep == DEFAULT_SEPARATOR {
+        std::borrow::Cow::Borrowed(input)
     } else {
-        normalized
+        std::borrow::Cow::Owned(input.replace(INSERTED_SEPARATOR, ""))
     };
+    if input.chars().any(is_bidi_control) {
+        std::borrow::Cow::Owned(input.chars().filter(|c| !is_bidi_control(*c)).collect())
+    } else {
+        input
+    }
+}
 
-    // 5. Decode numeric references (decimal / hex) as requested
-    let decoded_numeric = decode_numeric_refs(&decoded_named, opts.decimal, opts.hexadecimal);
+// `budget` units charged on the input of `step`; `None` for the steps that
+// only reorder or cut words.
+fn step_cost(step: BuiltinStage, opts: &SlugifyOptions) -> Option<u64> {
+    let regex_cost = |rx: &Option<Regex>| if rx.is_some() { REGEX_STEP_COST } else { 1 };
+    match step {
+        BuiltinStage::Replacements => Some(regex_cost(&opts.numeric_id_pattern)),
+        BuiltinStage::Sanitize => Some(regex_cost(&opts.regex_pattern)),
+        BuiltinStage::Truncate | BuiltinStage::WordOrder => None,
+        _ => Some(1),
+    }
+}
 
-    // 6. Re-normalize and apply lowercase if requested
-    let renormalized = normalize_text(&decoded_numeric, opts.allow_unicode, opts.transliterate_icons);
-    let case_folded = if opts.lowercase {
-        renormalized.to_lowercase()
-    } else {
-        renormalized
+// Steps 1-12, one at a time. With `strict`, input rejected by a policy
+// (`EntityPolicy::Error`, `RestrictedChars::Error`) is an error.
+pub(crate) fn run_step(
+    step: BuiltinStage,
+    text: &str,
+    opts: &SlugifyOptions,
+    sep: &'static str,
+    strict: bool,
+) -> Result<String, SlugifyError> {
+    let symbols = || {
+        opts.symbol_locale
+            .map(crate::special::symbol_words)
+            .unwrap_or(&[])
     };
+    let translit = opts.transliterator.as_ref();
+    Ok(match step {
+        // 1. Apply user replacements first (match python-slugify behavior).
+        // Note: pre-translations are available via `crate::special::apply_pre_translations`
+        // but are NOT applied by default to preserve original Python semantics.
+        BuiltinStage::Replacements => {
+            let input = match opts.numeric_id_pattern {
+                Some(ref rx) => rx.replace_all(text, " "),
+                None => std::borrow::Cow::Borrowed(text),
+            };
+            let after_replacements = apply_user_replacements(&input, opts);
+            let after_replacements =
+                apply_replacement_rules(&after_replacements, &opts.replacement_rules, false);
+            let after_replacements =
+                crate::special::apply_protected_terms(&after_replacements, &opts.protected_terms);
+            // numbers are cleaned up before quotes and source separators split them
+            // ("1'000"), and again in step 7 for digits decoded from references
+            let after_replacements = opts.numeric_cleanup.apply(&after_replacements);
+            let after_replacements =
+                crate::special::expand_abbreviations(&after_replacements, &opts.abbreviations);
+            let after_replacements = map_source_separators(
+                &after_replacements,
+                &opts.source_separators,
+                opts.cjk_quotes,
+            );
+            let after_replacements = crate::special::expand_symbols(&after_replacements, symbols());
+            if opts.split_camel_case {
+                split_camel_case(&after_replacements)
+            } else {
+                after_replacements
+            }
+        }
+
+        // 2. Replace quotes with separator early to avoid merging words. With
+        // `SeparatorScope::Inserted` a space is used so the quote is not mistaken
+        // for a source hyphen; sanitization turns it into a separator.
+        BuiltinStage::Quotes => {
+            let quote_sep = if sep == DEFAULT_SEPARATOR {
+                DEFAULT_SEPARATOR
+            } else {
+                " "
+            };
+            QUOTE_PATTERN.replace_all(text, quote_sep).to_string()
+        }
 
-    // 7. Remove quotes (now safe) and cleanup number commas
-    let cleaned = QUOTE_PATTERN.replace_all(&case_folded, "").to_string();
-    let cleaned = remove_commas_between_digits(&cleaned);
+        // 3. Apply custom translations, then normalize / transliterate
+        // according to `allow_unicode`. `CaseFoldStage::BeforeTranslit` folds
+        // first; step 6 lowercases again for characters decoded from references.
+        BuiltinStage::Normalize => {
+            let after_quotes =
+                if opts.lowercase && opts.case_fold_stage == CaseFoldStage::BeforeTranslit {
+                    std::borrow::Cow::Owned(text.to_lowercase())
+                } else {
+                    std::borrow::Cow::Borrowed(text)
+                };
+            let after_quotes = apply_translations_with(&opts.custom_translations, &after_quotes);
+            normalize_text(
+                &after_quotes,
+                opts.allow_unicode,
+                opts.emoji_policy,
+                translit,
+            )
+        }
 
-    // 8. Replace disallowed characters with separator using pattern or provided regex
-    let sanitized = apply_pattern_replacement(&cleaned, opts);
+        // 4-5. Decode named and numeric (decimal / hex) references as requested,
+        // then expand symbols that came out of the references ("&amp;")
+        BuiltinStage::Entities => {
+            let mut entity_opts = crate::entities::EntityOptions::from(opts);
+            if !strict && entity_opts.policy == EntityPolicy::Error {
+                entity_opts.policy = EntityPolicy::Decode;
+            }
+            let decoded = crate::entities::try_decode(text, &entity_opts)?;
+            crate::special::expand_symbols(&decoded, symbols())
+        }
 
-    // 9. Collapse duplicate separators and trim leading/trailing separators
-    let collapsed = DUPLICATE_DASH_PATTERN
-        .replace_all(&sanitized, DEFAULT_SEPARATOR)
-        .to_string();
-    let collapsed = collapsed.trim_matches('-').to_string();
+        // 6. Re-normalize (translating characters decoded from references) and
+        // apply lowercase if requested
+        BuiltinStage::Renormalize => {
+            let decoded_numeric = apply_translations_with(&opts.custom_translations, text);
+            let renormalized = normalize_text(
+                &decoded_numeric,
+                opts.allow_unicode,
+                opts.emoji_policy,
+                translit,
+            );
+            if opts.lowercase {
+                renormalized.to_lowercase()
+            } else {
+                renormalized
+            }
+        }
 
-    // 10. Remove stopwords if provided
-    let without_stopwords = remove_stopwords(&collapsed, &opts.stopwords, opts.lowercase);
+        // 7. Remove quotes (now safe) and clean up numbers, then split text
+        // written without spaces into words
+        BuiltinStage::Cleanup => {
+            let cleaned = QUOTE_PATTERN.replace_all(text, "").to_string();
+            let cleaned = opts.numeric_cleanup.apply(&cleaned);
+            if opts.allow_unicode {
+                segment_words(&cleaned, opts.word_segmentation)
+            } else {
+                cleaned
+            }
+        }
 
-    // 11. Apply replacements again (post-processing)
-    let finalized = apply_replacements(&without_stopwords, &opts.replacements);
+        // 8. Replace disallowed characters with separator using pattern or
+        // provided regex
+        BuiltinStage::Sanitize => {
+            let sanitized = apply_pattern_replacement(text, opts, sep);
+            let sanitized = split_digit_boundaries(&sanitized, opts.digit_boundaries, sep);
+            strip_restricted(sanitized, opts, sep, strict)?
+        }
 
-    // 12. Truncate if requested
-    let truncated = if opts.max_length > 0 {
-        smart_truncate(
-            &finalized,
-            opts.max_length,
-            opts.word_boundary,
-            DEFAULT_SEPARATOR,
-            opts.save_order,
-        )
-    } else {
-        finalized
-    };
+        // 9. Collapse duplicate separators and trim leading/trailing
+        // separators plus the `edge_trim` characters. The requested separator
+        // found in the text counts as one, so a separator made of word
+        // characters ("ZZ") is collapsed and trimmed like the others.
+        BuiltinStage::Collapse => {
+            let sanitized = separator_to_internal(text.to_string(), opts, sep);
+            if sep == DEFAULT_SEPARATOR {
+                let collapsed = DUPLICATE_DASH_PATTERN
+                    .replace_all(&sanitized, DEFAULT_SEPARATOR)
+                    .to_string();
+                collapsed
+                    .trim_matches(|c| opts.edge_trim.matches(c))
+                    .to_string()
+            } else {
+                // source hyphens only count inside words: drop dangling ones
+                sanitized
+                    .split(sep)
+                    .map(|w| w.trim_matches('-'))
+                    .filter(|w| !w.is_empty())
+                    .collect::<Vec<&str>>()
+                    .join(sep)
+                    .trim_matches(|c| sep.contains(c) || opts.edge_trim.matches(c))
+                    .to_string()
+            }
+        }
+
+        // 10. Translate words through the user dictionary, then remove stopwords
+        BuiltinStage::Stopwords => {
+            let translated = translate_words(text, &opts.word_translations, sep);
+            let remove = |s: &str, sep: &str| {
+                if opts.stopword_stemming {
+                    remove_stopwords_stemmed(
+                        s,
+                        &opts.stopwords,
+                        opts.lowercase,
+                        opts.stemming_locale,
+                        sep,
+                    )
+                } else {
+                    remove_stopwords(s, &opts.stopwords, opts.lowercase, sep)
+                }
+            };
+            if opts.word_breaks == WordBreaks::Unicode && !opts.stopwords.is_empty() {
+                remove_within_words(&translated, sep, remove)
+            } else {
+                remove(&translated, sep)
+            }
+        }
+
+        // 11. Apply replacements again (post-processing)
+        BuiltinStage::PostReplacements => {
+            let finalized = apply_user_replacements(text, opts);
+            apply_replacement_rules(&finalized, &opts.replacement_rules, true)
+        }
+
+        // 12. Truncate if requested
+        BuiltinStage::Truncate => {
+            if opts.max_length > 0 {
+                truncate_slug(text, opts, sep)
+            } else {
+                text.to_string()
+            }
+        }
 
-    // 13. Replace default separator with requested separator if different
-    finalize_separator(&truncated, &opts.separator)
+        BuiltinStage::WordOrder => {
+            if opts.rtl_word_order == RtlWordOrder::Visual {
+                visual_word_order(text, sep)
+            } else {
+                text.to_string()
+            }
+        }
+    })
 }
 
-fn apply_replacements(input: &str, replacements: &[(String, String)]) -> String {
-    if replacements.is_empty() {
-        return input.to_string();
+// 13. Replace the internal separator with the requested one if different,
+// then apply the identifier / IDNA forms.
+pub(crate) fn finish_slug(words: &str, sep: &str, opts: &SlugifyOptions) -> String {
+    let slug = finalize_separator(words, sep, &opts.separator, opts.case_style);
+
+    if let Some(style) = &opts.identifier {
+        return style.apply(&slug, opts.allow_unicode);
     }
-    let mut out = input.to_string();
-    for (old, new) in replacements.iter() {
-        out = out.replace(old, new);
+    if opts.idna {
+        let trim: Vec<char> = opts.separator.chars().chain(['.']).collect();
+        return slug
+            .split('.')
+            .map(|label| crate::punycode::fit_label(label, &trim))
+            .filter(|label| !label.is_empty())
+            .collect::<Vec<_>>()
+            .join(".");
     }
-    out
+    slug
 }
 
-fn is_emoji(c: char) -> bool {
-    // Heuristic ranges covering most common emoji/pictographs
-    let cp = c as u32;
-    matches!(cp,
-        0x1F300..=0x1F5FF | // Misc Symbols and Pictographs
-        0x1F600..=0x1F64F | // Emoticons
-        0x1F680..=0x1F6FF | // Transport & Map
-        0x1F700..=0x1F77F | // Alchemical Symbols
-        0x1F900..=0x1F9FF | // Supplemental Symbols and Pictographs
-        0x2600..=0x26FF   | // Misc symbols
-        0x2700..=0x27BF   | // Dingbats
-        0xFE00..=0xFE0F   | // Variation Selectors
-        0x1F1E6..=0x1F1FF   // Regional indicator symbols (flags)
-    )
+/// Formats the slug of `text` straight into a formatter:
+/// `write!(f, "{}", SlugDisplay::new(title, &opts))` renders what
+/// `slugify_with_options_public(&opts, title)` returns without allocating
+/// the final `String`. With plain separated output the words are written
+/// one by one; identifier, IDNA and camel/Pascal/snake styles still build
+/// the slug first.
+pub struct SlugDisplay<'a> {
+    text: &'a str,
+    opts: &'a SlugifyOptions,
+}
+
+impl<'a> SlugDisplay<'a> {
+    pub fn new(text: &'a str, opts: &'a SlugifyOptions) -> Self {
+        SlugDisplay { text, opts }
+    }
 }
 
-fn normalize_text(s: &str, allow_unicode: bool, transliterate_icons: bool) -> String {
-    if allow_unicode {
-        s.nfkc().collect()
-    } else {
-        // If transliterate_icons is disabled we remove emoji early.
-        // If enabled, perform a small, explicit mapping for common
-        // pictographs (heart, rocket, unicorn) to ASCII words so
-        // `deunicode` can handle the rest similar to python-slugify.
-        let filtered: String = if !transliterate_icons {
-            s.chars().filter(|c| !is_emoji(*c)).collect()
-        } else {
-            // Replace a few known icons with ASCII words separated by spaces
-            // so later normalization and pattern replacement will turn
-            // them into words in the final slug.
-            let mut out = String::with_capacity(s.len() * 4);
-            for c in s.chars() {
-                match c {
-                    '♥' => out.push_str(" hearts "),
-                    '🚀' => out.push_str(" rocket "),
-                    '🦄' => out.push_str(" unicorn "),
-                    // fall back to keeping the character for other codepoints
-                    other => out.push(other),
-                }
-            }
-            out
-        };
-        let decomposed: String = filtered.nfkd().collect();
-        deunicode(&decomposed)
-    }
-}
+/// Length in `opts.length_unit` of `slugify_with_options_public(opts, text)`,
+/// including the expansion of a multi-character separator, computed
+/// without joining the final slug. Meant for live length counters while a
+/// title is typed. Identifier, IDNA and camel/Pascal/snake styles (whose
+/// prefixes, suffixes and casing can change the length) are measured on
+/// the built slug.
+pub fn projected_length(text: &str, opts: &SlugifyOptions) -> usize {
+    let (words, sep) = match pipeline_words(text, opts, false, true) {
+        Ok(PipelineOutput::Words(words, sep)) => (words, sep),
+        Ok(PipelineOutput::Done(slug)) => return opts.length_unit.measure(&slug),
+        Err(_) => return 0,
+    };
+    let unit = opts.length_unit;
+    if opts.case_style != CaseStyle::Separated || opts.identifier.is_some() || opts.idna {
+        return unit.measure(&finish_slug(&words, sep, opts));
+    }
+    let gaps = words.matches(sep).count();
+    unit.measure(&words) - gaps * unit.measure(sep) + gaps * unit.measure(&opts.separator)
+}
+
+impl fmt::Display for SlugDisplay<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let opts = self.opts;
+        let (words, sep) = match pipeline_words(self.text, opts, false, true) {
+            Ok(PipelineOutput::Words(words, sep)) => (words, sep),
+            Ok(PipelineOutput::Done(slug)) => return f.write_str(&slug),
+            Err(_) => return Ok(()),
+        };
+        if opts.case_style != CaseStyle::Separated || opts.identifier.is_some() || opts.idna {
+            return f.write_str(&finish_slug(&words, sep, opts));
+        }
+        for (i, word) in words.split(sep).enumerate() {
+            if i > 0 {
+                f.write_str(&opts.separator)?;
+            }
+            f.write_str(word)?;
+        }
+        Ok(())
+    }
+}
+
+// Bidi marks, embeddings, overrides and isolates (U+061C, U+200E/F,
+// U+202A-E, U+2066-9).
+fn is_bidi_control(c: char) -> bool {
+    matches!(
+        c,
+        '\u{61C}' | '\u{200E}' | '\u{200F}' | '\u{202A}'..='\u{202E}' | '\u{2066}'..='\u{2069}'
+    )
+}
+
+fn is_rtl_char(c: char) -> bool {
+    matches!(c as u32,
+        0x0590..=0x08FF | // Hebrew, Arabic, Syriac, Thaana, NKo, ...
+        0xFB1D..=0xFDFF | // Hebrew / Arabic presentation forms A
+        0xFE70..=0xFEFF | // Arabic presentation forms B
+        0x10800..=0x10FFF | // historic RTL scripts
+        0x1E800..=0x1EFFF
+    )
+}
+
+// Direction of a word from its first letter; `None` for words without
+// letters (numbers), which follow the surrounding run.
+fn word_is_rtl(word: &str) -> Option<bool> {
+    word.chars().find(|c| c.is_alphabetic()).map(is_rtl_char)
+}
+
+// Word-level version of the bidi algorithm: the first strong word sets the
+// base direction, runs of the opposite direction are reversed in place and
+// an RTL base reverses the whole line.
+fn visual_word_order(s: &str, sep: &str) -> String {
+    let words: Vec<&str> = split_words(s, sep).collect();
+    let mut dirs = Vec::with_capacity(words.len());
+    let mut current = words.iter().find_map(|w| word_is_rtl(w)).unwrap_or(false);
+    let base_rtl = current;
+    for w in &words {
+        current = word_is_rtl(w).unwrap_or(current);
+        dirs.push(current);
+    }
+    // reverse maximal runs that go against the base direction
+    let mut ordered: Vec<&str> = Vec::with_capacity(words.len());
+    let mut i = 0;
+    while i < words.len() {
+        let mut j = i;
+        while j < words.len() && dirs[j] == dirs[i] {
+            j += 1;
+        }
+        if dirs[i] != base_rtl {
+            ordered.extend(words[i..j].iter().rev());
+        } else {
+            ordered.extend(&words[i..j]);
+        }
+        i = j;
+    }
+    if base_rtl {
+        ordered.reverse();
+    }
+    ordered.join(sep)
+}
+
+// A logical order whose `visual_word_order` is `s`, for slugs that are
+// already in visual order. Either base direction can lead to `s` (an RTL
+// line may end with an LTR run), so both are tried.
+pub(crate) fn logical_word_order(s: &str, sep: &str) -> String {
+    let words: Vec<&str> = split_words(s, sep).collect();
+    let first_rtl = words.iter().find_map(|w| word_is_rtl(w)).unwrap_or(false);
+    let guess = logical_words(&words, first_rtl, sep);
+    if visual_word_order(&guess, sep) == words.join(sep) {
+        return guess;
+    }
+    let other = logical_words(&words, !first_rtl, sep);
+    if visual_word_order(&other, sep) == words.join(sep) {
+        other
+    } else {
+        guess
+    }
+}
+
+// Undo `visual_word_order` for base direction `base_rtl`. Reversed runs
+// end with a strong word in visual order, so words without letters go
+// with the next strong word.
+fn logical_words(visual: &[&str], base_rtl: bool, sep: &str) -> String {
+    let mut words = visual.to_vec();
+    if base_rtl {
+        words.reverse();
+    }
+    let mut dirs = vec![base_rtl; words.len()];
+    let mut next = base_rtl;
+    for (i, w) in words.iter().enumerate().rev() {
+        next = word_is_rtl(w).unwrap_or(next);
+        dirs[i] = next;
+    }
+    let mut ordered: Vec<&str> = Vec::with_capacity(words.len());
+    let mut i = 0;
+    while i < words.len() {
+        let mut j = i;
+        while j < words.len() && dirs[j] == dirs[i] {
+            j += 1;
+        }
+        if dirs[i] != base_rtl {
+            ordered.extend(words[i..j].iter().rev());
+        } else {
+            ordered.extend(&words[i..j]);
+        }
+        i = j;
+    }
+    ordered.join(sep)
+}
+
+// Rewrite configured source separators, then CJK punctuation. Spaces
+// around words / separators let sanitization turn them into regular
+// (inserted) separators.
+fn map_source_separators(
+    s: &str,
+    table: &[(char, SourceSeparator)],
+    cjk_quotes: CjkQuotes,
+) -> String {
+    let cjk = |c: char| crate::special::CJK_PUNCTUATION.contains(&c);
+    if table.is_empty() && !s.chars().any(cjk) {
+        return s.to_string();
+    }
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        let action = table.iter().find(|(k, _)| *k == c).map(|(_, a)| a);
+        match action {
+            Some(SourceSeparator::Separator) => out.push(' '),
+            Some(SourceSeparator::Word(w)) => {
+                out.push(' ');
+                out.push_str(w);
+                out.push(' ');
+            }
+            Some(SourceSeparator::Remove) => {}
+            None if cjk_quotes == CjkQuotes::Remove && crate::special::CJK_QUOTES.contains(&c) => {}
+            None if cjk(c) => out.push(' '),
+            None => out.push(c),
+        }
+    }
+    out
+}
+
+// Truncation strategy for step 12: plain `smart_truncate`, or (with
+// `preserve_trailing_number`) reserve room for a trailing numeric word and
+// truncate only the words before it. Falls back to `smart_truncate` when the
+// number alone does not fit.
+fn truncate_slug(s: &str, opts: &SlugifyOptions, sep: &str) -> String {
+    if opts.truncation_hash {
+        return truncate_with_hash(s, opts, sep);
+    }
+    let unit = opts.length_unit;
+    let resized = unit.measure(&opts.separator) != unit.measure(sep);
+    if opts.separator.is_empty() || (opts.strict_max_length && resized) {
+        return truncate_words(s, opts, sep);
+    }
+    let plain = || {
+        if opts.word_boundary && opts.word_breaks == WordBreaks::Unicode {
+            return truncate_at_word_breaks(s, opts.max_length, unit, sep);
+        }
+        smart_truncate_by(
+            s,
+            opts.max_length,
+            unit,
+            opts.word_boundary,
+            sep,
+            opts.save_order,
+        )
+    };
+    if !opts.preserve_trailing_number || unit.measure(s) <= opts.max_length {
+        return plain();
+    }
+    let Some(number) = split_words(s, sep).last() else {
+        return plain();
+    };
+    if !number.chars().all(|c| c.is_ascii_digit()) {
+        return plain();
+    }
+    let head = s.trim_end_matches(sep).strip_suffix(number).unwrap_or("");
+    match crate::suffix::join_with_suffix_by(
+        head,
+        number,
+        sep,
+        opts.max_length,
+        unit,
+        opts.word_boundary,
+    ) {
+        Ok(out) => out,
+        Err(_) => plain(),
+    }
+}
+
+// `word_boundary` truncation with `WordBreaks::Unicode`: the longest prefix
+// within `max_length` ending at a Unicode word boundary, separators trimmed;
+// a hard cut when not even the first word fits.
+fn truncate_at_word_breaks(s: &str, max_length: usize, unit: LengthUnit, sep: &str) -> String {
+    let s = s.trim_matches(|c| sep.contains(c));
+    if unit.measure(s) <= max_length {
+        return s.to_string();
+    }
+    let limit = unit.prefix(s, max_length).len();
+    unicode_word_bounds(s)
+        .filter(|&i| i > 0 && i <= limit)
+        .map(|i| s[..i].trim_end_matches(|c| sep.contains(c)))
+        .filter(|head| !head.is_empty())
+        .last()
+        .unwrap_or(&s[..limit])
+        .to_string()
+}
+
+// Truncation when the final separator differs in length from the internal
+// `sep`: `Separator::None` (words are concatenated later) or
+// `strict_max_length`. Each gap between kept words costs as much as
+// `opts.separator`. Mirrors `smart_truncate` / `preserve_trailing_number`.
+fn truncate_words(s: &str, opts: &SlugifyOptions, sep: &str) -> String {
+    let unit = opts.length_unit;
+    let gap = unit.measure(&opts.separator);
+    let words: Vec<&str> = split_words(s, sep).collect();
+    let len = |w: &[&str]| {
+        w.iter().map(|w| unit.measure(w)).sum::<usize>() + gap * w.len().saturating_sub(1)
+    };
+    if len(&words) <= opts.max_length {
+        return words.join(sep);
+    }
+    if opts.preserve_trailing_number
+        && let Some((number, head)) = words.split_last()
+        && number.chars().all(|c| c.is_ascii_digit())
+        && unit.measure(number) + gap < opts.max_length
+    {
+        let budget = opts.max_length - unit.measure(number) - gap;
+        let mut kept = fit_words(head, budget, gap, unit, opts.word_boundary, opts.save_order);
+        kept.push(number.to_string());
+        return kept.join(sep);
+    }
+    fit_words(
+        &words,
+        opts.max_length,
+        gap,
+        unit,
+        opts.word_boundary,
+        opts.save_order,
+    )
+    .join(sep)
+}
+
+// `TruncationStrategy::CutWithHash`: when the slug is too long, keep what
+// fits before the separator and `TRUNCATION_HASH_LEN` hash characters, then
+// append the hash. A gap costs as much as the separator the slug ends up
+// with under `strict_max_length`, like `truncate_words`.
+fn truncate_with_hash(s: &str, opts: &SlugifyOptions, sep: &str) -> String {
+    let unit = opts.length_unit;
+    let gap = if opts.strict_max_length || opts.separator.is_empty() {
+        unit.measure(&opts.separator)
+    } else {
+        unit.measure(sep)
+    };
+    let words: Vec<&str> = split_words(s, sep).collect();
+    let len =
+        words.iter().map(|w| unit.measure(w)).sum::<usize>() + gap * words.len().saturating_sub(1);
+    if len <= opts.max_length {
+        return words.join(sep);
+    }
+    let hash = content_hash(&words);
+    let budget = opts.max_length.saturating_sub(TRUNCATION_HASH_LEN + gap);
+    let mut kept = fit_words(&words, budget, gap, unit, false, true);
+    if kept.is_empty() {
+        return unit.prefix(&hash, opts.max_length).to_string();
+    }
+    kept.push(hash);
+    kept.join(sep)
+}
+
+const TRUNCATION_HASH_LEN: usize = 8;
+
+// Lowercase base36 hash of the slug words, independent of the separator.
+fn content_hash(words: &[&str]) -> String {
+    const DIGITS: &[u8; 36] = b"0123456789abcdefghijklmnopqrstuvwxyz";
+    let mut h = crate::fingerprint::Fnv1a::new();
+    for word in words {
+        h.write_str(word);
+    }
+    let mut n = h.finish();
+    (0..TRUNCATION_HASH_LEN)
+        .map(|_| {
+            let c = DIGITS[(n % 36) as usize] as char;
+            n /= 36;
+            c
+        })
+        .collect()
+}
+
+// Words (or, without `word_boundary`, word prefixes) whose total length in
+// `unit`, plus `gap` between consecutive words, fits in `budget`.
+fn fit_words(
+    words: &[&str],
+    budget: usize,
+    gap: usize,
+    unit: LengthUnit,
+    word_boundary: bool,
+    save_order: bool,
+) -> Vec<String> {
+    let mut kept: Vec<String> = Vec::new();
+    let mut left = budget;
+    if word_boundary {
+        for word in words {
+            let n = unit.measure(word) + if kept.is_empty() { 0 } else { gap };
+            if n <= left {
+                kept.push(word.to_string());
+                left -= n;
+            } else if save_order {
+                break;
+            }
+            if left == 0 {
+                break;
+            }
+        }
+        if !kept.is_empty() {
+            return kept;
+        }
+        left = budget;
+    }
+    for word in words {
+        let cost = if kept.is_empty() { 0 } else { gap };
+        if left <= cost {
+            break;
+        }
+        left -= cost;
+        let part = unit.prefix(word, left);
+        if part.is_empty() {
+            break;
+        }
+        // `Chars` cuts count whole clusters, see `LengthUnit::prefix`
+        left -= match unit {
+            LengthUnit::Chars => part.graphemes(true).count(),
+            _ => unit.measure(part),
+        };
+        kept.push(part.to_string());
+    }
+    kept
+}
+
+// HTML entities and numeric references, kept intact by `split_camel_case`.
+#[allow(clippy::unwrap_used)]
+static ANY_ENTITY_PATTERN: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"&(?:#[xX][0-9a-fA-F]+|#\d+|\w+);").unwrap());
+
+// Put a space at lower -> upper transitions, before the last capital of an
+// acronym followed by a lowercase letter ("HTMLParser"), and between
+// letters and digits.
+fn split_camel_case(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 8);
+    let mut last = 0;
+    for m in ANY_ENTITY_PATTERN.find_iter(s) {
+        split_camel_run(&s[last..m.start()], &mut out);
+        out.push_str(m.as_str());
+        last = m.end();
+    }
+    split_camel_run(&s[last..], &mut out);
+    out
+}
+
+fn split_camel_run(s: &str, out: &mut String) {
+    let chars: Vec<char> = s.chars().collect();
+    for (i, &c) in chars.iter().enumerate() {
+        if i > 0 {
+            let p = chars[i - 1];
+            let next_lower = chars.get(i + 1).is_some_and(|n| n.is_lowercase());
+            let boundary = (p.is_lowercase() && c.is_uppercase())
+                || (p.is_uppercase() && c.is_uppercase() && next_lower)
+                || (p.is_alphabetic() && c.is_numeric())
+                || (p.is_numeric() && c.is_alphabetic());
+            if boundary {
+                out.push(' ');
+            }
+        }
+        out.push(c);
+    }
+}
+
+// Insert `sep` at letter/digit transitions selected by `placement`.
+fn split_digit_boundaries(s: &str, placement: DigitBoundaries, sep: &str) -> String {
+    if placement == DigitBoundaries::Keep {
+        return s.to_string();
+    }
+    let (before, after) = match placement {
+        DigitBoundaries::Keep => (false, false),
+        DigitBoundaries::BeforeDigits => (true, false),
+        DigitBoundaries::AfterDigits => (false, true),
+        DigitBoundaries::Both => (true, true),
+    };
+    let mut out = String::with_capacity(s.len() + 8);
+    let mut prev: Option<char> = None;
+    for c in s.chars() {
+        if let Some(p) = prev
+            && ((before && p.is_alphabetic() && c.is_numeric())
+                || (after && p.is_numeric() && c.is_alphabetic()))
+        {
+            out.push_str(sep);
+        }
+        out.push(c);
+        prev = Some(c);
+    }
+    out
+}
+
+// Put a space between adjacent words found by `segmentation`; boundaries
+// next to spaces or punctuation are left to sanitization.
+fn segment_words(s: &str, segmentation: WordSegmentation) -> String {
+    let boundaries: Vec<usize> = match segmentation {
+        WordSegmentation::Off => return s.to_string(),
+        WordSegmentation::Unicode => unicode_word_bounds(s).collect(),
+        WordSegmentation::Dictionary => dictionary_boundaries(s),
+    };
+    let is_word = |w: &str| w.chars().any(char::is_alphanumeric);
+    let mut out = String::with_capacity(s.len() + boundaries.len());
+    let mut start = 0;
+    let mut prev_word = false;
+    for end in boundaries.into_iter().filter(|&i| i > 0).chain([s.len()]) {
+        if end <= start {
+            continue;
+        }
+        let segment = &s[start..end];
+        let word = is_word(segment);
+        if word && prev_word {
+            out.push(' ');
+        }
+        out.push_str(segment);
+        prev_word = word;
+        start = end;
+    }
+    out
+}
+
+// Byte offsets of the UAX #29 word boundaries of `s` (starting with 0),
+// except inside Thai, Lao, Khmer, Myanmar, ... runs, which UAX #29 splits
+// into single characters.
+fn unicode_word_bounds(s: &str) -> impl Iterator<Item = usize> + '_ {
+    s.split_word_bound_indices().map(|(i, _)| i).filter(|&i| {
+        let before = s[..i].chars().next_back();
+        let after = s[i..].chars().next();
+        !(before.is_some_and(is_complex_context) && after.is_some_and(is_complex_context))
+    })
+}
+
+// Run `remove` on the Unicode words inside each separated word of `s`
+// (`WordBreaks::Unicode`): the pieces it keeps are joined back without a
+// separator, and words left empty are dropped.
+fn remove_within_words(s: &str, sep: &str, remove: impl Fn(&str, &str) -> String) -> String {
+    s.split(sep)
+        .map(|word| {
+            let pieces: Vec<&str> = unicode_word_bounds(word)
+                .chain([word.len()])
+                .collect::<Vec<usize>>()
+                .windows(2)
+                .map(|w| &word[w[0]..w[1]])
+                .collect();
+            remove(&pieces.join(INSERTED_SEPARATOR), INSERTED_SEPARATOR)
+                .replace(INSERTED_SEPARATOR, "")
+        })
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<String>>()
+        .join(sep)
+}
+
+// Scripts whose words UAX #29 cannot find without a dictionary (Thai, Lao,
+// Myanmar, Khmer, Tai Tham and relatives).
+fn is_complex_context(c: char) -> bool {
+    matches!(
+        c,
+        '\u{0E00}'..='\u{0EFF}'
+            | '\u{1000}'..='\u{109F}'
+            | '\u{1780}'..='\u{17FF}'
+            | '\u{1950}'..='\u{19FF}'
+            | '\u{1A20}'..='\u{1AAF}'
+            | '\u{A9E0}'..='\u{A9FF}'
+            | '\u{AA60}'..='\u{AADF}'
+    )
+}
+
+// Byte offsets of the word boundaries found by the ICU4X dictionary
+// segmenter.
+#[cfg(feature = "segmenter")]
+fn dictionary_boundaries(s: &str) -> Vec<usize> {
+    use icu_segmenter::WordSegmenter;
+    use icu_segmenter::options::WordBreakInvariantOptions;
+    WordSegmenter::new_dictionary(WordBreakInvariantOptions::default())
+        .segment_str(s)
+        .collect()
+}
+
+#[cfg(not(feature = "segmenter"))]
+fn dictionary_boundaries(_: &str) -> Vec<usize> {
+    Vec::new()
+}
+
+// `RestrictedChars` on the sanitized text; separators are always kept.
+fn strip_restricted(
+    s: String,
+    opts: &SlugifyOptions,
+    sep: &str,
+    strict: bool,
+) -> Result<String, SlugifyError> {
+    if opts.restricted_chars == RestrictedChars::Allow || !opts.allow_unicode {
+        return Ok(s);
+    }
+    let restricted = |c: char| c != '-' && !sep.contains(c) && !identifier_allowed(c);
+    if strict
+        && opts.restricted_chars == RestrictedChars::Error
+        && let Some(c) = s.chars().find(|&c| restricted(c))
+    {
+        return Err(SlugifyError::RestrictedCharacter(c));
+    }
+    Ok(s.chars().filter(|&c| !restricted(c)).collect())
+}
+
+// Occurrences of the requested separator in `s` replaced by the internal
+// one. Only plain separated slugs are joined with `opts.separator`, and
+// `SeparatorScope::Inserted` keeps source text as it is. A separator made
+// of word characters ("x", "ZZ") only counts when it forms a whole word,
+// so "xbox" keeps its letters.
+fn separator_to_internal(s: String, opts: &SlugifyOptions, internal: &str) -> String {
+    let separator = opts.separator.as_str();
+    if opts.case_style != CaseStyle::Separated
+        || opts.separator_scope != SeparatorScope::All
+        || separator.is_empty()
+        || separator == internal
+        || !s.contains(separator)
+    {
+        return s;
+    }
+    if !separator.chars().any(char::is_alphanumeric) {
+        return s.replace(separator, internal);
+    }
+    s.split(internal)
+        .map(|w| {
+            if w.split(separator).all(str::is_empty) {
+                ""
+            } else {
+                w
+            }
+        })
+        .collect::<Vec<&str>>()
+        .join(internal)
+}
+
+// Separator used between words inside the pipeline (steps 8-12).
+pub(crate) fn internal_separator(opts: &SlugifyOptions) -> &'static str {
+    if opts.separator_scope == SeparatorScope::Inserted && opts.separator != DEFAULT_SEPARATOR {
+        INSERTED_SEPARATOR
+    } else {
+        DEFAULT_SEPARATOR
+    }
+}
+
+fn apply_replacements(input: &str, replacements: &[(String, String)]) -> String {
+    if replacements.is_empty() {
+        return input.to_string();
+    }
+    let mut out = input.to_string();
+    for (old, new) in replacements.iter() {
+        out = out.replace(old, new);
+    }
+    out
+}
+
+fn apply_user_replacements(input: &str, opts: &SlugifyOptions) -> String {
+    if opts.grapheme_replacements {
+        apply_grapheme_replacements(input, &opts.replacements)
+    } else {
+        apply_replacements(input, &opts.replacements)
+    }
+}
+
+// Like `apply_replacements`, but on NFC text and only for matches that
+// start and end on a grapheme boundary. Empty targets are ignored.
+fn apply_grapheme_replacements(input: &str, replacements: &[(String, String)]) -> String {
+    if replacements.is_empty() {
+        return input.to_string();
+    }
+    let mut out: String = input.nfc().collect();
+    for (old, new) in replacements.iter() {
+        let old: String = old.nfc().collect();
+        if old.is_empty() {
+            continue;
+        }
+        let bounds: Vec<usize> = out
+            .grapheme_indices(true)
+            .map(|(i, _)| i)
+            .chain(std::iter::once(out.len()))
+            .collect();
+        let mut replaced = String::with_capacity(out.len());
+        let mut k = 0;
+        while k + 1 < bounds.len() {
+            let start = bounds[k];
+            if out[start..].starts_with(old.as_str())
+                && let Ok(end_k) = bounds.binary_search(&(start + old.len()))
+            {
+                replaced.push_str(new);
+                k = end_k;
+                continue;
+            }
+            replaced.push_str(&out[start..bounds[k + 1]]);
+            k += 1;
+        }
+        out = replaced;
+    }
+    out
+}
+
+fn is_emoji(c: char) -> bool {
+    // Heuristic ranges covering most common emoji/pictographs
+    let cp = c as u32;
+    matches!(cp,
+        0x1F300..=0x1F5FF | // Misc Symbols and Pictographs
+        0x1F600..=0x1F64F | // Emoticons
+        0x1F680..=0x1F6FF | // Transport & Map
+        0x1F700..=0x1F77F | // Alchemical Symbols
+        0x1F900..=0x1F9FF | // Supplemental Symbols and Pictographs
+        0x2600..=0x26FF   | // Misc symbols
+        0x2700..=0x27BF   | // Dingbats
+        0xFE00..=0xFE0F   | // Variation Selectors
+        0x1F1E6..=0x1F1FF   // Regional indicator symbols (flags)
+    )
+}
+
+fn normalize_text(
+    s: &str,
+    allow_unicode: bool,
+    emoji_policy: EmojiPolicy,
+    translit: &dyn Transliterator,
+) -> String {
+    if allow_unicode {
+        // emoji are dropped by sanitization unless `EmojiPolicy::Keep`
+        // (see `apply_pattern_replacement`)
+        s.nfkc().collect()
+    } else {
+        // Remove emoji early, or replace every emoji (including
+        // multi-codepoint sequences such as flags or skin tones) with its
+        // name / shortcode, separated by spaces so it becomes words in the
+        // final slug.
+        let filtered: String = match emoji_policy {
+            EmojiPolicy::Remove => s.chars().filter(|c| !is_emoji(*c)).collect(),
+            EmojiPolicy::Transliterate | EmojiPolicy::Keep => {
+                rename_emoji(s, |g| crate::special::emoji_name(g).map(str::to_string))
+            }
+            EmojiPolicy::Shortcode => rename_emoji(s, |g| {
+                crate::special::emoji_shortcode(g)
+                    .map(|sc| format!(":{}:", sc))
+                    .or_else(|| crate::special::emoji_name(g).map(str::to_string))
+            }),
+        };
+        let decomposed: String = filtered.nfkd().collect();
+        translit.transliterate(&decomposed)
+    }
+}
+
+// Replace emoji graphemes with `rename(grapheme)`, padded with spaces. Only
+// pictographs are renamed; text symbols such as © keep their `deunicode`
+// transliteration.
+fn rename_emoji<F: Fn(&str) -> Option<String>>(s: &str, rename: F) -> String {
+    let mut out = String::with_capacity(s.len() * 4);
+    for g in s.graphemes(true) {
+        let name = if g.chars().any(|c| is_emoji(c) || c == '\u{20E3}') {
+            rename(g)
+        } else {
+            None
+        };
+        match name {
+            Some(name) => {
+                out.push(' ');
+                out.push_str(&name);
+                out.push(' ');
+            }
+            None => out.push_str(g),
+        }
+    }
+    out
+}
+
+// Apply `rx` to the text between emoji graphemes only, so emoji survive the
+// unicode character class (`EmojiPolicy::Keep`).
+fn replace_outside_emoji(s: &str, rx: &Regex, sep: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut run = String::new();
+    for g in s.graphemes(true) {
+        if g.chars().any(is_emoji) {
+            out.push_str(&rx.replace_all(&run, sep));
+            run.clear();
+            out.push_str(g);
+        } else {
+            run.push_str(g);
+        }
+    }
+    out.push_str(&rx.replace_all(&run, sep));
+    out
+}
+
+fn apply_pattern_replacement(s: &str, opts: &SlugifyOptions, sep: &str) -> String {
+    if let Some(ref rx) = opts.regex_pattern {
+        return rx.replace_all(s, sep).to_string();
+    }
+    if let Some(ref classifier) = opts.char_classifier {
+        return classifier.apply(s, sep);
+    }
+    if let Some(ref set) = opts.allowed_chars {
+        return set.replace_disallowed(s, sep);
+    }
+    let pattern = match &opts.disallowed_pattern {
+        Some(rx) => rx,
+        None if opts.allow_unicode => &DISALLOWED_UNICODE_CHARS_PATTERN,
+        None => &DISALLOWED_CHARS_PATTERN,
+    };
+    if opts.allow_unicode && opts.emoji_policy == EmojiPolicy::Keep {
+        replace_outside_emoji(s, pattern, sep)
+    } else {
+        pattern.replace_all(s, sep).to_string()
+    }
+}
+
+// `DISALLOWED_CHARS_PATTERN` / `DISALLOWED_UNICODE_CHARS_PATTERN` minus
+// `extra`; `None` when there is nothing to subtract.
+fn disallowed_pattern(extra: &[char], allow_unicode: bool) -> Option<Regex> {
+    if extra.is_empty() {
+        return None;
+    }
+    let base = if allow_unicode {
+        DISALLOWED_UNICODE_CHARS_PATTERN.as_str()
+    } else {
+        DISALLOWED_CHARS_PATTERN.as_str()
+    };
+    let class = base.strip_suffix('+').unwrap_or(base);
+    // escaped single characters always form a valid class
+    #[allow(clippy::expect_used)]
+    let rx = Regex::new(&format!("[{}--[{}]]+", class, escape_class_chars(extra)))
+        .expect("disallowed pattern");
+    Some(rx)
+}
+
+fn escape_class_chars(chars: &[char]) -> String {
+    chars
+        .iter()
+        .map(|c| regex::escape(c.encode_utf8(&mut [0; 4])))
+        .collect()
+}
+
+// Normalize a dictionary entry the way slug words are normalized, using the
+// default character patterns.
+fn normalize_word(
+    s: &str,
+    allow_unicode: bool,
+    emoji_policy: EmojiPolicy,
+    translit: &dyn Transliterator,
+    lowercase: bool,
+) -> String {
+    let normalized = normalize_text(s, allow_unicode, emoji_policy, translit);
+    let folded = if lowercase { normalized.to_lowercase() } else { normalized };
+    let pattern = if allow_unicode {
+        &DISALLOWED_UNICODE_CHARS_PATTERN
+    } else {
+        &DISALLOWED_CHARS_PATTERN
+    };
+    let sanitized = pattern.replace_all(&folded, DEFAULT_SEPARATOR);
+    DUPLICATE_DASH_PATTERN
+        .replace_all(&sanitized, DEFAULT_SEPARATOR)
+        .trim_matches('-')
+        .to_string()
+}
+
+// Dictionary values are stored joined with `DEFAULT_SEPARATOR`; they are
+// re-joined with `sep` so multi-word translations stay separate words.
+fn translate_words(s: &str, dict: &HashMap<String, String>, sep: &str) -> String {
+    if dict.is_empty() {
+        return s.to_string();
+    }
+    s.split(sep)
+        .map(|w| match dict.get(w) {
+            Some(t) if sep != DEFAULT_SEPARATOR => t.replace(DEFAULT_SEPARATOR, sep),
+            Some(t) => t.clone(),
+            None => w.to_string(),
+        })
+        .filter(|w| !w.is_empty())
+        .collect::<Vec<String>>()
+        .join(sep)
+}
+
+fn remove_stopwords(s: &str, stopwords: &[String], lowercase: bool, sep: &str) -> String {
+    if stopwords.is_empty() {
+        return s.to_string();
+    }
+    if lowercase {
+        let lower_stop: Vec<String> = stopwords.iter().map(|w| w.to_lowercase()).collect();
+        s.split(sep)
+            .filter(|w| !lower_stop.contains(&w.to_string()))
+            .collect::<Vec<&str>>()
+            .join(sep)
+    } else {
+        s.split(sep)
+            .filter(|w| !stopwords.contains(&w.to_string()))
+            .collect::<Vec<&str>>()
+            .join(sep)
+    }
+}
+
+#[cfg(feature = "stemming")]
+fn remove_stopwords_stemmed(
+    s: &str,
+    stopwords: &[String],
+    lowercase: bool,
+    locale: Locale,
+    sep: &str,
+) -> String {
+    use rust_stemmers::{Algorithm, Stemmer};
+
+    if stopwords.is_empty() {
+        return s.to_string();
+    }
+    let stemmer = Stemmer::create(match locale {
+        Locale::En => Algorithm::English,
+        Locale::De => Algorithm::German,
+        Locale::Es => Algorithm::Spanish,
+        Locale::Fr => Algorithm::French,
+        Locale::Nl => Algorithm::Dutch,
+    });
+    let stem = |w: &str| -> String {
+        if lowercase {
+            stemmer.stem(&w.to_lowercase()).into_owned()
+        } else {
+            stemmer.stem(w).into_owned()
+        }
+    };
+    let stop_stems: Vec<String> = stopwords.iter().map(|w| stem(w)).collect();
+    s.split(sep)
+        .filter(|w| !stop_stems.contains(&stem(w)))
+        .collect::<Vec<&str>>()
+        .join(sep)
+}
+
+// Without the `stemming` feature the builder refuses `stopword_stemming`,
+// so this is only reachable through a hand-built `SlugifyOptions`.
+#[cfg(not(feature = "stemming"))]
+fn remove_stopwords_stemmed(
+    s: &str,
+    stopwords: &[String],
+    lowercase: bool,
+    _locale: Locale,
+    sep: &str,
+) -> String {
+    remove_stopwords(s, stopwords, lowercase, sep)
+}
+
+fn finalize_separator(s: &str, internal: &str, separator: &str, case: CaseStyle) -> String {
+    let capitalize = |w: &str| {
+        let mut chars = w.chars();
+        match chars.next() {
+            Some(first) => first.to_uppercase().chain(chars.flat_map(char::to_lowercase)).collect(),
+            None => String::new(),
+        }
+    };
+    let words = split_words(s, internal);
+    match case {
+        CaseStyle::Separated if separator != internal => s.replace(internal, separator),
+        CaseStyle::Separated => s.to_string(),
+        CaseStyle::Camel => words
+            .enumerate()
+            .map(|(i, w)| if i == 0 { w.to_lowercase() } else { capitalize(w) })
+            .collect(),
+        CaseStyle::Pascal => words.map(capitalize).collect(),
+        CaseStyle::Snake => words.map(str::to_lowercase).collect::<Vec<_>>().join("_"),
+        CaseStyle::ScreamingSnake => words.map(str::to_uppercase).collect::<Vec<_>>().join("_"),
+    }
+}
+
+/// Remove commas between digits ("1,234" -> "1234"); the default
+/// `NumericCleanup`.
+pub fn remove_commas_between_digits(s: &str) -> String {
+    NumericCleanup::default().apply(s)
+}
+
+/// Helper: normalize and transliterate when `allow_unicode == false`.
+pub fn normalize_and_transliterate(s: &str, allow_unicode: bool) -> String {
+    if allow_unicode {
+        // Normalize to compatibility composed form (similar to NFKC in Python)
+        s.nfkc().collect()
+    } else {
+        // NFKD then ascii transliteration using `deunicode`
+        let decomposed: String = s.nfkd().collect();
+        deunicode(&decomposed)
+    }
+}
+
+pub fn smart_truncate(
+    input: &str,
+    max_length: usize,
+    word_boundary: bool,
+    separator: &str,
+    save_order: bool,
+) -> String {
+    smart_truncate_by(
+        input,
+        max_length,
+        LengthUnit::Chars,
+        word_boundary,
+        separator,
+        save_order,
+    )
+}
+
+/// `smart_truncate` with `max_length` counted in `unit`. Cuts inside a word
+/// keep whole grapheme clusters (see `LengthUnit::prefix`).
+pub fn smart_truncate_by(
+    input: &str,
+    max_length: usize,
+    unit: LengthUnit,
+    word_boundary: bool,
+    separator: &str,
+    save_order: bool,
+) -> String {
+    // strip characters contained in `separator` from both ends (python semantics)
+    let sep_chars: Vec<char> = separator.chars().collect();
+    // Trims any leading or trailing characters from `input` that are present in `sep_chars`,
+    // and assigns the resulting string to `s`.
+    //
+    // # Arguments
+    //
+    // * `input` - The original string to be trimmed.
+    // * `sep_chars` - A collection of separator characters to be removed from both ends of `input`.
+    //
+    // # Returns
+    //
+    // A new `String` with the specified separator characters removed from the start and end.
+    let s = input
+        .trim_matches(|c: char| sep_chars.contains(&c))
+        .to_string();
+
+    if max_length == 0 {
+        return s;
+    }
+
+    if unit.measure(&s) < max_length {
+        return s;
+    }
+
+    if !word_boundary {
+        return unit
+            .prefix(&s, max_length)
+            .trim_matches(|c: char| sep_chars.contains(&c))
+            .to_string();
+    }
+
+    if !s.contains(separator) {
+        return unit.prefix(&s, max_length).to_string();
+    }
+
+    let mut truncated = String::new();
+    for word in split_words(&s, separator) {
+        let next_len = unit.measure(&truncated) + unit.measure(word);
+        if next_len < max_length {
+            truncated.push_str(word);
+            truncated.push_str(separator);
+        } else if next_len == max_length {
+            truncated.push_str(word);
+            break;
+        } else if save_order {
+            break;
+        } else {
+            // when not save_order, continue to next word (Python picks different word order)
+            // Python's original logic implicitly continues the loop; so we continue here.
+            continue;
+        }
+    }
+
+    if truncated.is_empty() {
+        truncated = unit.prefix(&s, max_length).to_string();
+    }
+
+    truncated
+        .trim_matches(|c: char| sep_chars.contains(&c))
+        .to_string()
+}
+
+/// Iterate over the words of `slug` exactly as `smart_truncate` sees them.
+///
+/// Splits on the whole `separator` string (so multi-character separators
+/// such as `"--"` are honoured) and skips the empty segments produced by
+/// leading, trailing or repeated separators. An empty separator yields the
+/// whole slug as a single word.
+pub fn split_words<'a>(slug: &'a str, separator: &'a str) -> impl Iterator<Item = &'a str> + 'a {
+    let (whole, parts) = if separator.is_empty() {
+        (Some(slug), None)
+    } else {
+        (None, Some(slug.split(separator)))
+    };
+    whole
+        .into_iter()
+        .chain(parts.into_iter().flatten())
+        .filter(|w| !w.is_empty())
+}
+
+/// Build an anchored regex matching every slug produced under `opts`,
+/// e.g. `^[a-z0-9]+(?:-[a-z0-9]+)*$` for the defaults.
+///
+/// Useful for route constraints that must stay consistent with generation.
+/// The word class follows `allow_unicode`, `lowercase` and `case_style`
+/// (camel, Pascal and screaming snake case allow uppercase), and words are
+/// joined with the separator the case style imposes; when a custom
+/// `regex_pattern`, `char_classifier`, `allowed_chars` or an `identifier`
+/// style (whose prefix and suffix may be anything) is set the allowed
+/// characters are not derived and the pattern only requires a non-empty slug.
+/// With `idna`, punycode labels (`xn--mnchen-3ya`) are accepted too.
+/// `Compat::GitHub` anchors keep `-` and `_` anywhere ("foo--bar_baz"),
+/// `Compat::Django` slugs keep `_` inside words ("foo_bar-baz"). `max_length` and user
+/// `replacements` that introduce other characters are not reflected.
+pub fn slug_pattern(opts: &SlugifyOptions) -> String {
+    let Some(word) = word_class(opts) else {
+        return String::from("^.+$");
+    };
+    let sep = slug_separator(opts);
+    let words = if sep.is_empty() {
+        format!("{}+", word)
+    } else {
+        format!(
+            "{w}+(?:{sep}{w}+)*",
+            w = word,
+            // `-` is literal outside a character class; keep the common case readable
+            sep = regex::escape(sep).replace(r"\-", "-")
+        )
+    };
+    if opts.idna && opts.compat == Compat::None {
+        // punycode: the `xn--` prefix, then ASCII, separators and `-`
+        let letters = if slug_is_lowercase(opts) {
+            "a-z"
+        } else {
+            "A-Za-z"
+        };
+        let sep_chars: Vec<char> = sep.chars().collect();
+        return format!(
+            "^(?:xn--[{}0-9{}-]+|{})$",
+            letters,
+            escape_class_chars(&sep_chars),
+            words
+        );
+    }
+    format!("^{}$", words)
+}
+
+// Whether the final slug is lowercase; the case style decides over
+// `lowercase`.
+pub(crate) fn slug_is_lowercase(opts: &SlugifyOptions) -> bool {
+    match opts.compat {
+        Compat::GitHub => return opts.lowercase,
+        Compat::Django => return true,
+        Compat::None => {}
+    }
+    match opts.case_style {
+        CaseStyle::Separated => opts.lowercase,
+        CaseStyle::Snake => true,
+        CaseStyle::Camel | CaseStyle::Pascal | CaseStyle::ScreamingSnake => false,
+    }
+}
+
+// Separator between the words of the final slug: the one `case_style`
+// imposes (`_` for snake case, none for camel case), else `separator`.
+// Empty for identifiers, where `_` may also lead or end the slug, and for
+// GitHub anchors, which keep every `-`.
+pub(crate) fn slug_separator(opts: &SlugifyOptions) -> &str {
+    match opts.compat {
+        Compat::GitHub => return "",
+        Compat::Django => return "-",
+        Compat::None => {}
+    }
+    if opts.identifier.is_some() {
+        return "";
+    }
+    opts.case_style.separator().unwrap_or(&opts.separator)
+}
+
+// Regex class of the characters slug words may use under `opts`, including
+// `allowed_extra_chars` and the hyphens `SeparatorScope::Inserted` keeps;
+// `None` when a custom `regex_pattern`, `char_classifier`, `allowed_chars`
+// or `identifier` makes it unknowable.
+pub(crate) fn word_class(opts: &SlugifyOptions) -> Option<String> {
+    // github-slugger keeps letters, marks, numbers, `_` and `-`
+    if opts.compat == Compat::GitHub {
+        let class = if opts.lowercase {
+            r"[\p{Ll}\p{Lm}\p{Lo}\p{M}\p{N}\p{Pc}-]"
+        } else {
+            r"[\p{L}\p{M}\p{N}\p{Pc}-]"
+        };
+        return Some(class.to_string());
+    }
+    // Django keeps `\w`, so `_` too
+    if opts.compat == Compat::Django {
+        let class = if opts.allow_unicode {
+            r"[^\W\p{Lu}\p{Lt}]"
+        } else {
+            r"[a-z0-9_]"
+        };
+        return Some(class.to_string());
+    }
+    if opts.regex_pattern.is_some()
+        || opts.char_classifier.is_some()
+        || opts.allowed_chars.is_some()
+        || opts.identifier.is_some()
+    {
+        return None;
+    }
+    let class = match (opts.allow_unicode, slug_is_lowercase(opts)) {
+        (false, true) => r"[a-z0-9]",
+        (false, false) => r"[A-Za-z0-9]",
+        (true, true) => r"[^\W_\p{Lu}\p{Lt}]",
+        (true, false) => r"[^\W_]",
+    };
+    let mut extra = opts.allowed_extra_chars.clone();
+    // source hyphens are kept when only inserted separators are rewritten
+    if opts.separator_scope == SeparatorScope::Inserted && slug_separator(opts) != "-" {
+        extra.push('-');
+    }
+    if extra.is_empty() {
+        return Some(class.to_string());
+    }
+    Some(format!("[{}{}]", class, escape_class_chars(&extra)))
+}
+
+/// Public API that accepts an options struct. Prefer this for programmatic use
+/// to avoid long argument lists and improve readability.
+pub fn slugify_with_options_public(opts: &SlugifyOptions, text: &str) -> String {
+    slugify_with_options(text, opts)
+}
+
+/// Slugify every item of `texts` with the same compiled options, in order.
+/// With the `parallel` feature the items are spread over the rayon thread
+/// pool.
+pub fn slugify_batch<S: AsRef<str> + Sync>(texts: &[S], opts: &SlugifyOptions) -> Vec<String> {
+    #[cfg(feature = "parallel")]
+    {
+        use rayon::prelude::*;
+        texts
+            .par_iter()
+            .map(|t| slugify_with_options(t.as_ref(), opts))
+            .collect()
+    }
+    #[cfg(not(feature = "parallel"))]
+    {
+        texts
+            .iter()
+            .map(|t| slugify_with_options(t.as_ref(), opts))
+            .collect()
+    }
+}
+
+/// Like `slugify_with_options_public`, but input rejected by a strict
+/// policy (`EntityPolicy::Error`, `strict`) is an error instead of being
+/// slugified leniently.
+pub fn try_slugify_with_options(opts: &SlugifyOptions, text: &str) -> Result<String, SlugifyError> {
+    let slug = slugify_pipeline(text, opts, true, true)?;
+    if opts.strict {
+        check_loss(text, &slug, opts)?;
+    }
+    Ok(slug)
+}
+
+// The `strict` checks: an empty slug, then too many dropped characters.
+fn check_loss(text: &str, slug: &str, opts: &SlugifyOptions) -> Result<(), SlugifyError> {
+    if slug.is_empty() {
+        return Err(SlugifyError::EmptyResult);
+    }
+    if opts.max_dropped_fraction >= 1.0 {
+        return Ok(());
+    }
+    let total = text.chars().filter(|c| !c.is_whitespace()).count();
+    let dropped = crate::loss::dropped_count(text, opts);
+    if dropped as f64 > opts.max_dropped_fraction * total as f64 {
+        return Err(SlugifyError::UnmappableInput { dropped, total });
+    }
+    Ok(())
+}
+
+/// Slugify `text` with `opts`, returning errors instead of panicking or
+/// falling back: together with `SlugifyOptions::builder().build()?` it
+/// covers both configuration and input errors. Same as
+/// `try_slugify_with_options`.
+pub fn try_slugify(text: &str, opts: &SlugifyOptions) -> Result<String, SlugifyError> {
+    try_slugify_with_options(opts, text)
+}
+
+fn first_n_chars(s: &str, n: usize) -> String {
+    // Use grapheme clusters so we don't split combined characters or emoji.
+    s.graphemes(true).take(n).collect()
+}
+
+#[cfg(test)]
+#[allow(clippy::expect_used)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_remove_commas_between_digits() {
+        assert_eq!(remove_commas_between_digits("1,234"), "1234");
+        assert_eq!(remove_commas_between_digits("1,234,567"), "1234567");
+        assert_eq!(remove_commas_between_digits("1,234 apples"), "1234 apples");
+        assert_eq!(
+            remove_commas_between_digits("apples, 1,234"),
+            "apples, 1234"
+        );
+        assert_eq!(
+            remove_commas_between_digits("no commas here"),
+            "no commas here"
+        );
+        assert_eq!(
+            remove_commas_between_digits(",leading comma"),
+            ",leading comma"
+        );
+        assert_eq!(
+            remove_commas_between_digits("trailing comma,"),
+            "trailing comma,"
+        );
+        assert_eq!(
+            remove_commas_between_digits("multiple,,commas"),
+            "multiple,,commas"
+        );
+    }
+
+    #[test]
+    fn test_smart_truncate() {
+        let start = std::time::Instant::now();
+        let input = "This is a simple test string for truncation.";
+        assert_eq!(smart_truncate(input, 10, false, " ", true), "This is a");
+        assert_eq!(smart_truncate(input, 10, true, " ", true), "This is a");
+        assert_eq!(
+            smart_truncate(input, 0, true, " ", true),
+            "This is a simple test string for truncation."
+        );
+        assert_eq!(
+            smart_truncate(input, 50, true, " ", true),
+            "This is a simple test string for truncation."
+        );
+        assert_eq!(
+            smart_truncate(input, 15, true, " ", false),
+            "This is a test"
+        );
+        let elapsed = start.elapsed();
+        println!("[TIMER] test_smart_truncate: {} ms", elapsed.as_millis());
+    }
+
+    #[test]
+    fn test_smart_truncate_no_max_length_python_case() {
+        let start = std::time::Instant::now();
+        let txt = "1,000 reasons you are #1";
+        // default max_length == 0 should return the original string
+        assert_eq!(smart_truncate(txt, 0, false, " ", false), txt);
+        let elapsed = start.elapsed();
+        println!(
+            "[TIMER] test_smart_truncate_no_max_length_python_case: {} ms",
+            elapsed.as_millis()
+        );
+    }
+
+    #[test]
+    fn test_smart_truncate_no_separator_python_case() {
+        let start = std::time::Instant::now();
+        let txt = "1,000 reasons you are #1";
+        // separator '_' is not present in the input; with large max_length, should return original
+        assert_eq!(smart_truncate(txt, 100, false, "_", false), txt);
+        let elapsed = start.elapsed();
+        println!(
+            "[TIMER] test_smart_truncate_no_separator_python_case: {} ms",
+            elapsed.as_millis()
+        );
+    }
+
+    #[test]
+    fn test_split_words_separators_and_empty_segments() {
+        let words: Vec<&str> = split_words("-one--two-", "-").collect();
+        assert_eq!(words, vec!["one", "two"]);
+        let words: Vec<&str> = split_words("aZZbZZZZc", "ZZ").collect();
+        assert_eq!(words, vec!["a", "b", "c"]);
+        let words: Vec<&str> = split_words("one-two", "").collect();
+        assert_eq!(words, vec!["one-two"]);
+        assert_eq!(split_words("", "-").count(), 0);
+        assert_eq!(split_words("---", "-").count(), 0);
+    }
+
+    #[test]
+    fn test_slug_pattern_matches_generated_slugs() {
+        let opts = SlugifyOptions::builder().build().unwrap();
+        assert_eq!(slug_pattern(&opts), "^[a-z0-9]+(?:-[a-z0-9]+)*$");
+        let rx = Regex::new(&slug_pattern(&opts)).unwrap();
+        assert!(rx.is_match(&slugify_with_options_public(&opts, "C'est déjà l'été.")));
+        assert!(!rx.is_match("Hello-World"));
+        assert!(!rx.is_match("-hello"));
+
+        let opts = SlugifyOptions::builder().separator(".").build().unwrap();
+        let rx = Regex::new(&slug_pattern(&opts)).unwrap();
+        assert!(rx.is_match(&slugify_with_options_public(&opts, "jaja---lol-méméméoo--a")));
+        assert!(!rx.is_match("jaja-lol"));
+
+        let opts = SlugifyOptions::builder().allow_unicode(true).build().unwrap();
+        let rx = Regex::new(&slug_pattern(&opts)).unwrap();
+        assert!(rx.is_match(&slugify_with_options_public(&opts, "Компьютер и 漢字")));
+        assert!(!rx.is_match("Компьютер"));
+    }
+
+    #[cfg(feature = "stemming")]
+    #[test]
+    fn test_stopword_stemming() {
+        let opts = SlugifyOptions::builder()
+            .stopwords(vec!["run"])
+            .stopword_stemming(true)
+            .build()
+            .unwrap();
+        assert_eq!(
+            slugify_with_options_public(&opts, "She runs while running a run"),
+            "she-while-a"
+        );
+    }
+
+    #[cfg(not(feature = "stemming"))]
+    #[test]
+    fn test_stopword_stemming_requires_feature() {
+        let res = SlugifyOptions::builder().stopword_stemming(true).build();
+        assert!(matches!(res, Err(SlugifyError::FeatureDisabled("stemming"))));
+    }
+
+    #[test]
+    fn test_word_translations() {
+        let mut dict = HashMap::new();
+        dict.insert("école", "School");
+        dict.insert("pomme", "apple tree");
+        dict.insert("le", "");
+        dict.insert("et", "and");
+        // translation runs before stopword removal
+        let opts = SlugifyOptions::builder()
+            .word_translations(dict)
+            .stopwords(vec!["and"])
+            .build()
+            .unwrap();
+        assert_eq!(
+            slugify_with_options_public(&opts, "L'École et le Pomme"),
+            "l-school-apple-tree"
+        );
+    }
+
+    #[test]
+    fn test_expand_abbreviations_option() {
+        let txt = "Dr. Smith & St. John";
+        let opts = SlugifyOptions::builder().build().unwrap();
+        assert_eq!(slugify_with_options_public(&opts, txt), "dr-smith-st-john");
+        let opts = SlugifyOptions::builder()
+            .expand_abbreviations(true)
+            .build()
+            .unwrap();
+        assert_eq!(
+            slugify_with_options_public(&opts, txt),
+            "doctor-smith-saint-john"
+        );
+        let opts = SlugifyOptions::builder()
+            .expand_abbreviations(true)
+            .st_expansion(StExpansion::Street)
+            .abbreviations(vec![("Dr.", "drive")])
+            .build()
+            .unwrap();
+        assert_eq!(
+            slugify_with_options_public(&opts, txt),
+            "drive-smith-street-john"
+        );
+    }
+
+    #[test]
+    fn test_case_fold_stage() {
+        let build = |stage, table: &[(&str, &str)]| {
+            SlugifyOptions::builder()
+                .case_fold_stage(stage)
+                .custom_translations(table.iter().copied())
+                .build()
+                .unwrap()
+        };
+        let lower_keys = [("ä", "ae"), ("ü", "ue")];
+        let opts = build(CaseFoldStage::AfterTranslit, &lower_keys);
+        assert_eq!(slugify_with_options_public(&opts, "Äpfel über"), "apfel-ueber");
+        let opts = build(CaseFoldStage::BeforeTranslit, &lower_keys);
+        assert_eq!(slugify_with_options_public(&opts, "Äpfel über"), "aepfel-ueber");
+
+        let upper_keys = [("Ü", "Ue")];
+        let opts = build(CaseFoldStage::AfterTranslit, &upper_keys);
+        assert_eq!(slugify_with_options_public(&opts, "Über"), "ueber");
+        let opts = build(CaseFoldStage::BeforeTranslit, &upper_keys);
+        assert_eq!(slugify_with_options_public(&opts, "Über"), "uber");
+
+        // without lowercase the stage has no effect
+        let opts = SlugifyOptions::builder()
+            .lowercase(false)
+            .case_fold_stage(CaseFoldStage::BeforeTranslit)
+            .build()
+            .unwrap();
+        assert_eq!(slugify_with_options_public(&opts, "Äpfel"), "Apfel");
+    }
+
+    #[test]
+    fn test_restricted_chars() {
+        let build = |policy| {
+            SlugifyOptions::builder()
+                .allow_unicode(true)
+                .restricted_chars(policy)
+                .build()
+        };
+        if !cfg!(feature = "unicode_security") {
+            assert!(matches!(
+                build(RestrictedChars::Strip),
+                Err(SlugifyError::FeatureDisabled("unicode_security"))
+            ));
+            return;
+        }
+        let text = "pay\u{200D}pal ᚠᚢ café-au-lait";
+        let allow = build(RestrictedChars::Allow).unwrap();
+        assert_eq!(
+            slugify_with_options_public(&allow, text),
+            "pay\u{200D}pal-ᚠᚢ-café-au-lait"
+        );
+        let strip = build(RestrictedChars::Strip).unwrap();
+        assert_eq!(
+            slugify_with_options_public(&strip, text),
+            "paypal-café-au-lait"
+        );
+        let error = build(RestrictedChars::Error).unwrap();
+        assert!(matches!(
+            try_slugify_with_options(&error, text),
+            Err(SlugifyError::RestrictedCharacter('\u{200D}'))
+        ));
+        assert_eq!(
+            slugify_with_options_public(&error, text),
+            "paypal-café-au-lait"
+        );
+        assert_eq!(
+            try_slugify_with_options(&error, "café au lait").unwrap(),
+            "café-au-lait"
+        );
+        // ASCII slugs are not affected
+        let ascii = SlugifyOptions::builder()
+            .restricted_chars(RestrictedChars::Error)
+            .build()
+            .unwrap();
+        assert_eq!(
+            try_slugify_with_options(&ascii, text).unwrap(),
+            "paypal-fu-cafe-au-lait"
+        );
+    }
+
+    #[test]
+    fn test_word_segmentation() {
+        let build = |segmentation| {
+            SlugifyOptions::builder()
+                .allow_unicode(true)
+                .word_segmentation(segmentation)
+                .build()
+        };
+        let off = build(WordSegmentation::Off).unwrap();
+        assert_eq!(slugify_with_options_public(&off, "我爱北京"), "我爱北京");
+        let unicode = build(WordSegmentation::Unicode).unwrap();
+        assert_eq!(
+            slugify_with_options_public(&unicode, "日本語のテキスト"),
+            "日-本-語-の-テキスト"
+        );
+        assert_eq!(
+            slugify_with_options_public(&unicode, "iPhone手机, l'été 3.14"),
+            "iphone-手-机-l-été-3-14"
+        );
+        // no dictionary: Thai is kept whole
+        assert_eq!(
+            slugify_with_options_public(&unicode, "ทุกสองสัปดาห์"),
+            "ทุกสองสัปดาห์"
+        );
+        // transliterated slugs are not affected
+        let ascii = SlugifyOptions::builder()
+            .word_segmentation(WordSegmentation::Unicode)
+            .build()
+            .unwrap();
+        assert_eq!(slugify_with_options_public(&ascii, "北京"), "bei-jing");
+        if !cfg!(feature = "segmenter") {
+            assert!(matches!(
+                build(WordSegmentation::Dictionary),
+                Err(SlugifyError::FeatureDisabled("segmenter"))
+            ));
+            return;
+        }
+        let dictionary = build(WordSegmentation::Dictionary).unwrap();
+        assert_eq!(
+            slugify_with_options_public(&dictionary, "こんにちは世界"),
+            "こんにちは-世界"
+        );
+        assert_eq!(
+            slugify_with_options_public(&dictionary, "ทุกสองสัปดาห์"),
+            "ทุก-สอง-สัปดาห์"
+        );
+    }
+
+    #[test]
+    fn test_word_breaks() {
+        let build = |breaks, max_length| {
+            SlugifyOptions::builder()
+                .allow_unicode(true)
+                .word_boundary(true)
+                .max_length(max_length)
+                .word_breaks(breaks)
+                .build()
+                .unwrap()
+        };
+        let text = "日本語のテキスト";
+        let separator = build(WordBreaks::Separator, 6);
+        assert_eq!(
+            slugify_with_options_public(&separator, text),
+            "日本語のテキ"
+        );
+        // cut between words, never inside the katakana word
+        let unicode = build(WordBreaks::Unicode, 6);
+        assert_eq!(slugify_with_options_public(&unicode, text), "日本語の");
+        assert_eq!(
+            slugify_with_options_public(&build(WordBreaks::Unicode, 9), "東京 日本語のテキスト"),
+            "東京-日本語の"
+        );
+        // like `Separator` when separators are the only word breaks
+        assert_eq!(
+            slugify_with_options_public(&build(WordBreaks::Unicode, 13), "hello big world"),
+            slugify_with_options_public(&build(WordBreaks::Separator, 13), "hello big world")
+        );
+        // a first word longer than `max_length` is cut
+        assert_eq!(
+            slugify_with_options_public(&build(WordBreaks::Unicode, 3), "テキスト"),
+            "テキス"
+        );
+        assert_eq!(
+            slugify_with_options_public(&build(WordBreaks::Unicode, 5), "ทุกสองสัปดาห์"),
+            "ทุกสอง"
+        );
+
+        let stopwords = |breaks| {
+            SlugifyOptions::builder()
+                .allow_unicode(true)
+                .stopwords(["の", "a"])
+                .word_breaks(breaks)
+                .build()
+                .unwrap()
+        };
+        let separator = stopwords(WordBreaks::Separator);
+        assert_eq!(slugify_with_options_public(&separator, text), text);
+        let unicode = stopwords(WordBreaks::Unicode);
+        assert_eq!(
+            slugify_with_options_public(&unicode, text),
+            "日本語テキスト"
+        );
+        assert_eq!(slugify_with_options_public(&unicode, "a の b"), "b");
+        assert_eq!(slugify_with_options_public(&unicode, "banana"), "banana");
+    }
+
+    #[test]
+    fn test_decoded_separators_option() {
+        let txt = "foo&#32;bar&#45;baz&#x2014;qux&#9;end &#233;t&#233; &#169;";
+        let opts = SlugifyOptions::builder().build().unwrap();
+        assert_eq!(
+            slugify_with_options_public(&opts, txt),
+            "foo-bar-baz-qux-end-ete-c"
+        );
+        let opts = SlugifyOptions::builder()
+            .decoded_separators(DecodedSeparators::Strip)
+            .build()
+            .unwrap();
+        // letters and symbols still decode; literal separators still split
+        assert_eq!(
+            slugify_with_options_public(&opts, txt),
+            "foobarbazquxend-ete-c"
+        );
+        assert_eq!(
+            slugify_with_options_public(&opts, "foo bar-baz"),
+            "foo-bar-baz"
+        );
+    }
+
+    #[test]
+    fn test_entity_policy_option() {
+        let build = |policy| {
+            SlugifyOptions::builder()
+                .entity_policy(policy)
+                .build()
+                .unwrap()
+        };
+        let txt = "Caf&eacute; &bogus; &amp; more";
+        let opts = build(EntityPolicy::Decode);
+        assert_eq!(slugify_with_options_public(&opts, txt), "cafe-bogus-more");
+        let opts = build(EntityPolicy::PassThrough);
+        assert_eq!(
+            slugify_with_options_public(&opts, txt),
+            "caf-eacute-bogus-amp-more"
+        );
+        let opts = build(EntityPolicy::StripAll);
+        assert_eq!(slugify_with_options_public(&opts, txt), "caf-more");
+        let opts = build(EntityPolicy::Error);
+        assert!(matches!(
+            try_slugify_with_options(&opts, txt),
+            Err(SlugifyError::UnknownEntity(e)) if e == "&bogus;"
+        ));
+        assert_eq!(slugify_with_options_public(&opts, txt), "cafe-bogus-more");
+        assert_eq!(
+            try_slugify_with_options(&opts, "Caf&eacute; &amp; more").unwrap(),
+            "cafe-more"
+        );
+        // decoding disabled: the policy is not consulted
+        let opts = SlugifyOptions::builder()
+            .entities(false)
+            .entity_policy(EntityPolicy::Error)
+            .build()
+            .unwrap();
+        assert_eq!(try_slugify_with_options(&opts, "&bogus;").unwrap(), "bogus");
+    }
+
+    #[test]
+    fn test_replacement_rules() {
+        let build = |rules: Vec<ReplacementRule>| {
+            SlugifyOptions::builder()
+                .replacement_rules(rules)
+                .build()
+                .unwrap()
+        };
+        // plain substring rules corrupt longer words
+        let opts = build(vec![ReplacementRule::new("or", "ou")]);
+        assert_eq!(slugify_with_options_public(&opts, "World or"), "would-ou");
+        let opts = build(vec![ReplacementRule::new("or", "ou").whole_word(true)]);
+        assert_eq!(slugify_with_options_public(&opts, "World or"), "world-ou");
+
+        let opts = build(vec![
+            ReplacementRule::new("USD", "dollars")
+                .case_insensitive(true)
+                .whole_word(true)
+                .stage(ReplacementStage::Pre),
+        ]);
+        assert_eq!(
+            slugify_with_options_public(&opts, "5 usd, 5 USD, USDT"),
+            "5-dollars-5-dollars-usdt"
+        );
+
+        // post rules see the slug: separators and lowercase are final
+        let opts = build(vec![
+            ReplacementRule::new("new-york", "nyc").stage(ReplacementStage::Post),
+        ]);
+        assert_eq!(slugify_with_options_public(&opts, "New York pizza"), "nyc-pizza");
+        let opts = build(vec![
+            ReplacementRule::new("New York", "nyc").stage(ReplacementStage::Post),
+        ]);
+        assert_eq!(
+            slugify_with_options_public(&opts, "New York pizza"),
+            "new-york-pizza"
+        );
+
+        // a pattern over the regex size limit is an error, not a panic
+        let huge = ReplacementRule::new("ß".repeat(1 << 17), "").case_insensitive(true);
+        assert!(matches!(
+            SlugifyOptions::builder().replacement_rules([huge]).build(),
+            Err(SlugifyError::InvalidRegex(_))
+        ));
+    }
+
+    #[test]
+    fn test_protected_terms_option() {
+        let opts = SlugifyOptions::builder()
+            .protected_terms([("K8s", "kubernetes"), ("C++", "c-plus-plus")])
+            .build()
+            .unwrap();
+        assert_eq!(
+            slugify_with_options_public(&opts, "Deploying C++ apps on k8s"),
+            "deploying-c-plus-plus-apps-on-kubernetes"
+        );
+        let opts = SlugifyOptions::builder()
+            .protected_terms([("C++", "cpp")])
+            .builtin_protected_terms(false)
+            .build()
+            .unwrap();
+        assert_eq!(slugify_with_options_public(&opts, "C# vs C++"), "c-vs-cpp");
+    }
+
+    #[cfg(feature = "tech_terms")]
+    #[test]
+    fn test_builtin_tech_terms() {
+        // the feature alone leaves the default output alone
+        let opts = SlugifyOptions::builder().build().unwrap();
+        assert_eq!(slugify_with_options_public(&opts, "C++ and C#"), "c-and-c");
+        let opts = SlugifyOptions::builder()
+            .builtin_protected_terms(true)
+            .build()
+            .unwrap();
+        assert_eq!(
+            slugify_with_options_public(&opts, "C++, C# and .NET (Node.js)"),
+            "cpp-c-sharp-and-dotnet-nodejs"
+        );
+        let opts = SlugifyOptions::builder()
+            .protected_terms([("C#", "csharp")])
+            .builtin_protected_terms(true)
+            .build()
+            .unwrap();
+        assert_eq!(slugify_with_options_public(&opts, "Learn C#"), "learn-csharp");
+    }
+
+    #[test]
+    fn test_strip_numeric_ids() {
+        let opts = SlugifyOptions::builder()
+            .strip_numeric_ids(true)
+            .build()
+            .unwrap();
+        assert_eq!(
+            slugify_with_options_public(&opts, "12345 – Blue Widget"),
+            "blue-widget"
+        );
+        assert_eq!(
+            slugify_with_options_public(&opts, "Blue Widget (#987)"),
+            "blue-widget"
+        );
+        assert_eq!(
+            slugify_with_options_public(&opts, "SKU-0042: Red Widget"),
+            "red-widget"
+        );
+        // short numbers inside titles are kept
+        assert_eq!(
+            slugify_with_options_public(&opts, "Top 10 Widgets"),
+            "top-10-widgets"
+        );
+        // years are not IDs
+        assert_eq!(
+            slugify_with_options_public(&opts, "Annual Report 2024"),
+            "annual-report-2024"
+        );
+        assert_eq!(
+            slugify_with_options_public(&opts, "1984 Revisited"),
+            "1984-revisited"
+        );
+
+        let opts = SlugifyOptions::builder()
+            .strip_numeric_ids(true)
+            .numeric_id_pattern(Some(r"^ID\d+\s+"))
+            .build()
+            .unwrap();
+        assert_eq!(
+            slugify_with_options_public(&opts, "ID7 Blue 12345"),
+            "blue-12345"
+        );
 
-fn decode_named_entities(s: &str) -> String {
-    CHAR_ENTITY_PATTERN
-        .replace_all(s, |caps: &regex::Captures| {
-            let full = caps.get(0).map(|m| m.as_str()).unwrap_or("");
-            decode_html_entities(full).to_string()
-        })
-        .to_string()
-}
+        let res = SlugifyOptions::builder()
+            .strip_numeric_ids(true)
+            .numeric_id_pattern(Some("(?"))
+            .build();
+        assert!(matches!(res, Err(SlugifyError::InvalidRegex(_))));
+    }
 
-fn decode_numeric_refs(s: &str, decimal: bool, hexadecimal: bool) -> String {
-    let mut out = s.to_string();
-    if decimal {
-        out = DECIMAL_PATTERN
-            .replace_all(&out, |caps: &regex::Captures| {
-                caps.get(1)
-                    .and_then(|m| m.as_str().parse::<u32>().ok())
-                    .and_then(std::char::from_u32)
-                    .map(|c| c.to_string())
-                    .unwrap_or_default()
-            })
-            .to_string();
+    #[test]
+    fn test_separator_in_text() {
+        let build = |separator: &str| {
+            SlugifyOptions::builder()
+                .separator(separator)
+                .lowercase(false)
+                .stopwords(vec!["b"])
+                .build()
+                .unwrap()
+        };
+        let opts = build("ZZ");
+        assert_eq!(
+            slugify_with_options_public(&opts, "hello ZZ world"),
+            "helloZZworld"
+        );
+        assert_eq!(slugify_with_options_public(&opts, "ZZ hello ZZ"), "hello");
+        assert_eq!(slugify_with_options_public(&opts, "a ZZZZ c"), "aZZc");
+        // letters of a word-character separator inside words are kept
+        assert_eq!(slugify_with_options_public(&opts, "ZZhelloZZ"), "ZZhelloZZ");
+        let opts = build("zz");
+        assert_eq!(slugify_with_options_public(&opts, "a zz b"), "a");
+        assert_eq!(slugify_with_options_public(&opts, "azzb"), "azzb");
+        let opts = build("x");
+        assert_eq!(slugify_with_options_public(&opts, "xbox one"), "xboxxone");
+        assert_eq!(slugify_with_options_public(&opts, "x box x one"), "boxxone");
+        let opts = build("__");
+        assert_eq!(
+            slugify_with_options_public(&opts, "__a_d____c__"),
+            "a__d__c"
+        );
     }
-    if hexadecimal {
-        out = HEX_PATTERN
-            .replace_all(&out, |caps: &regex::Captures| {
-                u32::from_str_radix(&caps[1], 16)
-                    .ok()
-                    .and_then(std::char::from_u32)
-                    .map(|c| c.to_string())
-                    .unwrap_or_default()
-            })
-            .to_string();
+
+    #[test]
+    fn test_separator_scope_inserted_keeps_source_hyphens() {
+        let build = |scope| {
+            SlugifyOptions::builder()
+                .separator("_")
+                .regex_pattern(Some(r"[^-a-z0-9_]+"))
+                .separator_scope(scope)
+                .build()
+                .unwrap()
+        };
+        let txt = "___This is a well-known test___";
+        // with `All`, underscores in the text are separators and get trimmed
+        assert_eq!(
+            slugify_with_options_public(&build(SeparatorScope::All), txt),
+            "this_is_a_well_known_test"
+        );
+        assert_eq!(
+            slugify_with_options_public(&build(SeparatorScope::Inserted), txt),
+            "___this_is_a_well-known_test___"
+        );
+        // quotes and dangling hyphens are still treated as separators
+        assert_eq!(
+            slugify_with_options_public(&build(SeparatorScope::Inserted), "C'est - déjà"),
+            "c_est_deja"
+        );
+        let opts = SlugifyOptions::builder()
+            .separator(".")
+            .separator_scope(SeparatorScope::Inserted)
+            .max_length(12)
+            .word_boundary(true)
+            .build()
+            .unwrap();
+        assert_eq!(
+            slugify_with_options_public(&opts, "Self-hosted git server"),
+            "self-hosted"
+        );
     }
-    out
-}
 
-fn apply_pattern_replacement(s: &str, opts: &SlugifyOptions) -> String {
-    if let Some(ref rx) = opts.regex_pattern {
-        rx.replace_all(s, DEFAULT_SEPARATOR).to_string()
-    } else if opts.allow_unicode {
-        DISALLOWED_UNICODE_CHARS_PATTERN
-            .replace_all(s, DEFAULT_SEPARATOR)
-            .to_string()
-    } else {
-        DISALLOWED_CHARS_PATTERN
-            .replace_all(s, DEFAULT_SEPARATOR)
-            .to_string()
+    #[test]
+    fn test_digit_boundaries() {
+        let slug = |placement, txt| {
+            let opts = SlugifyOptions::builder()
+                .digit_boundaries(placement)
+                .build()
+                .unwrap();
+            slugify_with_options_public(&opts, txt)
+        };
+        let txt = "GPT4 on a 4K mp3 player";
+        assert_eq!(slug(DigitBoundaries::Keep, txt), "gpt4-on-a-4k-mp3-player");
+        assert_eq!(
+            slug(DigitBoundaries::BeforeDigits, txt),
+            "gpt-4-on-a-4k-mp-3-player"
+        );
+        assert_eq!(
+            slug(DigitBoundaries::AfterDigits, txt),
+            "gpt4-on-a-4-k-mp3-player"
+        );
+        assert_eq!(slug(DigitBoundaries::Both, "mp3player"), "mp-3-player");
     }
-}
 
-fn remove_stopwords(s: &str, stopwords: &[String], lowercase: bool) -> String {
-    if stopwords.is_empty() {
-        return s.to_string();
+    #[test]
+    fn test_slugify_default_uses_builder_defaults() {
+        assert_eq!(slugify_default("Hello World!"), "hello-world");
+        assert_eq!(slugify_default("foo &amp; bar &#381;"), "foo-bar-z");
+        assert_eq!(slugify_default("I ♥ 🚀"), "i-hearts-rocket");
     }
-    if lowercase {
-        let lower_stop: Vec<String> = stopwords.iter().map(|w| w.to_lowercase()).collect();
-        s.split(DEFAULT_SEPARATOR)
-            .filter(|w| !lower_stop.contains(&w.to_string()))
-            .collect::<Vec<&str>>()
-            .join(DEFAULT_SEPARATOR)
-    } else {
-        s.split(DEFAULT_SEPARATOR)
-            .filter(|w| !stopwords.contains(&w.to_string()))
-            .collect::<Vec<&str>>()
-            .join(DEFAULT_SEPARATOR)
+
+    #[cfg(feature = "emoji_names")]
+    #[test]
+    fn test_transliterate_icons_uses_emoji_names() {
+        assert_eq!(slugify_default("I ♥ 🚀 🦄"), "i-hearts-rocket-unicorn");
+        assert_eq!(
+            slugify_default("Party 🎉 in 🇫🇷"),
+            "party-party-popper-in-flag-france"
+        );
+        assert_eq!(
+            slugify_default("👍🏽 Great"),
+            "thumbs-up-medium-skin-tone-great"
+        );
+        assert_eq!(slugify_default("👨‍👩‍👧 trip"), "family-man-woman-girl-trip");
+        // text symbols keep their transliteration
+        assert_eq!(slugify_default("Acme© 2024"), "acme-c-2024");
+        let opts = SlugifyOptions::builder()
+            .emoji_policy(EmojiPolicy::Remove)
+            .build()
+            .unwrap();
+        assert_eq!(slugify_with_options("Party 🎉", &opts), "party");
     }
-}
 
-fn finalize_separator(s: &str, separator: &str) -> String {
-    if separator != DEFAULT_SEPARATOR {
-        s.replace(DEFAULT_SEPARATOR, separator)
-    } else {
-        s.to_string()
+    #[cfg(not(feature = "emoji_names"))]
+    #[test]
+    fn test_emoji_names_fallback() {
+        // only the overrides are named; other emoji are left to deunicode
+        assert_eq!(crate::special::emoji_name("♥"), Some("hearts"));
+        assert_eq!(crate::special::emoji_name("🚀"), None);
+        assert_eq!(crate::special::emoji_shortcode("🚀"), None);
+        assert_eq!(slugify_default("I ♥ 🚀 🎉"), "i-hearts-rocket-tada");
     }
-}
 
-// Note: Python used lookbehind/lookahead for numbers ("," between digits).
-// Rust's `regex` crate doesn't support lookarounds, so we implement a small
-// helper that removes commas between digits.
-pub fn remove_commas_between_digits(s: &str) -> String {
-    let mut out = String::with_capacity(s.len());
-    let mut chars = s.chars().peekable();
-    let mut prev_is_digit = false;
-
-    while let Some(c) = chars.next() {
-        if c == ',' {
-            let next_is_digit = chars.peek().map(|ch| ch.is_ascii_digit()).unwrap_or(false);
-            if prev_is_digit && next_is_digit {
-                // skip the comma
-            } else {
-                out.push(c);
-                prev_is_digit = false;
-            }
-        } else {
-            prev_is_digit = c.is_ascii_digit();
-            out.push(c);
+    #[test]
+    fn test_emoji_policies() {
+        let build = |policy, allow_unicode, pattern: Option<&str>| {
+            SlugifyOptions::builder()
+                .emoji_policy(policy)
+                .allow_unicode(allow_unicode)
+                .regex_pattern(pattern)
+                .build()
+                .unwrap()
+        };
+        let txt = "I ♥ 🚀 👍";
+        assert_eq!(
+            slugify_with_options(txt, &build(EmojiPolicy::Remove, false, None)),
+            "i"
+        );
+        #[cfg(feature = "emoji_names")]
+        {
+            assert_eq!(
+                slugify_with_options(txt, &build(EmojiPolicy::Transliterate, false, None)),
+                "i-hearts-rocket-thumbs-up"
+            );
+            assert_eq!(
+                slugify_with_options(txt, &build(EmojiPolicy::Shortcode, false, None)),
+                "i-hearts-rocket-1"
+            );
+            assert_eq!(
+                slugify_with_options(
+                    txt,
+                    &build(EmojiPolicy::Shortcode, false, Some(r"[^-a-z0-9:+]+"))
+                ),
+                "i-:hearts:-:rocket:-:+1:"
+            );
         }
+        assert_eq!(
+            slugify_with_options("Déjà 🚀 👨‍👩‍👧!", &build(EmojiPolicy::Keep, true, None)),
+            "déjà-🚀-👨\u{200d}👩\u{200d}👧"
+        );
+        assert_eq!(
+            slugify_with_options("Déjà 🚀", &build(EmojiPolicy::Transliterate, true, None)),
+            "déjà"
+        );
+        #[allow(deprecated)]
+        let legacy = SlugifyOptions::builder()
+            .transliterate_icons(false)
+            .build()
+            .unwrap();
+        assert_eq!(legacy.emoji_policy, EmojiPolicy::Remove);
     }
-    out
-}
 
-/// Helper: normalize and transliterate when `allow_unicode == false`.
-pub fn normalize_and_transliterate(s: &str, allow_unicode: bool) -> String {
-    if allow_unicode {
-        // Normalize to compatibility composed form (similar to NFKC in Python)
-        s.nfkc().collect()
-    } else {
-        // NFKD then ascii transliteration using `deunicode`
-        let decomposed: String = s.nfkd().collect();
-        deunicode(&decomposed)
+    #[test]
+    fn test_grapheme_replacements() {
+        let build = |graphemes| {
+            SlugifyOptions::builder()
+                .replacements([("e", "3"), ("o\u{308}", "o")])
+                .grapheme_replacements(graphemes)
+                .allow_unicode(true)
+                .build()
+                .unwrap()
+        };
+        // NFD input: "é" is "e" + U+0301, "ö" is precomposed. Unicode mode
+        // keeps "é" intact for the post-processing pass.
+        let txt = "Cafe\u{301} e Sch\u{f6}n";
+        assert_eq!(slugify_with_options(txt, &build(false)), "caf3\u{301}-3-sch\u{f6}n");
+        assert_eq!(slugify_with_options(txt, &build(true)), "caf\u{e9}-3-schon");
+        // U+20DD has no precomposed form, so it stays a combining mark
+        let ee = [("ee".to_string(), "x".to_string())];
+        assert_eq!(apply_grapheme_replacements("ee\u{20dd}e", &ee), "ee\u{20dd}e");
+        assert_eq!(apply_grapheme_replacements("eee\u{20dd}", &ee), "xe\u{20dd}");
+        assert_eq!(apply_grapheme_replacements("abc", &[(String::new(), "x".into())]), "abc");
     }
-}
-
-pub fn smart_truncate(
-    input: &str,
-    max_length: usize,
-    word_boundary: bool,
-    separator: &str,
-    save_order: bool,
-) -> String {
-    // strip characters contained in `separator` from both ends (python semantics)
-    let sep_chars: Vec<char> = separator.chars().collect();
-    // Trims any leading or trailing characters from `input` that are present in `sep_chars`,
-    // and assigns the resulting string to `s`.
-    //
-    // # Arguments
-    //
-    // * `input` - The original string to be trimmed.
-    // * `sep_chars` - A collection of separator characters to be removed from both ends of `input`.
-    //
-    // # Returns
-    //
-    // A new `String` with the specified separator characters removed from the start and end.
-    let s = input
-        .trim_matches(|c: char| sep_chars.contains(&c))
-        .to_string();
 
-    if max_length == 0 {
-        return s;
+    #[test]
+    fn test_separator_presets() {
+        let slug = |sep: Separator| {
+            let opts = SlugifyOptions::builder()
+                .separator(sep)
+                .stopwords(["the"])
+                .build()
+                .unwrap();
+            slugify_with_options(" The Rust-Lang  Book! ", &opts)
+        };
+        assert_eq!(slug(Separator::Hyphen), "rust-lang-book");
+        assert_eq!(slug(Separator::Underscore), "rust_lang_book");
+        assert_eq!(slug(Separator::Dot), "rust.lang.book");
+        assert_eq!(slug(Separator::Custom("~".into())), "rust~lang~book");
+        // stopwords, collapsing and trimming happen on words before they
+        // are concatenated
+        assert_eq!(slug(Separator::None), "rustlangbook");
+        assert_eq!(Separator::from(""), Separator::None);
+        assert_eq!(Separator::from("_"), Separator::Underscore);
+        assert_eq!(Separator::from("::"), Separator::Custom("::".into()));
     }
 
-    let char_count = s.chars().count();
-    if char_count < max_length {
-        return s;
+    #[test]
+    fn test_separator_none_truncation() {
+        let build = |max, word_boundary, save_order, trailing| {
+            SlugifyOptions::builder()
+                .separator(Separator::None)
+                .max_length(max)
+                .word_boundary(word_boundary)
+                .save_order(save_order)
+                .preserve_trailing_number(trailing)
+                .build()
+                .unwrap()
+        };
+        let txt = "one two three 2024";
+        // max_length counts word characters only
+        assert_eq!(slugify_with_options(txt, &build(15, false, false, false)), "onetwothree2024");
+        assert_eq!(slugify_with_options(txt, &build(9, false, false, false)), "onetwothr");
+        assert_eq!(slugify_with_options(txt, &build(9, true, true, false)), "onetwo");
+        assert_eq!(slugify_with_options(txt, &build(10, true, false, false)), "onetwo2024");
+        assert_eq!(slugify_with_options(txt, &build(9, true, true, true)), "one2024");
+        assert_eq!(slugify_with_options("extraordinary", &build(5, true, true, false)), "extra");
     }
 
-    if !word_boundary {
-        return first_n_chars(&s, max_length)
-            .trim_matches(|c: char| sep_chars.contains(&c))
-            .to_string();
+    #[test]
+    fn test_symbol_locale() {
+        let slug = |txt, locale| {
+            let opts = SlugifyOptions::builder()
+                .symbol_locale(locale)
+                .build()
+                .unwrap();
+            slugify_with_options(txt, &opts)
+        };
+        let txt = "Q&A: 50% off @ 20€ #1";
+        assert_eq!(slug(txt, None), "q-a-50-off-20eur-1");
+        assert_eq!(
+            slug(txt, Some(Locale::En)),
+            "q-and-a-50-percent-off-at-20-euro-number-1"
+        );
+        assert_eq!(
+            slug(txt, Some(Locale::Fr)),
+            "q-et-a-50-pour-cent-off-arobase-20-euro-numero-1"
+        );
+        assert_eq!(slug("Tom &amp; Jerry", Some(Locale::De)), "tom-und-jerry");
+        assert_eq!(slug("Tom &amp; Jerry", None), "tom-jerry");
     }
 
-    if !s.contains(separator) {
-        return first_n_chars(&s, max_length);
+    #[test]
+    fn test_numeric_cleanup() {
+        let slug = |txt, cleanup| {
+            let opts = SlugifyOptions::builder()
+                .numeric_cleanup(cleanup)
+                // "C++" below is punctuation, not a protected term
+                .builtin_protected_terms(false)
+                .build()
+                .unwrap();
+            slugify_with_options(txt, &opts)
+        };
+        let txt = "1,234.5 km, 1.000 m, 10 000 l, 1'000 g";
+        assert_eq!(
+            slug(txt, NumericCleanup::default()),
+            "1234-5-km-1-000-m-10-000-l-1-000-g"
+        );
+        assert_eq!(
+            slug(txt, NumericCleanup::international('.')),
+            "1234-5-km-1-000-m-10000-l-1000-g"
+        );
+        assert_eq!(slug("1.234,5 €", NumericCleanup::international(',')), "1234-5-eur");
+        let plus = NumericCleanup {
+            keep_leading_plus: true,
+            ..NumericCleanup::default()
+        };
+        assert_eq!(slug("Call +33 1 23, C++ 2+2", plus), "call-plus-33-1-23-c-2-2");
+        assert_eq!(slug("&#49;,&#50;", NumericCleanup::default()), "12");
     }
 
-    let mut truncated = String::new();
-    for word in s.split(separator) {
-        if word.is_empty() {
-            continue;
-        }
-        let next_len = truncated.chars().count() + word.chars().count();
-        if next_len < max_length {
-            truncated.push_str(word);
-            truncated.push_str(separator);
-        } else if next_len == max_length {
-            truncated.push_str(word);
-            break;
-        } else if save_order {
-            break;
-        } else {
-            // when not save_order, continue to next word (Python picks different word order)
-            // Python's original logic implicitly continues the loop; so we continue here.
-            continue;
+    #[test]
+    fn test_custom_transliterator() {
+        #[derive(Debug)]
+        struct Upper;
+        impl Transliterator for Upper {
+            fn transliterate(&self, s: &str) -> String {
+                deunicode(s).replace('u', "U")
+            }
         }
+        let opts = SlugifyOptions::builder()
+            .transliterator(Upper)
+            .lowercase(false)
+            .word_translations([("über", "over")])
+            .build()
+            .unwrap();
+        // dictionary keys are normalized with the same backend
+        assert_eq!(slugify_with_options("Über Mut", &opts), "over-MUt");
     }
 
-    if truncated.is_empty() {
-        truncated = first_n_chars(&s, max_length);
+    #[test]
+    fn test_rtl_word_order_and_bidi_marks() {
+        let build = |order| {
+            SlugifyOptions::builder()
+                .allow_unicode(true)
+                .rtl_word_order(order)
+                .build()
+                .unwrap()
+        };
+        let (logical, visual) = (build(RtlWordOrder::Logical), build(RtlWordOrder::Visual));
+        // directional marks never split a word
+        assert_eq!(slugify_with_options("של\u{200F}ום עולם", &logical), "שלום-עולם");
+        assert_eq!(slugify_with_options("\u{2067}Hello\u{2069} world", &logical), "hello-world");
+        assert_eq!(slugify_with_options("שלום עולם", &visual), "עולם-שלום");
+        // LTR title with an RTL run: only the run is reversed
+        assert_eq!(
+            slugify_with_options("Intro to שלום עולם in Python 3", &visual),
+            "intro-to-עולם-שלום-in-python-3"
+        );
+        // RTL title with an LTR run: the line is reversed, the run is not
+        assert_eq!(
+            slugify_with_options("מדריך Rust Web 2024 חדש", &visual),
+            "חדש-rust-web-2024-מדריך"
+        );
+        assert_eq!(
+            slugify_with_options("مرحبا بالعالم", &visual),
+            "بالعالم-مرحبا"
+        );
+        assert_eq!(slugify_with_options("Hello World", &visual), "hello-world");
     }
 
-    truncated
-        .trim_matches(|c: char| sep_chars.contains(&c))
-        .to_string()
-}
-
-/// Public API that accepts an options struct. Prefer this for programmatic use
-/// to avoid long argument lists and improve readability.
-pub fn slugify_with_options_public(opts: &SlugifyOptions, text: &str) -> String {
-    slugify_with_options(text, opts)
-}
-
-fn first_n_chars(s: &str, n: usize) -> String {
-    // Use grapheme clusters so we don't split combined characters or emoji.
-    s.graphemes(true).take(n).collect()
-}
+    #[test]
+    fn test_custom_translations() {
+        let opts = SlugifyOptions::builder()
+            .custom_translations([("ß", "ss"), ("ø", "oe"), ("Ø", "Oe")])
+            .build()
+            .unwrap();
+        assert_eq!(slugify_with_options("Straße Øresund", &opts), "strasse-oeresund");
+        assert_eq!(slugify_with_options("&oslash;l", &opts), "oel");
+        assert_eq!(slugify_default("Øresund"), "oresund");
+    }
 
-#[cfg(test)]
-#[allow(clippy::expect_used)]
-#[allow(clippy::unwrap_used)]
-mod tests {
-    use super::*;
+    #[test]
+    fn test_edge_trim() {
+        let build = |set: TrimSet, scope| {
+            SlugifyOptions::builder()
+                .regex_pattern(Some(r"[^-a-z0-9_]+"))
+                .edge_trim(set)
+                .separator_scope(scope)
+                .separator("+")
+                .build()
+                .unwrap()
+        };
+        let txt = "___This is a test___";
+        for scope in [SeparatorScope::All, SeparatorScope::Inserted] {
+            assert_eq!(
+                slugify_with_options(txt, &build(TrimSet::Hyphen, scope)),
+                "___this+is+a+test___"
+            );
+            assert_eq!(
+                slugify_with_options(txt, &build(TrimSet::Chars(vec!['_']), scope)),
+                "this+is+a+test"
+            );
+        }
+        assert_eq!(
+            slugify_with_options(
+                "_-_ Hello _-_",
+                &build(TrimSet::NonAlphanumeric, SeparatorScope::All)
+            ),
+            "hello"
+        );
+        assert!(TrimSet::Chars(vec!['.']).matches('-'));
+    }
 
     #[test]
-    fn test_remove_commas_between_digits() {
-        assert_eq!(remove_commas_between_digits("1,234"), "1234");
-        assert_eq!(remove_commas_between_digits("1,234,567"), "1234567");
-        assert_eq!(remove_commas_between_digits("1,234 apples"), "1234 apples");
+    fn test_source_separators() {
+        let opts = SlugifyOptions::builder()
+            .source_separator('|', SourceSeparator::Word("or".to_string()))
+            .source_separator('/', SourceSeparator::Remove)
+            .source_separator('—', SourceSeparator::Separator)
+            .separator_scope(SeparatorScope::Inserted)
+            .separator("_")
+            .build()
+            .unwrap();
         assert_eq!(
-            remove_commas_between_digits("apples, 1,234"),
-            "apples, 1234"
+            slugify_with_options("Rock|Pop — AC/DC", &opts),
+            "rock_or_pop_acdc"
         );
+        let opts = SlugifyOptions::builder()
+            .source_separator('/', SourceSeparator::Remove)
+            .source_separator('/', SourceSeparator::Word("and".to_string()))
+            .build()
+            .unwrap();
+        assert_eq!(opts.source_separators.len(), 1);
         assert_eq!(
-            remove_commas_between_digits("no commas here"),
-            "no commas here"
+            slugify_with_options("Salt/Pepper", &opts),
+            "salt-and-pepper"
         );
+        // unmapped characters keep the default behavior
+        assert_eq!(slugify_with_options("Rock|Pop", &opts), "rock-pop");
+    }
+
+    #[test]
+    fn test_cjk_punctuation() {
+        for allow_unicode in [false, true] {
+            let opts = SlugifyOptions::builder()
+                .allow_unicode(allow_unicode)
+                .build()
+                .unwrap();
+            for p in crate::special::CJK_PUNCTUATION {
+                let text = format!("ab{}cd", p);
+                assert_eq!(slugify_with_options(&text, &opts), "ab-cd", "{:?}", text);
+            }
+        }
+        let opts = SlugifyOptions::builder()
+            .allow_unicode(true)
+            .build()
+            .unwrap();
         assert_eq!(
-            remove_commas_between_digits(",leading comma"),
-            ",leading comma"
+            slugify_with_options("東京（とうきょう）、大阪。", &opts),
+            "東京-とうきょう-大阪"
+        );
+        assert_eq!(
+            slugify_with_options("「吾輩は猫である」を読む！", &opts),
+            "吾輩は猫である-を読む"
         );
+        // fullwidth commas in numbers are left to `NumericCleanup`
+        assert_eq!(slugify_with_options("１，０００円", &opts), "1000円");
+    }
+
+    #[test]
+    fn test_cjk_quotes_remove() {
+        let opts = SlugifyOptions::builder()
+            .allow_unicode(true)
+            .cjk_quotes(CjkQuotes::Remove)
+            .build()
+            .unwrap();
         assert_eq!(
-            remove_commas_between_digits("trailing comma,"),
-            "trailing comma,"
+            slugify_with_options("「吾輩は猫である」を読む！", &opts),
+            "吾輩は猫であるを読む"
         );
         assert_eq!(
-            remove_commas_between_digits("multiple,,commas"),
-            "multiple,,commas"
+            slugify_with_options("東京『タワー』、夜", &opts),
+            "東京タワー-夜"
         );
+        let opts = SlugifyOptions::builder()
+            .cjk_quotes(CjkQuotes::Remove)
+            .build()
+            .unwrap();
+        assert_eq!(slugify_with_options("abc「def」ghi", &opts), "abcdefghi");
+        // `source_separator` entries win
+        let opts = SlugifyOptions::builder()
+            .cjk_quotes(CjkQuotes::Remove)
+            .source_separator('「', SourceSeparator::Separator)
+            .build()
+            .unwrap();
+        assert_eq!(slugify_with_options("abc「def」ghi", &opts), "abc-defghi");
     }
 
     #[test]
-    fn test_smart_truncate() {
-        let start = std::time::Instant::now();
-        let input = "This is a simple test string for truncation.";
-        assert_eq!(smart_truncate(input, 10, false, " ", true), "This is a");
-        assert_eq!(smart_truncate(input, 10, true, " ", true), "This is a");
+    fn test_preserve_trailing_number() {
+        let build = |keep: bool| {
+            SlugifyOptions::builder()
+                .max_length(18)
+                .word_boundary(true)
+                .save_order(true)
+                .preserve_trailing_number(keep)
+                .build()
+                .unwrap()
+        };
+        let title = "Annual Report of the Board 2024";
         assert_eq!(
-            smart_truncate(input, 0, true, " ", true),
-            "This is a simple test string for truncation."
+            slugify_with_options(title, &build(false)),
+            "annual-report-of"
         );
         assert_eq!(
-            smart_truncate(input, 50, true, " ", true),
-            "This is a simple test string for truncation."
+            slugify_with_options(title, &build(true)),
+            "annual-report-2024"
         );
+        // short enough or not numeric: unchanged behavior
         assert_eq!(
-            smart_truncate(input, 15, true, " ", false),
-            "This is a test"
+            slugify_with_options("Report 2024", &build(true)),
+            "report-2024"
         );
-        let elapsed = start.elapsed();
-        println!("[TIMER] test_smart_truncate: {} ms", elapsed.as_millis());
+        assert_eq!(
+            slugify_with_options("Annual Report of the Board", &build(true)),
+            "annual-report-of"
+        );
+        // the number alone does not fit: plain truncation
+        let opts = SlugifyOptions::builder()
+            .max_length(4)
+            .preserve_trailing_number(true)
+            .build()
+            .unwrap();
+        assert_eq!(slugify_with_options("Pi 31415926", &opts), "pi-3");
     }
 
     #[test]
-    fn test_smart_truncate_no_max_length_python_case() {
-        let start = std::time::Instant::now();
-        let txt = "1,000 reasons you are #1";
-        // default max_length == 0 should return the original string
-        assert_eq!(smart_truncate(txt, 0, false, " ", false), txt);
-        let elapsed = start.elapsed();
-        println!(
-            "[TIMER] test_smart_truncate_no_max_length_python_case: {} ms",
-            elapsed.as_millis()
-        );
+    fn test_separator_str_and_char_are_validated() {
+        let opts = SlugifyOptions::builder()
+            .separator_str("--")
+            .build()
+            .unwrap();
+        assert_eq!(slugify_with_options("Hello World", &opts), "hello--world");
+        let opts = SlugifyOptions::builder()
+            .allow_unicode(true)
+            .separator_char('·')
+            .build()
+            .unwrap();
+        assert_eq!(slugify_with_options("Hello World", &opts), "hello·world");
+        for bad in ["", " ", "a", "-\t", "·"] {
+            let res = SlugifyOptions::builder().separator_str(bad).build();
+            assert!(
+                matches!(res, Err(SlugifyError::InvalidSeparator(_))),
+                "{:?}",
+                bad
+            );
+        }
+        // the plain setter stays unvalidated for backwards compatibility
+        assert!(SlugifyOptions::builder().separator(" ").build().is_ok());
     }
 
     #[test]
-    fn test_smart_truncate_no_separator_python_case() {
-        let start = std::time::Instant::now();
-        let txt = "1,000 reasons you are #1";
-        // separator '_' is not present in the input; with large max_length, should return original
-        assert_eq!(smart_truncate(txt, 100, false, "_", false), txt);
-        let elapsed = start.elapsed();
-        println!(
-            "[TIMER] test_smart_truncate_no_separator_python_case: {} ms",
-            elapsed.as_millis()
+    fn test_slugify_extension_trait() {
+        let opts = SlugifyOptions::builder().separator("_").build().unwrap();
+        assert_eq!("Hello World".slugify(), "hello-world");
+        assert_eq!(
+            String::from("Hello World").slugify_with(&opts),
+            "hello_world"
         );
+        let cow: Cow<str> = Cow::Owned("Déjà vu".to_string());
+        assert_eq!(cow.slugify(), "deja-vu");
     }
 
     #[test]
@@ -726,6 +4625,131 @@ mod tests {
         assert_eq!(first_n_chars("hi", 10), "hi");
     }
 
+    #[test]
+    fn test_length_unit_prefix() {
+        let s = "a\u{0301}bc";
+        // chars keep whole clusters, like the historical cut
+        assert_eq!(LengthUnit::Chars.prefix(s, 1), "a\u{0301}");
+        assert_eq!(LengthUnit::Graphemes.prefix(s, 2), "a\u{0301}b");
+        assert_eq!(LengthUnit::Bytes.prefix("été", 4), "ét");
+        assert_eq!(LengthUnit::Bytes.prefix("été", 2), "é");
+        assert_eq!(smart_truncate(s, 1, false, "-", false), "a\u{0301}");
+        let family = "👨‍👩‍👧‍👦";
+        assert_eq!(
+            smart_truncate(&format!("{family}abc"), 1, false, "-", false),
+            family
+        );
+        assert_eq!(
+            smart_truncate("e\u{301}te\u{301}-x", 3, false, "-", false),
+            "e\u{301}te\u{301}"
+        );
+    }
+
+    #[test]
+    fn test_truncation_strategy() {
+        let build = |strategy, max_length| {
+            SlugifyOptions::builder()
+                .truncation_strategy(strategy)
+                .max_length(max_length)
+                .build()
+                .unwrap()
+        };
+        let txt = "one two three four";
+        let cases = [
+            (TruncationStrategy::Cut, "one-two-th"),
+            (TruncationStrategy::WordBoundary, "one-two"),
+            (TruncationStrategy::WordBoundaryKeepOrder, "one-two"),
+        ];
+        for (strategy, expected) in cases {
+            assert_eq!(
+                slugify_with_options_public(&build(strategy, 10), txt),
+                expected
+            );
+        }
+        let opts = build(TruncationStrategy::WordBoundary, 12);
+        assert_eq!(
+            slugify_with_options_public(&opts, "one two three four"),
+            "one-two-four"
+        );
+        let opts = build(TruncationStrategy::WordBoundaryKeepOrder, 12);
+        assert_eq!(
+            slugify_with_options_public(&opts, "one two three four"),
+            "one-two"
+        );
+
+        let opts = build(TruncationStrategy::CutWithHash, 20);
+        let a =
+            slugify_with_options_public(&opts, "A very long title shared by two posts, part one");
+        let b =
+            slugify_with_options_public(&opts, "A very long title shared by two posts, part two");
+        assert_ne!(a, b);
+        assert_eq!(a.len(), 20);
+        assert_eq!(&a[..11], "a-very-long");
+        assert_eq!(&a[..12], &b[..12]);
+        assert!(
+            a[12..]
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit())
+        );
+        // the hash only depends on the words, and short slugs are left alone
+        let underscored = SlugifyOptions::builder()
+            .truncation_strategy(TruncationStrategy::CutWithHash)
+            .max_length(20)
+            .separator("_")
+            .build()
+            .unwrap();
+        let c = slugify_with_options_public(
+            &underscored,
+            "A very long title shared by two posts, part one",
+        );
+        assert_eq!(c.replace('_', "-"), a);
+        assert_eq!(
+            slugify_with_options_public(&opts, "Short title"),
+            "short-title"
+        );
+        // no room for anything but the hash
+        let opts = build(TruncationStrategy::CutWithHash, 6);
+        assert_eq!(slugify_with_options_public(&opts, txt).len(), 6);
+    }
+
+    #[test]
+    fn test_length_unit() {
+        let build = |unit, max_length| {
+            SlugifyOptions::builder()
+                .allow_unicode(true)
+                .length_unit(unit)
+                .max_length(max_length)
+                .build()
+                .unwrap()
+        };
+        let txt = "Ελληνικά και русский";
+        // 8 + 1 + 3 chars, 16 + 1 + 6 bytes
+        let opts = build(LengthUnit::Chars, 12);
+        assert_eq!(slugify_with_options_public(&opts, txt), "ελληνικά-και");
+        let opts = build(LengthUnit::Bytes, 12);
+        assert_eq!(slugify_with_options_public(&opts, txt), "ελληνι");
+        let opts = build(LengthUnit::Bytes, 23);
+        let slug = slugify_with_options_public(&opts, txt);
+        assert_eq!(slug, "ελληνικά-και");
+        assert_eq!(projected_length(txt, &opts), 23);
+        // a word that does not fit is cut on a char boundary
+        let opts = build(LengthUnit::Bytes, 4);
+        assert_eq!(slugify_with_options_public(&opts, "été"), "ét");
+        // graphemes count an emoji with a skin tone modifier once
+        let opts = SlugifyOptions::builder()
+            .allow_unicode(true)
+            .emoji_policy(EmojiPolicy::Keep)
+            .length_unit(LengthUnit::Graphemes)
+            .max_length(3)
+            .build()
+            .unwrap();
+        assert_eq!(slugify_with_options_public(&opts, "👍🏽👍🏽👍🏽👍🏽"), "👍🏽👍🏽👍🏽");
+        assert_eq!(
+            smart_truncate_by("ab-cd-ef", 5, LengthUnit::Bytes, true, "-", true),
+            "ab-cd"
+        );
+    }
+
     #[test]
     fn test_from_args_invalid_regex() {
         let res = SlugifyOptions::from_args(
@@ -753,10 +4777,10 @@ mod tests {
             .lowercase(true)
             .replacements(Vec::<(&str, &str)>::new())
             .allow_unicode(false)
-            .transliterate_icons(false)
+            .emoji_policy(EmojiPolicy::Remove)
             .build()
             .unwrap();
-        let out = apply_pattern_replacement("hello -- world!!!", &opts);
+        let out = apply_pattern_replacement("hello -- world!!!", &opts, DEFAULT_SEPARATOR);
         assert!(out.contains("hello"));
 
         // if regex is None and allow_unicode true, use unicode-disallowed pattern
@@ -773,10 +4797,10 @@ mod tests {
             .lowercase(true)
             .replacements(Vec::<(&str, &str)>::new())
             .allow_unicode(true)
-            .transliterate_icons(false)
+            .emoji_policy(EmojiPolicy::Remove)
             .build()
             .unwrap();
-        let out2 = apply_pattern_replacement("hello 🦄 world!!!", &opts2);
+        let out2 = apply_pattern_replacement("hello 🦄 world!!!", &opts2, DEFAULT_SEPARATOR);
     // since allow_unicode=true, the emoji is not replaced by the ascii pattern; ensure non-empty
     assert!(!out2.is_empty());
     }
@@ -808,7 +4832,7 @@ mod tests {
             .lowercase(true)
             .replacements(Vec::<(&str, &str)>::new())
             .allow_unicode(false)
-            .transliterate_icons(false)
+            .emoji_policy(EmojiPolicy::Remove)
             .build()
             .unwrap();
         slugify_with_options_public(&opts, text)
@@ -828,7 +4852,7 @@ mod tests {
             .lowercase(true)
             .replacements(Vec::<(&str, &str)>::new())
             .allow_unicode(true)
-            .transliterate_icons(false)
+            .emoji_policy(EmojiPolicy::Remove)
             .build()
             .unwrap();
         slugify_with_options_public(&opts, text)
@@ -880,7 +4904,7 @@ mod tests {
                     .lowercase(true)
                     .replacements(Vec::<(&str, &str)>::new())
                     .allow_unicode(false)
-                    .transliterate_icons(false)
+                    .emoji_policy(EmojiPolicy::Remove)
                     .build()
                     .unwrap();
                 slugify_with_options_public(&opts, txt)
@@ -926,7 +4950,7 @@ mod tests {
             .lowercase(true)
             .replacements(Vec::<(&str, &str)>::new())
             .allow_unicode(false)
-            .transliterate_icons(false)
+            .emoji_policy(EmojiPolicy::Remove)
             .build()
             .unwrap();
         let r = s_args_with_opts(txt, opts);
@@ -944,13 +4968,261 @@ mod tests {
             .lowercase(true)
             .replacements(Vec::<(&str, &str)>::new())
             .allow_unicode(false)
-            .transliterate_icons(false)
+            .emoji_policy(EmojiPolicy::Remove)
             .build()
             .unwrap();
         let r2 = s_args_with_opts(txt, opts2);
         assert_eq!(r2, "jajaZZZZZZlolZZZZZZmememeooZZZZZZa");
     }
 
+    #[test]
+    fn test_slug_display() {
+        let titles = [
+            "Hello, World!",
+            "  Ünïcode   déjà vu  ",
+            "!!!",
+            "parseHTMLDocument 2",
+        ];
+        let builds: Vec<SlugifyOptions> = vec![
+            SlugifyOptions::builder().build().unwrap(),
+            SlugifyOptions::builder()
+                .separator("__")
+                .max_length(8)
+                .build()
+                .unwrap(),
+            SlugifyOptions::builder()
+                .case_style(CaseStyle::Pascal)
+                .build()
+                .unwrap(),
+            SlugifyOptions::builder()
+                .identifier(IdentifierStyle::python())
+                .build()
+                .unwrap(),
+            SlugifyOptions::builder()
+                .compat(Compat::GitHub)
+                .build()
+                .unwrap(),
+        ];
+        for opts in &builds {
+            for title in titles {
+                assert_eq!(
+                    SlugDisplay::new(title, opts).to_string(),
+                    slugify_with_options_public(opts, title)
+                );
+            }
+        }
+        let opts = &builds[0];
+        assert_eq!(
+            format!("/posts/{}/", SlugDisplay::new("First Post", opts)),
+            "/posts/first-post/"
+        );
+    }
+
+    #[test]
+    fn test_projected_length() {
+        let titles = [
+            "Hello, World!",
+            "  Ünïcode   déjà vu  ",
+            "!!!",
+            "2nd class 2024",
+            "a b c d",
+        ];
+        let builds: Vec<SlugifyOptions> = vec![
+            SlugifyOptions::builder().build().unwrap(),
+            SlugifyOptions::builder()
+                .separator("ZZZ")
+                .max_length(10)
+                .build()
+                .unwrap(),
+            SlugifyOptions::builder()
+                .separator("::")
+                .separator_scope(SeparatorScope::Inserted)
+                .build()
+                .unwrap(),
+            SlugifyOptions::builder().separator("").build().unwrap(),
+            SlugifyOptions::builder()
+                .identifier(IdentifierStyle::python())
+                .build()
+                .unwrap(),
+        ];
+        for opts in &builds {
+            for title in titles {
+                assert_eq!(
+                    projected_length(title, opts),
+                    slugify_with_options_public(opts, title).chars().count(),
+                    "{}",
+                    title
+                );
+            }
+        }
+        assert_eq!(projected_length("a b c", &builds[1]), 9);
+    }
+
+    #[test]
+    fn test_budget() {
+        let build = |budget| SlugifyOptions::builder().budget(budget).build().unwrap();
+        let title = "Hello World";
+        let opts = build(Budget::Steps(1_000));
+        assert_eq!(
+            try_slugify_with_options(&opts, title).unwrap(),
+            "hello-world"
+        );
+        let long = "word ".repeat(1_000);
+        assert!(matches!(
+            try_slugify_with_options(&opts, &long),
+            Err(SlugifyError::BudgetExceeded)
+        ));
+        // the infallible functions ignore the budget
+        assert_eq!(slugify_with_options(&long, &opts).len(), 4_999);
+
+        // ten stages of 11 bytes; a custom regex costs 4 per byte
+        assert!(try_slugify_with_options(&build(Budget::Steps(120)), title).is_ok());
+        assert!(try_slugify_with_options(&build(Budget::Steps(100)), title).is_err());
+        let regex = SlugifyOptions::builder()
+            .regex_pattern(Some(r"[^a-z0-9]+"))
+            .budget(Budget::Steps(120))
+            .build()
+            .unwrap();
+        assert!(matches!(
+            try_slugify_with_options(&regex, "Hello World"),
+            Err(SlugifyError::BudgetExceeded)
+        ));
+
+        let opts = build(Budget::Time(Duration::from_secs(60)));
+        assert_eq!(
+            try_slugify_with_options(&opts, title).unwrap(),
+            "hello-world"
+        );
+        let opts = build(Budget::Time(Duration::ZERO));
+        assert!(matches!(
+            try_slugify_with_options(&opts, &long),
+            Err(SlugifyError::BudgetExceeded)
+        ));
+        // targets without a clock reject a time budget instead of panicking
+        let res = SlugifyOptions::builder()
+            .budget(Budget::Time(Duration::from_secs(1)))
+            .build();
+        assert_eq!(res.is_ok(), HAS_CLOCK);
+    }
+
+    #[test]
+    fn test_strict() {
+        let strict = || SlugifyOptions::builder().strict(true);
+        let opts = strict().emoji_policy(EmojiPolicy::Remove).build().unwrap();
+        assert!(matches!(
+            try_slugify("🦄 🚀", &opts),
+            Err(SlugifyError::EmptyResult)
+        ));
+        assert!(matches!(
+            try_slugify("", &opts),
+            Err(SlugifyError::EmptyResult)
+        ));
+        // only `try_slugify` is strict
+        assert_eq!(slugify_with_options_public(&opts, "🦄 🚀"), "");
+        assert_eq!(try_slugify("🦄 and 🚀", &opts).unwrap(), "and");
+
+        let opts = strict()
+            .emoji_policy(EmojiPolicy::Remove)
+            .max_dropped_fraction(0.5)
+            .build()
+            .unwrap();
+        assert!(matches!(
+            try_slugify("日本 🦄🦄🦄", &opts),
+            Err(SlugifyError::UnmappableInput {
+                dropped: 3,
+                total: 5
+            })
+        ));
+        assert_eq!(try_slugify("日本 🦄", &opts).unwrap(), "ri-ben");
+        // transliterated characters are not dropped
+        assert_eq!(try_slugify("日本語", &opts).unwrap(), "ri-ben-yu");
+        assert_eq!(
+            SlugifyOptions::builder()
+                .max_dropped_fraction(7.0)
+                .build()
+                .unwrap()
+                .max_dropped_fraction,
+            1.0
+        );
+    }
+
+    #[test]
+    fn test_try_slugify_default() {
+        assert_eq!(
+            try_slugify_default("C'est déjà l'été!").unwrap(),
+            slugify_default("C'est déjà l'été!")
+        );
+    }
+
+    #[test]
+    fn test_try_slugify() {
+        let opts = SlugifyOptions::builder().separator("_").build().unwrap();
+        assert_eq!(try_slugify("Hello World", &opts).unwrap(), "hello_world");
+        assert!(matches!(
+            SlugifyOptions::builder().regex_pattern(Some("[")).build(),
+            Err(SlugifyError::InvalidRegex(_))
+        ));
+        // the deprecated wrapper drops the invalid pattern instead
+        #[allow(deprecated)]
+        let slug = slugify(
+            "Hello World",
+            true,
+            true,
+            true,
+            0,
+            false,
+            "-",
+            false,
+            &[],
+            Some("["),
+            true,
+            &[],
+            false,
+        );
+        assert_eq!(slug, "hello-world");
+    }
+
+    #[test]
+    fn test_strict_max_length() {
+        let txt = "jaja---lol-méméméoo--a";
+        let strict = |sep: &str, max_length: usize, word_boundary: bool, save_order: bool| {
+            let opts = SlugifyOptions::builder()
+                .separator(sep)
+                .max_length(max_length)
+                .word_boundary(word_boundary)
+                .save_order(save_order)
+                .strict_max_length(true)
+                .build()
+                .unwrap();
+            slugify_with_options_public(&opts, txt)
+        };
+        assert_eq!(strict("ZZZZZZ", 20, true, false), "jajaZZZZZZlolZZZZZZa");
+        assert_eq!(strict("ZZZZZZ", 20, true, true), "jajaZZZZZZlol");
+        assert_eq!(strict("ZZZZZZ", 15, false, true), "jajaZZZZZZlol");
+        assert_eq!(strict("__", 9, false, true), "jaja__lol");
+        assert_eq!(strict("__", 3, true, true), "jaj");
+        // single-character separators already count exactly
+        assert_eq!(strict("_", 8, true, true), "jaja_lol");
+        for (sep, max_length) in [("ZZZZZZ", 20), ("__", 9), ("::", 100)] {
+            for word_boundary in [true, false] {
+                let slug = strict(sep, max_length, word_boundary, true);
+                assert!(slug.chars().count() <= max_length, "{}", slug);
+            }
+        }
+
+        let opts = SlugifyOptions::builder()
+            .separator("__")
+            .max_length(12)
+            .preserve_trailing_number(true)
+            .strict_max_length(true)
+            .build()
+            .unwrap();
+        assert_eq!(
+            slugify_with_options_public(&opts, "annual report 2024"),
+            "annual__2024"
+        );
+    }
+
 
     #[test]
     fn test_slugify_save_order() {
@@ -968,7 +5240,7 @@ mod tests {
             .lowercase(true)
             .replacements(Vec::<(&str, &str)>::new())
             .allow_unicode(false)
-            .transliterate_icons(false)
+            .emoji_policy(EmojiPolicy::Remove)
             .build()
             .unwrap();
         let opts_b = SlugifyOptions::builder()
@@ -984,7 +5256,7 @@ mod tests {
             .lowercase(true)
             .replacements(Vec::<(&str, &str)>::new())
             .allow_unicode(false)
-            .transliterate_icons(false)
+            .emoji_policy(EmojiPolicy::Remove)
             .build()
             .unwrap();
         let opts_c = SlugifyOptions::builder()
@@ -1000,7 +5272,7 @@ mod tests {
             .lowercase(true)
             .replacements(Vec::<(&str, &str)>::new())
             .allow_unicode(false)
-            .transliterate_icons(false)
+            .emoji_policy(EmojiPolicy::Remove)
             .build()
             .unwrap();
         assert_eq!(s_args_with_opts(txt, opts_a), "one-two-three");
@@ -1024,7 +5296,7 @@ mod tests {
             .lowercase(true)
             .replacements(Vec::<(&str, &str)>::new())
             .allow_unicode(false)
-            .transliterate_icons(false)
+            .emoji_policy(EmojiPolicy::Remove)
             .build()
             .unwrap();
         assert_eq!(s_args_with_opts(txt, opts_stop), "this-has-a");
@@ -1042,7 +5314,7 @@ mod tests {
             .lowercase(false)
             .replacements(Vec::<(&str, &str)>::new())
             .allow_unicode(false)
-            .transliterate_icons(false)
+            .emoji_policy(EmojiPolicy::Remove)
             .build()
             .unwrap();
         assert_eq!(s_args_with_opts(txt2, opts_stop2), "thIs-Has-a-stopword");
@@ -1065,7 +5337,7 @@ mod tests {
             .lowercase(true)
             .replacements(Vec::<(&str, &str)>::new())
             .allow_unicode(false)
-            .transliterate_icons(false)
+            .emoji_policy(EmojiPolicy::Remove)
             .build()
             .unwrap();
         assert_eq!(s_args_with_opts(txt, opts_entities), "foo-amp-bar");
@@ -1084,7 +5356,7 @@ mod tests {
             .lowercase(true)
             .replacements(Vec::<(&str, &str)>::new())
             .allow_unicode(false)
-            .transliterate_icons(false)
+            .emoji_policy(EmojiPolicy::Remove)
             .build()
             .unwrap();
         assert_eq!(s_args_with_opts(dec, opts_dec), "z");
@@ -1101,7 +5373,7 @@ mod tests {
             .lowercase(true)
             .replacements(Vec::<(&str, &str)>::new())
             .allow_unicode(false)
-            .transliterate_icons(false)
+            .emoji_policy(EmojiPolicy::Remove)
             .build()
             .unwrap();
         assert_eq!(s_args_with_opts(dec, opts_dec2), "381");
@@ -1120,7 +5392,7 @@ mod tests {
             .lowercase(true)
             .replacements(Vec::<(&str, &str)>::new())
             .allow_unicode(false)
-            .transliterate_icons(false)
+            .emoji_policy(EmojiPolicy::Remove)
             .build()
             .unwrap();
         assert_eq!(s_args_with_opts(hex, opts_hex), "z");
@@ -1137,7 +5409,7 @@ mod tests {
             .lowercase(true)
             .replacements(Vec::<(&str, &str)>::new())
             .allow_unicode(false)
-            .transliterate_icons(false)
+            .emoji_policy(EmojiPolicy::Remove)
             .build()
             .unwrap();
         assert_eq!(s_args_with_opts(hex, opts_hex2), "x17d");
@@ -1167,7 +5439,7 @@ mod tests {
             .lowercase(true)
             .replacements(Vec::<(&str, &str)>::new())
             .allow_unicode(false)
-            .transliterate_icons(false)
+            .emoji_policy(EmojiPolicy::Remove)
             .build()
             .unwrap();
         let r = s_args_with_opts(txt, opts_pattern);
@@ -1187,13 +5459,145 @@ mod tests {
             .lowercase(true)
             .replacements(vec![("|".to_string(), "or".to_string()), ("%".to_string(), "percent".to_string())])
             .allow_unicode(false)
-            .transliterate_icons(false)
+            .emoji_policy(EmojiPolicy::Remove)
             .build()
             .unwrap();
         let r2 = s_args_with_opts(txt2, opts_repl);
         assert_eq!(r2, "10-or-20-percent");
     }
 
+    #[test]
+    fn test_allowed_chars() {
+        let build = |set: &str| SlugifyOptions::builder().allowed_chars(Some(set)).build();
+        let opts = build("-a-z0-9._").unwrap();
+        let regex = SlugifyOptions::builder()
+            .regex_pattern(Some(r"[^-a-z0-9._]+"))
+            .build()
+            .unwrap();
+        for txt in [
+            "Version 1.2_beta",
+            "Ünïcödé -- config.yaml!",
+            "___This is a test___",
+        ] {
+            assert_eq!(
+                slugify_with_options_public(&opts, txt),
+                slugify_with_options_public(&regex, txt)
+            );
+        }
+        assert_eq!(
+            slugify_with_options_public(&opts, "Version 1.2_beta"),
+            "version-1.2_beta"
+        );
+        // a denylist keeps Unicode letters
+        let opts = SlugifyOptions::builder()
+            .allow_unicode(true)
+            .allowed_chars(Some("^ /?#"))
+            .build()
+            .unwrap();
+        assert_eq!(
+            slugify_with_options_public(&opts, "Déjà vu/part #2"),
+            "déjà-vu-part-2"
+        );
+        assert!(matches!(build("z-a"), Err(SlugifyError::InvalidCharSet(s)) if s == "z-a"));
+        assert_eq!(slug_pattern(&build("a-z").unwrap()), "^.+$");
+    }
+
+    #[test]
+    fn test_char_classifier() {
+        let opts = SlugifyOptions::builder()
+            .char_classifier(|c| match c {
+                'a'..='z' | '0'..='9' | '.' => CharAction::Keep,
+                '_' => CharAction::Drop,
+                '+' => CharAction::Replace("plus".into()),
+                _ => CharAction::Separator,
+            })
+            .builtin_protected_terms(false)
+            .build()
+            .unwrap();
+        assert_eq!(
+            slugify_with_options_public(&opts, "snake_case C++ v1.2!"),
+            "snakecase-cplusplus-v1.2"
+        );
+        // same result as the equivalent regex
+        let regex = SlugifyOptions::builder()
+            .regex_pattern(Some(r"[^a-z0-9.]+"))
+            .build()
+            .unwrap();
+        let keep = SlugifyOptions::builder()
+            .char_classifier(|c| match c {
+                'a'..='z' | '0'..='9' | '.' => CharAction::Keep,
+                _ => CharAction::Separator,
+            })
+            .build()
+            .unwrap();
+        for txt in ["Version 1.2_beta", "Ünïcödé -- config.yaml!", "i love 🦄"] {
+            assert_eq!(
+                slugify_with_options_public(&keep, txt),
+                slugify_with_options_public(&regex, txt)
+            );
+        }
+        // regex_pattern wins; the classifier wins over allowed_chars
+        let opts = SlugifyOptions::builder()
+            .char_classifier(|_| CharAction::Separator)
+            .regex_pattern(Some(r"[^a-z]+"))
+            .build()
+            .unwrap();
+        assert_eq!(slugify_with_options_public(&opts, "a b"), "a-b");
+        let opts = SlugifyOptions::builder()
+            .allowed_chars(Some("a-z"))
+            .char_classifier(|c| match c {
+                '.' => CharAction::Keep,
+                c if c.is_ascii_alphanumeric() => CharAction::Keep,
+                _ => CharAction::Separator,
+            })
+            .build()
+            .unwrap();
+        assert_eq!(slugify_with_options_public(&opts, "a.b c"), "a.b-c");
+        assert_eq!(slug_pattern(&opts), "^.+$");
+    }
+
+    #[test]
+    fn test_allowed_extra_chars() {
+        let extra = |chars: &str| {
+            SlugifyOptions::builder()
+                .allowed_extra_chars(chars)
+                .builtin_protected_terms(false)
+        };
+        let opts = extra("._~+").build().unwrap();
+        assert_eq!(slugify_with_options_public(&opts, "v1.2 C++"), "v1.2-c++");
+        assert_eq!(slugify_with_options_public(&opts, "~user/a b"), "~user-a-b");
+        assert_eq!(slugify_default("v1.2 C++"), "v1-2-c");
+        // regex metacharacters are taken literally
+        let opts = extra("]^\\").build().unwrap();
+        assert_eq!(
+            slugify_with_options_public(&opts, "a]b^c\\d[e"),
+            "a]b^c\\d-e"
+        );
+        let opts = extra(".").allow_unicode(true).build().unwrap();
+        assert_eq!(
+            slugify_with_options_public(&opts, "Déjà vu 2.0!"),
+            "déjà-vu-2.0"
+        );
+        // the pattern and validation know about the extra characters
+        let opts = extra("._~+").build().unwrap();
+        let pattern = Regex::new(&slug_pattern(&opts)).unwrap();
+        assert!(pattern.is_match("v1.2-c++"));
+        assert!(crate::slug::validate_slug("v1.2-c++", &opts).is_valid());
+        assert!(!crate::slug::validate_slug("v1.2-c#", &opts).is_valid());
+        // allowed_chars takes precedence
+        let opts = extra(".").allowed_chars(Some("a-z")).build().unwrap();
+        assert_eq!(slugify_with_options_public(&opts, "a.b"), "a-b");
+        // built-in terms made of allowed characters are left alone
+        #[cfg(feature = "tech_terms")]
+        {
+            let opts = extra("+").builtin_protected_terms(true).build().unwrap();
+            assert_eq!(
+                slugify_with_options_public(&opts, "C++ and C#"),
+                "c++-and-c-sharp"
+            );
+        }
+    }
+
     #[test]
     fn test_slugify_emojis_and_unicode() {
         let txt = "i love 🦄";
@@ -1213,10 +5617,158 @@ mod tests {
             .lowercase(true)
             .replacements(Vec::<(&str, &str)>::new())
             .allow_unicode(true)
-            .transliterate_icons(false)
+            .emoji_policy(EmojiPolicy::Remove)
             .build()
             .unwrap();
         let r = s_args_with_opts(txt, opts_emoji);
         assert_eq!(r, "🦄");
     }
+
+    #[test]
+    fn test_dns_label_preset() {
+        let opts = SlugifyOptions::dns_label();
+        let s = |t: &str| slugify_with_options_public(&opts, t);
+        assert_eq!(s("My App_Service (Staging)"), "my-app-service-staging");
+        assert_eq!(s("--Ünïcödé 🚀 release!!"), "unicode-rocket-release");
+        let long = "the quick brown fox jumps over the lazy dog and keeps on running far away";
+        let label = s(long);
+        assert_eq!(label, "the-quick-brown-fox-jumps-over-the-lazy-dog-and-keeps-on-far");
+        assert!(label.len() <= DNS_LABEL_MAX_LENGTH);
+        assert_eq!(s(&"a".repeat(80)).len(), DNS_LABEL_MAX_LENGTH);
+        assert_eq!(s("!!!"), "");
+
+        // every non-empty output is a valid label, including cuts next to
+        // separators and digits
+        let words = ["Alpha", "b", "Ω", "12", "x_y", "-", "日本", "long-word-here", "É"];
+        let mut seed: u64 = 0x9e37_79b9_7f4a_7c15;
+        for _ in 0..500 {
+            let mut text = String::new();
+            for _ in 0..40 {
+                seed ^= seed << 13;
+                seed ^= seed >> 7;
+                seed ^= seed << 17;
+                text.push_str(words[(seed % words.len() as u64) as usize]);
+                text.push(if seed.is_multiple_of(3) { '-' } else { ' ' });
+            }
+            let label = s(&text);
+            assert!(label.is_empty() || is_dns_label(&label), "{:?} -> {:?}", text, label);
+        }
+
+        assert!(is_dns_label("a"));
+        assert!(is_dns_label("web-1"));
+        assert!(!is_dns_label(""));
+        assert!(!is_dns_label("-web"));
+        assert!(!is_dns_label("web-"));
+        assert!(!is_dns_label("Web"));
+        assert!(!is_dns_label("web_1"));
+        assert!(!is_dns_label(&"a".repeat(64)));
+    }
+
+    #[test]
+    fn test_idna_labels() {
+        let opts = SlugifyOptions::builder().allow_unicode(true).idna(true).build().unwrap();
+        let s = |t: &str| slugify_with_options_public(&opts, t);
+        assert_eq!(s("Café München"), "xn--caf-mnchen-d7a4u");
+        assert_eq!(s("日本語 ガイド"), "xn----jeuxa1j8860acvb2w6i");
+        assert_eq!(s("Plain Title"), "plain-title");
+        let long = s(&"Größenwahn ".repeat(10));
+        assert!(long.starts_with("xn--") && long.len() <= DNS_LABEL_MAX_LENGTH, "{}", long);
+        assert!(!long.ends_with('-'));
+
+        let dotted = SlugifyOptions::builder()
+            .allow_unicode(true)
+            .separator(".")
+            .idna(true)
+            .build()
+            .unwrap();
+        assert_eq!(
+            slugify_with_options_public(&dotted, "Bücher Straße com"),
+            "xn--bcher-kva.xn--strae-oqa.com"
+        );
+        // without allow_unicode the slug is already ASCII
+        let ascii = SlugifyOptions::builder().idna(true).build().unwrap();
+        assert_eq!(slugify_with_options_public(&ascii, "Café München"), "cafe-munchen");
+    }
+
+    #[test]
+    fn test_identifier_styles() {
+        let ident = |style: IdentifierStyle, text: &str| {
+            let opts = SlugifyOptions::builder()
+                .separator("-")
+                .identifier(style)
+                .build()
+                .unwrap();
+            slugify_with_options_public(&opts, text)
+        };
+        assert_eq!(ident(IdentifierStyle::python(), "User Name"), "user_name");
+        assert_eq!(ident(IdentifierStyle::python(), "2nd Class"), "_2nd_class");
+        assert_eq!(ident(IdentifierStyle::python(), "Class"), "class_");
+        assert_eq!(ident(IdentifierStyle::python(), "None"), "none");
+        assert_eq!(ident(IdentifierStyle::rust(), "Type"), "type_");
+        assert_eq!(ident(IdentifierStyle::c(), "Unsigned"), "unsigned_");
+        assert_eq!(ident(IdentifierStyle::c(), "Déjà-vu & more"), "deja_vu_more");
+        let custom = IdentifierStyle {
+            digit_prefix: "n".to_string(),
+            reserved: vec!["select".to_string()],
+            reserved_suffix: "_col".to_string(),
+        };
+        assert_eq!(ident(custom.clone(), "404 Page"), "n404_page");
+        assert_eq!(ident(custom, "SELECT"), "select_col");
+        assert_eq!(ident(IdentifierStyle::default(), "!!!"), "");
+
+        // hyphens kept from the source become underscores too
+        let opts = SlugifyOptions::builder()
+            .separator_scope(SeparatorScope::Inserted)
+            .allow_unicode(true)
+            .identifier(IdentifierStyle::python())
+            .build()
+            .unwrap();
+        assert_eq!(slugify_with_options_public(&opts, "Ünïcode e-mail 5"), "ünïcode_e_mail_5");
+    }
+
+    #[test]
+    fn test_case_styles() {
+        let styled = |style: CaseStyle, text: &str| {
+            let opts = SlugifyOptions::builder()
+                .separator(".")
+                .case_style(style)
+                .build()
+                .unwrap();
+            slugify_with_options_public(&opts, text)
+        };
+        let text = "Hello World foo";
+        assert_eq!(styled(CaseStyle::Separated, text), "hello.world.foo");
+        assert_eq!(styled(CaseStyle::Camel, text), "helloWorldFoo");
+        assert_eq!(styled(CaseStyle::Pascal, text), "HelloWorldFoo");
+        assert_eq!(styled(CaseStyle::Snake, text), "hello_world_foo");
+        assert_eq!(styled(CaseStyle::ScreamingSnake, text), "HELLO_WORLD_FOO");
+        assert_eq!(styled(CaseStyle::Camel, "iPhone 13 PRO max"), "iphone13ProMax");
+        assert_eq!(styled(CaseStyle::Pascal, "Déjà vu"), "DejaVu");
+        assert_eq!(styled(CaseStyle::Camel, ""), "");
+
+        // max_length counts the joined result
+        let opts = SlugifyOptions::builder()
+            .case_style(CaseStyle::Pascal)
+            .max_length(10)
+            .word_boundary(true)
+            .build()
+            .unwrap();
+        assert_eq!(slugify_with_options_public(&opts, "Hello World Again"), "HelloWorld");
+    }
+
+    #[test]
+    fn test_split_camel_case() {
+        let opts = SlugifyOptions::builder().split_camel_case(true).build().unwrap();
+        let s = |t: &str| slugify_with_options_public(&opts, t);
+        assert_eq!(s("iPhone13ProMax"), "i-phone-13-pro-max");
+        assert_eq!(s("parseHTMLDocument"), "parse-html-document");
+        assert_eq!(s("HTMLParser"), "html-parser");
+        assert_eq!(s("XMLHttpRequest v2"), "xml-http-request-v-2");
+        assert_eq!(s("Hello World ALLCAPS"), "hello-world-allcaps");
+        assert_eq!(s("ÉcoleNormale"), "ecole-normale");
+        // entities are decoded, not split
+        assert_eq!(s("fooBar &#x17D;ivot &amp; AT&amp;T"), "foo-bar-zivot-at-t");
+        // off by default
+        assert_eq!(slugify_default("iPhone13ProMax"), "iphone13promax");
+    }
 }
@@ -1,3 +1,5 @@
+use crate::tables::{Keys, Values, check_symbols, check_translations};
+
 // Static PRE_TRANSLATIONS in the exact order expected by the Python tests.
 pub static PRE_TRANSLATIONS: &[(&str, &str)] = &[
     ("Ю", "U"),
@@ -31,11 +33,373 @@ pub static PRE_TRANSLATIONS: &[(&str, &str)] = &[
     ("ϋ", "y"),
     ("ΰ", "y"),
 ];
+const _: () = check_translations(PRE_TRANSLATIONS, Keys::Exact, Values::Slug);
 
 pub fn pre_translations() -> &'static [(&'static str, &'static str)] {
     PRE_TRANSLATIONS
 }
 
+// Emoji names that differ from the CLDR table, kept for compatibility with
+// slugs produced by earlier releases (and python-slugify).
+pub static EMOJI_NAME_OVERRIDES: &[(&str, &str)] = &[("♥", "hearts"), ("♥\u{fe0f}", "hearts")];
+const _: () = check_translations(EMOJI_NAME_OVERRIDES, Keys::Exact, Values::Slug);
+
+/// English name of the emoji `grapheme` ("🚀" -> "rocket", "🇫🇷" ->
+/// "flag: France"), from the CLDR / gemoji table of the `emojis` crate.
+/// Skin tone, ZWJ and keycap sequences are looked up as a whole. Returns
+/// `None` when `grapheme` is not a known emoji. Without the `emoji_names`
+/// feature only `EMOJI_NAME_OVERRIDES` are known.
+pub fn emoji_name(grapheme: &str) -> Option<&'static str> {
+    if let Some((_, name)) = EMOJI_NAME_OVERRIDES.iter().find(|(e, _)| *e == grapheme) {
+        return Some(name);
+    }
+    #[cfg(feature = "emoji_names")]
+    {
+        emojis::get(grapheme).map(|e| e.name())
+    }
+    #[cfg(not(feature = "emoji_names"))]
+    {
+        None
+    }
+}
+
+/// GitHub / gemoji shortcode of `grapheme` without colons ("🚀" ->
+/// "rocket"). Returns `None` for unknown emoji and for sequences without a
+/// shortcode (e.g. most skin tone variants), and always without the
+/// `emoji_names` feature.
+pub fn emoji_shortcode(grapheme: &str) -> Option<&'static str> {
+    #[cfg(feature = "emoji_names")]
+    {
+        emojis::get(grapheme).and_then(|e| e.shortcode())
+    }
+    #[cfg(not(feature = "emoji_names"))]
+    {
+        let _ = grapheme;
+        None
+    }
+}
+
+/// Languages with built-in rules (title casing, small words, ...).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Locale {
+    #[default]
+    En,
+    De,
+    Es,
+    Fr,
+    Nl,
+}
+
+/// Words kept lowercase inside titles for a given locale (unless they
+/// start the title).
+pub fn small_words(locale: Locale) -> &'static [&'static str] {
+    match locale {
+        Locale::En => &[
+            "a", "an", "and", "as", "at", "but", "by", "for", "in", "nor", "of", "on", "or", "the",
+            "to", "vs", "with",
+        ],
+        Locale::De => &[
+            "der", "die", "das", "und", "oder", "von", "zu", "im", "in", "mit",
+        ],
+        Locale::Es => &["el", "la", "los", "las", "y", "o", "de", "del", "en", "con"],
+        Locale::Fr => &["le", "la", "les", "et", "ou", "de", "des", "du", "en", "un", "une"],
+        Locale::Nl => &["de", "het", "een", "en", "of", "van", "in", "op", "met"],
+    }
+}
+
+/// Words that symbols expand to in `locale` (used by
+/// `SlugifyOptionsBuilder::symbol_locale`). `#` only expands before a
+/// digit ("#1" -> "number 1"), so hashtags are left alone.
+pub fn symbol_words(locale: Locale) -> &'static [(char, &'static str)] {
+    match locale {
+        Locale::En => SYMBOLS_EN,
+        Locale::De => SYMBOLS_DE,
+        Locale::Es => SYMBOLS_ES,
+        Locale::Fr => SYMBOLS_FR,
+        Locale::Nl => SYMBOLS_NL,
+    }
+}
+
+const SYMBOLS_EN: &[(char, &str)] = &[
+    ('&', "and"),
+    ('@', "at"),
+    ('%', "percent"),
+    ('#', "number"),
+    ('€', "euro"),
+    ('$', "dollar"),
+    ('£', "pound"),
+    ('+', "plus"),
+];
+const _: () = check_symbols(SYMBOLS_EN);
+
+const SYMBOLS_DE: &[(char, &str)] = &[
+    ('&', "und"),
+    ('@', "at"),
+    ('%', "prozent"),
+    ('#', "nummer"),
+    ('€', "euro"),
+    ('$', "dollar"),
+    ('£', "pfund"),
+    ('+', "plus"),
+];
+const _: () = check_symbols(SYMBOLS_DE);
+
+const SYMBOLS_ES: &[(char, &str)] = &[
+    ('&', "y"),
+    ('@', "arroba"),
+    ('%', "por ciento"),
+    ('#', "numero"),
+    ('€', "euro"),
+    ('$', "dolar"),
+    ('£', "libra"),
+    ('+', "mas"),
+];
+const _: () = check_symbols(SYMBOLS_ES);
+
+const SYMBOLS_FR: &[(char, &str)] = &[
+    ('&', "et"),
+    ('@', "arobase"),
+    ('%', "pour cent"),
+    ('#', "numero"),
+    ('€', "euro"),
+    ('$', "dollar"),
+    ('£', "livre"),
+    ('+', "plus"),
+];
+const _: () = check_symbols(SYMBOLS_FR);
+
+const SYMBOLS_NL: &[(char, &str)] = &[
+    ('&', "en"),
+    ('@', "at"),
+    ('%', "procent"),
+    ('#', "nummer"),
+    ('€', "euro"),
+    ('$', "dollar"),
+    ('£', "pond"),
+    ('+', "plus"),
+];
+const _: () = check_symbols(SYMBOLS_NL);
+
+/// Replace the symbols of `table` with their word, padded with spaces so it
+/// becomes a word of the slug. An `&` that starts an HTML entity or numeric
+/// reference ("&amp;", "&#381;") is kept for entity decoding.
+pub fn expand_symbols(s: &str, table: &[(char, &str)]) -> String {
+    if table.is_empty() {
+        return s.to_string();
+    }
+    let mut out = String::with_capacity(s.len() + 16);
+    // end of an entity being copied verbatim
+    let mut entity_end = 0;
+    for (i, c) in s.char_indices() {
+        let rest = &s[i + c.len_utf8()..];
+        if i < entity_end {
+            out.push(c);
+            continue;
+        }
+        if c == '&'
+            && let Some(m) = ENTITY_START.find(rest)
+        {
+            entity_end = i + 1 + m.end();
+            out.push(c);
+            continue;
+        }
+        let word = match table.iter().find(|(sym, _)| *sym == c) {
+            Some(('#', _)) if !rest.starts_with(|n: char| n.is_ascii_digit()) => None,
+            Some((_, word)) => Some(word),
+            None => None,
+        };
+        match word {
+            Some(word) => {
+                out.push(' ');
+                out.push_str(word);
+                out.push(' ');
+            }
+            None => out.push(c),
+        }
+    }
+    out
+}
+
+// Remainder of an entity or numeric reference after its `&`.
+#[allow(clippy::unwrap_used)]
+static ENTITY_START: Lazy<regex::Regex> =
+    Lazy::new(|| regex::Regex::new(r"^(?:[A-Za-z0-9]+|#\d+|#x[\da-fA-F]+);").unwrap());
+
+/// How the ambiguous abbreviation "St." is expanded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum StExpansion {
+    #[default]
+    Saint,
+    Street,
+}
+
+/// Reserved words of Rust (strict and reserved keywords, 2024 edition).
+pub static RUST_KEYWORDS: &[&str] = &[
+    "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "crate",
+    "do", "dyn", "else", "enum", "extern", "false", "final", "fn", "for", "gen", "if", "impl",
+    "in", "let", "loop", "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref",
+    "return", "self", "static", "struct", "super", "trait", "true", "try", "type", "typeof",
+    "unsafe", "unsized", "use", "virtual", "where", "while", "yield",
+];
+
+/// Python keywords and the soft keywords `match`, `case`, `type` and `_`.
+pub static PYTHON_KEYWORDS: &[&str] = &[
+    "False", "None", "True", "_", "and", "as", "assert", "async", "await", "break", "case", "class",
+    "continue", "def", "del", "elif", "else", "except", "finally", "for", "from", "global", "if",
+    "import", "in", "is", "lambda", "match", "nonlocal", "not", "or", "pass", "raise", "return",
+    "try", "type", "while", "with", "yield",
+];
+
+/// C keywords up to C23.
+pub static C_KEYWORDS: &[&str] = &[
+    "alignas", "alignof", "auto", "bool", "break", "case", "char", "const", "constexpr",
+    "continue", "default", "do", "double", "else", "enum", "extern", "false", "float", "for",
+    "goto", "if", "inline", "int", "long", "nullptr", "register", "restrict", "return", "short",
+    "signed", "sizeof", "static", "static_assert", "struct", "switch", "thread_local", "true",
+    "typedef", "typeof", "typeof_unqual", "union", "unsigned", "void", "volatile", "while",
+    "_Alignas", "_Alignof", "_Atomic", "_BitInt", "_Bool", "_Complex", "_Decimal128",
+    "_Decimal32", "_Decimal64", "_Generic", "_Imaginary", "_Noreturn", "_Static_assert",
+    "_Thread_local",
+];
+
+/// CJK and fullwidth punctuation that always separates words, whatever the
+/// transliteration backend or `allow_unicode` makes of it. The fullwidth
+/// comma and full stop are left out: inside numbers ("１，０００") they are
+/// handled by `NumericCleanup`.
+pub static CJK_PUNCTUATION: &[char] = &[
+    '、', '。', '〃', '〈', '〉', '《', '》', '「', '」', '『', '』', '【', '】', '〔', '〕', '〖',
+    '〗', '〘', '〙', '〚', '〛', '〜', '〝', '〞', '〟', '・', '…', '‥', '！', '（', '）', '：',
+    '；', '？', '［', '］', '｛', '｝', '～', '｡', '｢', '｣', '､', '･',
+];
+
+/// Corner brackets and other CJK quotation marks, which
+/// `CjkQuotes::Remove` drops instead of turning into separators.
+pub static CJK_QUOTES: &[char] = &['「', '」', '『', '』', '〝', '〞', '〟', '｢', '｣'];
+
+/// Built-in abbreviations expanded by `expand_abbreviations` (matched
+/// case-insensitively as whole whitespace-separated tokens). "St." is not
+/// listed here; see `StExpansion`.
+pub static ABBREVIATIONS: &[(&str, &str)] = &[
+    ("&c.", "etc"),
+    ("Ave.", "avenue"),
+    ("Bros.", "brothers"),
+    ("Co.", "company"),
+    ("Dr.", "doctor"),
+    ("Ed.", "edition"),
+    ("etc.", "etc"),
+    ("Jr.", "junior"),
+    ("Mr.", "mister"),
+    ("Mrs.", "missus"),
+    ("Mt.", "mount"),
+    ("No.", "number"),
+    ("Prof.", "professor"),
+    ("Sr.", "senior"),
+    ("Vol.", "volume"),
+    ("vs.", "versus"),
+];
+const _: () = check_translations(ABBREVIATIONS, Keys::AsciiCaseInsensitive, Values::Slug);
+
+/// Built-in abbreviation table with "St." resolved according to `st`.
+pub fn abbreviations(st: StExpansion) -> Vec<(&'static str, &'static str)> {
+    let mut table = ABBREVIATIONS.to_vec();
+    table.push((
+        "St.",
+        match st {
+            StExpansion::Saint => "saint",
+            StExpansion::Street => "street",
+        },
+    ));
+    table
+}
+
+/// Expand whitespace-delimited abbreviations using `table`.
+///
+/// A token matches when it starts with an entry (ASCII case-insensitive)
+/// and the rest of the token holds no letters or digits, so "Dr.," expands
+/// but "Drive" does not. Earlier entries win over later ones of the same
+/// length; longer entries are tried first.
+pub fn expand_abbreviations(s: &str, table: &[(String, String)]) -> String {
+    if table.is_empty() {
+        return s.to_string();
+    }
+    let mut by_len: Vec<&(String, String)> = table.iter().collect();
+    by_len.sort_by_key(|(abbr, _)| std::cmp::Reverse(abbr.len()));
+
+    let mut out = String::with_capacity(s.len());
+    for piece in s.split_inclusive(char::is_whitespace) {
+        let token = piece.trim_end();
+        let trailing = &piece[token.len()..];
+        let expanded = by_len.iter().find_map(|(abbr, exp)| {
+            let head = token.get(..abbr.len())?;
+            let rest = &token[abbr.len()..];
+            (head.eq_ignore_ascii_case(abbr) && !rest.chars().any(char::is_alphanumeric))
+                .then(|| format!("{}{}", exp, rest))
+        });
+        match expanded {
+            Some(e) => out.push_str(&e),
+            None => out.push_str(token),
+        }
+        out.push_str(trailing);
+    }
+    out
+}
+
+/// Built-in protected terms: technology names whose punctuation carries
+/// meaning and would otherwise be dropped ("C++" -> "c", ".NET" -> "net").
+#[cfg(feature = "tech_terms")]
+pub static TECH_TERMS: &[(&str, &str)] = &[
+    ("ASP.NET", "asp-dotnet"),
+    ("C#", "c-sharp"),
+    ("C++", "cpp"),
+    ("Express.js", "expressjs"),
+    ("F#", "f-sharp"),
+    ("G++", "gpp"),
+    ("J#", "j-sharp"),
+    ("Next.js", "nextjs"),
+    (".NET", "dotnet"),
+    ("Node.js", "nodejs"),
+    ("Nuxt.js", "nuxtjs"),
+    ("Three.js", "threejs"),
+    ("Vue.js", "vuejs"),
+];
+#[cfg(feature = "tech_terms")]
+const _: () = check_translations(TECH_TERMS, Keys::AsciiCaseInsensitive, Values::Slug);
+
+/// Replace whitespace-delimited tokens that are exactly a term of `table`
+/// (ASCII case-insensitive). Opening brackets and quotes before the term
+/// and punctuation after it are kept, so "(C++)," becomes "(cpp),", while
+/// "C++11" or "libc++" are left alone. Longer terms are tried first;
+/// earlier entries win over later ones of the same length.
+pub fn apply_protected_terms(s: &str, table: &[(String, String)]) -> String {
+    if table.is_empty() {
+        return s.to_string();
+    }
+    let mut by_len: Vec<&(String, String)> = table.iter().collect();
+    by_len.sort_by_key(|(term, _)| std::cmp::Reverse(term.len()));
+
+    let mut out = String::with_capacity(s.len());
+    for piece in s.split_inclusive(char::is_whitespace) {
+        let token = piece.trim_end();
+        let trailing = &piece[token.len()..];
+        let core = token.trim_start_matches(['(', '[', '{', '"', '\'']);
+        let lead = &token[..token.len() - core.len()];
+        let replaced = by_len.iter().find_map(|(term, with)| {
+            let head = core.get(..term.len())?;
+            let rest = &core[term.len()..];
+            (head.eq_ignore_ascii_case(term) && !rest.chars().any(char::is_alphanumeric))
+                .then(|| format!("{}{}{}", lead, with, rest))
+        });
+        match replaced {
+            Some(r) => out.push_str(&r),
+            None => out.push_str(token),
+        }
+        out.push_str(trailing);
+    }
+    out
+}
+
+use std::borrow::Cow;
+
 use aho_corasick::{AhoCorasick, AhoCorasickBuilder, MatchKind};
 use once_cell::sync::Lazy;
 
@@ -51,23 +415,126 @@ static AC_AUTOMATON: Lazy<AhoCorasick> = Lazy::new(|| {
     }
 });
 
-pub fn apply_pre_translations(s: &str) -> String {
-    let mut out = String::with_capacity(s.len());
+/// Apply `PRE_TRANSLATIONS` to `s`; borrows `s` when nothing matches.
+pub fn apply_pre_translations(s: &str) -> Cow<'_, str> {
+    replace_matches(s, &AC_AUTOMATON, |idx| PRE_TRANSLATIONS[idx].1)
+}
+
+/// Apply a compiled `TranslationTable` to `s`, like
+/// `apply_pre_translations` does with the built-in table; borrows `s` when
+/// nothing matches.
+pub fn apply_translations_with<'a>(table: &TranslationTable, s: &'a str) -> Cow<'a, str> {
+    match table.automaton {
+        Some(ref ac) => replace_matches(s, ac, |idx| &table.pairs[idx].1),
+        None => Cow::Borrowed(s),
+    }
+}
+
+/// `apply_pre_translations` with a caller-supplied table, e.g.
+/// `&[("ß", "ss"), ("Þ", "Th")]`. Longer keys win over shorter ones; empty
+/// keys are ignored. The automaton is built on every call: use
+/// `TranslationTable` to reuse it.
+pub fn apply_pre_translations_with(s: &str, table: &[(&str, &str)]) -> String {
+    match TranslationTable::new(table.iter().copied()) {
+        Ok(t) => t.apply(s),
+        Err(_) => s.to_string(),
+    }
+}
+
+/// Per-character (or per-sequence) translations compiled once into an
+/// Aho-Corasick automaton. Used by
+/// `SlugifyOptionsBuilder::custom_translations`.
+#[derive(Debug, Clone, Default)]
+pub struct TranslationTable {
+    pairs: Vec<(String, String)>,
+    automaton: Option<AhoCorasick>,
+}
+
+impl TranslationTable {
+    /// Compile `pairs` (earlier pairs win over later ones with the same
+    /// key, longer keys over shorter ones). Fails only when the automaton
+    /// would be too large.
+    pub fn new<I, A, B>(pairs: I) -> Result<Self, aho_corasick::BuildError>
+    where
+        I: IntoIterator<Item = (A, B)>,
+        A: AsRef<str>,
+        B: AsRef<str>,
+    {
+        let mut out: Vec<(String, String)> = Vec::new();
+        for (a, b) in pairs {
+            let (a, b) = (a.as_ref(), b.as_ref());
+            if !a.is_empty() && !out.iter().any(|(k, _)| k == a) {
+                out.push((a.to_string(), b.to_string()));
+            }
+        }
+        let automaton = if out.is_empty() {
+            None
+        } else {
+            Some(
+                AhoCorasickBuilder::new()
+                    .match_kind(MatchKind::LeftmostLongest)
+                    .build(out.iter().map(|(k, _)| k))?,
+            )
+        };
+        Ok(TranslationTable {
+            pairs: out,
+            automaton,
+        })
+    }
+
+    pub fn pairs(&self) -> &[(String, String)] {
+        &self.pairs
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pairs.is_empty()
+    }
+
+    /// Owned form of `apply_translations_with(self, s)`.
+    pub fn apply(&self, s: &str) -> String {
+        apply_translations_with(self, s).into_owned()
+    }
+}
+
+/// Replace every non-overlapping match of `ac` in `s` with
+/// `replacement(pattern_index)`. Overlaps are resolved by the automaton's
+/// `MatchKind`.
+///
+/// The output is always valid UTF-8 and never panics on slicing: matches
+/// that do not start and end on a char boundary (an empty pattern inside a
+/// multi-byte character, or a byte pattern holding part of a code point)
+/// are skipped and the text is copied unchanged.
+pub fn replace_all_ac<'r, F>(s: &str, ac: &AhoCorasick, replacement: F) -> String
+where
+    F: Fn(usize) -> &'r str,
+{
+    replace_matches(s, ac, replacement).into_owned()
+}
+
+// `replace_all_ac` without allocating when there is no usable match.
+fn replace_matches<'a, 'r, F>(s: &'a str, ac: &AhoCorasick, replacement: F) -> Cow<'a, str>
+where
+    F: Fn(usize) -> &'r str,
+{
+    let mut out: Option<String> = None;
     let mut last = 0usize;
-    for mat in AC_AUTOMATON.find_iter(s) {
+    for mat in ac.find_iter(s) {
         let (start, end) = (mat.start(), mat.end());
-        if start > last {
-            out.push_str(&s[last..start]);
+        if start < last || !s.is_char_boundary(start) || !s.is_char_boundary(end) {
+            continue;
         }
-        let idx = mat.pattern();
-        let repl = PRE_TRANSLATIONS[idx].1;
-        out.push_str(repl);
+        let out = out.get_or_insert_with(|| String::with_capacity(s.len()));
+        out.push_str(&s[last..start]);
+        out.push_str(replacement(mat.pattern().as_usize()));
         last = end;
     }
-    if last < s.len() {
-        out.push_str(&s[last..]);
+    match out {
+        Some(mut out) => {
+            out.push_str(&s[last..]);
+            Cow::Owned(out)
+        }
+        None => Cow::Borrowed(s),
     }
-    out
 }
 
 #[cfg(test)]
@@ -128,6 +595,144 @@ mod tests {
         assert!(out.contains("Chch") || out.contains("Chch"));
     }
 
+    fn ac(pats: &[&str], kind: MatchKind) -> AhoCorasick {
+        AhoCorasickBuilder::new().match_kind(kind).build(pats).unwrap()
+    }
+
+    #[test]
+    fn test_replace_all_ac_multibyte_and_overlaps() {
+        let a = ac(&["ü", "üb", "€"], MatchKind::LeftmostLongest);
+        let repl = ["ue", "UB", "EUR"];
+        assert_eq!(replace_all_ac("Müber 5€", &a, |i| repl[i]), "MUBer 5EUR");
+        assert_eq!(replace_all_ac("", &a, |i| repl[i]), "");
+        // leftmost-first prefers the earlier pattern over the longer one
+        let a = ac(&["ab", "abc", "bc"], MatchKind::LeftmostFirst);
+        let repl = ["1", "2", "3"];
+        assert_eq!(replace_all_ac("abcbc", &a, |i| repl[i]), "1c3");
+        let a = ac(&["ab", "abc", "bc"], MatchKind::LeftmostLongest);
+        assert_eq!(replace_all_ac("abcbc", &a, |i| repl[i]), "23");
+    }
+
+    #[test]
+    fn test_replace_all_ac_skips_non_boundary_matches() {
+        // an empty pattern matches at every byte offset, including inside
+        // multi-byte characters
+        let a = ac(&[""], MatchKind::LeftmostFirst);
+        assert_eq!(replace_all_ac("é€", &a, |_| "-"), "-é-€-");
+        // a byte pattern holding the tail of "é" (0xC3 0xA9)
+        let a = AhoCorasick::new([&[0xA9u8][..]]).unwrap();
+        assert_eq!(replace_all_ac("café", &a, |_| "x"), "café");
+    }
+
+    #[test]
+    fn test_replace_all_ac_randomized() {
+        // cheap deterministic fuzzing (see `fuzz/` for the cargo-fuzz
+        // target): random mixes of 1..4 byte chars against a naive
+        // char-by-char reference
+        let alphabet = ['a', 'b', 'é', 'ü', 'Ж', '€', '🚀', '\u{301}'];
+        let pats = ["ü", "€", "ab", "b", "🚀", "é\u{301}"];
+        let repl = ["ue", "EUR", "AB", "B", "rocket", "E"];
+        let a = ac(&pats, MatchKind::LeftmostLongest);
+        let mut state = 0x2545_f491_4f6c_dd1du64;
+        for _ in 0..2000 {
+            let mut input = String::new();
+            for _ in 0..(state % 12) {
+                state ^= state << 13;
+                state ^= state >> 7;
+                state ^= state << 17;
+                input.push(alphabet[(state % alphabet.len() as u64) as usize]);
+            }
+            let mut expected = String::new();
+            let mut rest = input.as_str();
+            while let Some(c) = rest.chars().next() {
+                let hit = pats
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, p)| rest.starts_with(**p))
+                    .max_by_key(|(i, p)| (p.len(), std::cmp::Reverse(*i)));
+                match hit {
+                    Some((i, p)) => {
+                        expected.push_str(repl[i]);
+                        rest = &rest[p.len()..];
+                    }
+                    None => {
+                        expected.push(c);
+                        rest = &rest[c.len_utf8()..];
+                    }
+                }
+            }
+            assert_eq!(replace_all_ac(&input, &a, |i| repl[i]), expected, "{:?}", input);
+        }
+    }
+
+    #[test]
+    fn test_custom_translation_tables() {
+        let table = [("ß", "ss"), ("Þ", "Th"), ("ße", "sse!"), ("ß", "sz"), ("", "x")];
+        assert_eq!(apply_pre_translations_with("Straße Þór ß", &table), "Strasse! Thór ss");
+        let t = TranslationTable::new(table).unwrap();
+        assert_eq!(t.pairs().len(), 3);
+        assert_eq!(t.apply("Großes"), "Grosse!s");
+        assert!(TranslationTable::default().is_empty());
+        assert_eq!(TranslationTable::default().apply("ß"), "ß");
+        assert!(matches!(
+            apply_translations_with(&t, "Gross"),
+            Cow::Borrowed("Gross")
+        ));
+        assert_eq!(apply_translations_with(&t, "Þór"), "Thór");
+        assert!(matches!(
+            apply_translations_with(&TranslationTable::default(), "ß"),
+            Cow::Borrowed("ß")
+        ));
+        assert!(matches!(
+            apply_pre_translations("plain"),
+            Cow::Borrowed("plain")
+        ));
+        assert_eq!(apply_pre_translations("ёж"), "eж");
+    }
+
+    #[test]
+    fn test_expand_symbols() {
+        let en = symbol_words(Locale::En);
+        assert_eq!(
+            expand_symbols("Q&A @home 50%", en),
+            "Q and A  at home 50 percent "
+        );
+        assert_eq!(expand_symbols("#1 #rust", en), " number 1 #rust");
+        assert_eq!(expand_symbols("a &amp; b &#38; c", en), "a &amp; b &#38; c");
+        assert_eq!(expand_symbols("5€", symbol_words(Locale::Fr)), "5 euro ");
+        assert_eq!(expand_symbols("a & b", &[]), "a & b");
+    }
+
+    #[test]
+    fn test_expand_abbreviations() {
+        let table: Vec<(String, String)> = abbreviations(StExpansion::Street)
+            .into_iter()
+            .map(|(a, b)| (a.to_string(), b.to_string()))
+            .collect();
+        assert_eq!(
+            expand_abbreviations("Dr. Jekyll, 12 Baker St., &c.", &table),
+            "doctor Jekyll, 12 Baker street, etc"
+        );
+        // tokens that merely start like an abbreviation are left alone
+        assert_eq!(expand_abbreviations("Drive Co.op", &table), "Drive Co.op");
+        assert_eq!(expand_abbreviations("dr.\tno.", &table), "doctor\tnumber");
+        assert_eq!(expand_abbreviations("Dr. Who", &[]), "Dr. Who");
+    }
+
+    #[test]
+    fn test_apply_protected_terms() {
+        let table: Vec<(String, String)> = [("C", "see"), ("C++", "cpp"), (".NET", "dotnet")]
+            .iter()
+            .map(|(a, b)| (a.to_string(), b.to_string()))
+            .collect();
+        assert_eq!(
+            apply_protected_terms("(C++), c and .net!", &table),
+            "(cpp), see and dotnet!"
+        );
+        assert_eq!(apply_protected_terms("C++11 libc++", &table), "C++11 libc++");
+        assert_eq!(apply_protected_terms("C++", &[]), "C++");
+    }
+
     #[test]
     fn test_apply_pre_translations_integration_with_slugify() {
         let input = "ё ÜBER";
@@ -145,7 +750,7 @@ mod tests {
             .lowercase(true)
             .replacements(Vec::<(&str, &str)>::new())
             .allow_unicode(false)
-            .transliterate_icons(false)
+            .emoji_policy(crate::slugify::EmojiPolicy::Remove)
             .build()
             .unwrap();
         let out = crate::slugify::slugify_with_options_public(&opts, &pre);
@@ -0,0 +1,111 @@
+// Corpus statistics for slugs: length distribution, duplicates and charset.
+use std::collections::BTreeMap;
+
+/// Summary of a set of slugs produced by `summarize`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SlugStats {
+    /// Number of slugs inspected (empty ones included).
+    pub total: usize,
+    /// Number of empty slugs.
+    pub empty: usize,
+    /// Slug length in characters -> number of slugs with that length.
+    pub lengths: BTreeMap<usize, usize>,
+    /// Slugs seen more than once -> number of occurrences.
+    pub duplicates: BTreeMap<String, usize>,
+    /// Character -> number of occurrences across all slugs.
+    pub charset: BTreeMap<char, usize>,
+}
+
+impl SlugStats {
+    pub fn min_len(&self) -> Option<usize> {
+        self.lengths.keys().next().copied()
+    }
+
+    pub fn max_len(&self) -> Option<usize> {
+        self.lengths.keys().next_back().copied()
+    }
+
+    pub fn mean_len(&self) -> f64 {
+        if self.total == 0 {
+            return 0.0;
+        }
+        let sum: usize = self.lengths.iter().map(|(len, n)| len * n).sum();
+        sum as f64 / self.total as f64
+    }
+
+    /// Number of slugs longer than `max_length` characters, i.e. the slugs
+    /// a `max_length` policy would truncate.
+    pub fn exceeding(&self, max_length: usize) -> usize {
+        self.lengths
+            .range(max_length.saturating_add(1)..)
+            .map(|(_, n)| n)
+            .sum()
+    }
+
+    /// Group lengths into buckets of `width` characters. Returns
+    /// `(bucket_start, count)` pairs in ascending order.
+    pub fn histogram(&self, width: usize) -> Vec<(usize, usize)> {
+        let width = width.max(1);
+        let mut buckets: BTreeMap<usize, usize> = BTreeMap::new();
+        for (len, n) in &self.lengths {
+            *buckets.entry(len / width * width).or_insert(0) += n;
+        }
+        buckets.into_iter().collect()
+    }
+}
+
+/// Collect length, duplicate and charset statistics over `slugs`.
+pub fn summarize<I, S>(slugs: I) -> SlugStats
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    let mut stats = SlugStats::default();
+    let mut seen: BTreeMap<String, usize> = BTreeMap::new();
+    for slug in slugs {
+        let slug = slug.as_ref();
+        stats.total += 1;
+        if slug.is_empty() {
+            stats.empty += 1;
+        }
+        *stats.lengths.entry(slug.chars().count()).or_insert(0) += 1;
+        for c in slug.chars() {
+            *stats.charset.entry(c).or_insert(0) += 1;
+        }
+        *seen.entry(slug.to_string()).or_insert(0) += 1;
+    }
+    stats.duplicates = seen.into_iter().filter(|(_, n)| *n > 1).collect();
+    stats
+}
+
+#[cfg(test)]
+#[allow(clippy::expect_used)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_summarize_lengths_duplicates_charset() {
+        let stats = summarize(["hello-world", "foo", "foo", "", "a-much-longer-slug"]);
+        assert_eq!(stats.total, 5);
+        assert_eq!(stats.empty, 1);
+        assert_eq!(stats.min_len(), Some(0));
+        assert_eq!(stats.max_len(), Some(18));
+        assert_eq!(stats.exceeding(10), 2);
+        assert_eq!(stats.exceeding(18), 0);
+        assert_eq!(stats.duplicates.get("foo"), Some(&2));
+        assert_eq!(stats.duplicates.len(), 1);
+        assert_eq!(stats.charset.get(&'-'), Some(&4));
+        assert_eq!(stats.histogram(10), vec![(0, 3), (10, 2)]);
+        assert!((stats.mean_len() - 35.0 / 5.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_summarize_empty_input() {
+        let stats = summarize(Vec::<String>::new());
+        assert_eq!(stats.total, 0);
+        assert_eq!(stats.min_len(), None);
+        assert_eq!(stats.mean_len(), 0.0);
+        assert!(stats.histogram(10).is_empty());
+    }
+}
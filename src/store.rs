@@ -0,0 +1,115 @@
+// Uniqueness checks against an external (usually async) slug store such as
+// a database table.
+use std::future::Future;
+
+use crate::registry::{SuffixStrategy, suffixed};
+use crate::slugify::{SlugifyError, SlugifyOptions, slugify_with_options_public};
+
+/// A place where slugs are already in use, e.g. a `posts.slug` column
+/// queried through sqlx or diesel-async.
+///
+/// Implementations can be written with `async fn`:
+///
+/// ```
+/// use std::collections::HashSet;
+/// use slugify_rs::store::SlugStore;
+///
+/// struct Memory(HashSet<String>);
+///
+/// impl SlugStore for Memory {
+///     async fn exists(&self, slug: &str) -> bool {
+///         self.0.contains(slug)
+///     }
+/// }
+/// ```
+pub trait SlugStore {
+    /// Whether `slug` is already taken.
+    fn exists(&self, slug: &str) -> impl Future<Output = bool> + Send;
+}
+
+/// Slugify `text` with `opts` and append counter suffixes (`-2`, `-3`, ...)
+/// until `store` reports the slug as free.
+///
+/// The slug is not reserved in the store: insert it (ideally under a unique
+/// constraint) before another caller can pick the same one.
+pub async fn unique_slug<S: SlugStore + ?Sized>(
+    store: &S,
+    text: &str,
+    opts: &SlugifyOptions,
+) -> Result<String, SlugifyError> {
+    unique_slug_with(store, text, opts, SuffixStrategy::Counter).await
+}
+
+/// `unique_slug` with a custom `SuffixStrategy`. Fails with
+/// `SlugifyError::SuffixTooLong` when `max_length` leaves no room for the
+/// suffix. A store that never reports a free slug loops forever.
+pub async fn unique_slug_with<S: SlugStore + ?Sized>(
+    store: &S,
+    text: &str,
+    opts: &SlugifyOptions,
+    strategy: SuffixStrategy,
+) -> Result<String, SlugifyError> {
+    let slug = slugify_with_options_public(opts, text);
+    if !store.exists(&slug).await {
+        return Ok(slug);
+    }
+    let mut attempt = 1;
+    loop {
+        let candidate = suffixed(&slug, strategy, attempt, opts)?;
+        if !store.exists(&candidate).await {
+            return Ok(candidate);
+        }
+        attempt += 1;
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::expect_used)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+    use std::pin::pin;
+    use std::task::{Context, Poll, Waker};
+
+    struct Memory(HashSet<&'static str>);
+
+    impl SlugStore for Memory {
+        async fn exists(&self, slug: &str) -> bool {
+            self.0.contains(slug)
+        }
+    }
+
+    // The in-memory store never suspends, so a single poll completes.
+    fn block_on<F: Future>(fut: F) -> F::Output {
+        let mut cx = Context::from_waker(Waker::noop());
+        match pin!(fut).poll(&mut cx) {
+            Poll::Ready(v) => v,
+            Poll::Pending => panic!("future suspended"),
+        }
+    }
+
+    #[test]
+    fn test_unique_slug_retries_with_suffixes() {
+        let store = Memory(HashSet::from(["my-post", "my-post-2"]));
+        let opts = SlugifyOptions::builder().build().unwrap();
+        assert_eq!(block_on(unique_slug(&store, "My Post", &opts)).unwrap(), "my-post-3");
+        assert_eq!(block_on(unique_slug(&store, "Fresh", &opts)).unwrap(), "fresh");
+
+        let hashed = block_on(unique_slug_with(
+            &store,
+            "My Post",
+            &opts,
+            SuffixStrategy::ShortHash(5),
+        ))
+        .unwrap();
+        assert_eq!(hashed.len(), "my-post-".len() + 5);
+
+        let short = SlugifyOptions::builder().max_length(7).build().unwrap();
+        let store = Memory(HashSet::from(["abcdefg"]));
+        assert!(matches!(
+            block_on(unique_slug_with(&store, "abcdefg", &short, SuffixStrategy::ShortHash(6))),
+            Err(SlugifyError::SuffixTooLong { .. })
+        ));
+    }
+}
@@ -0,0 +1,331 @@
+// Slugify text read from an `io::Read` into an `io::Write` without holding
+// the whole input in memory: the input is cut into chunks at whitespace,
+// each chunk is slugified on its own and the slugs are joined with the
+// separator.
+use std::io::{self, ErrorKind, Read, Write};
+
+use crate::slugify::{
+    CaseStyle, Compat, RtlWordOrder, SlugifyOptions, slugify_untruncated, truncate_joined,
+};
+
+// Input is slugified once this many bytes are pending.
+const CHUNK_BYTES: usize = 64 * 1024;
+
+/// Write the slug of everything `reader` yields to `writer`.
+///
+/// Chunks end at whitespace, so the result equals
+/// `slugify_with_options_public` on the whole text, except that a single
+/// word longer than four chunks (256 KiB) is split in two. Options that
+/// need the whole text at once are rejected with `ErrorKind::InvalidInput`:
+/// `compat`, `identifier`, `idna`, case styles other than `Separated`,
+/// `RtlWordOrder::Visual`, `max_length` with an empty separator,
+/// `strip_numeric_ids` (its pattern is anchored to the ends of the text,
+/// which every chunk would have), and replacements or abbreviations
+/// containing whitespace (a chunk may end inside them). Input that is not
+/// UTF-8 fails with `ErrorKind::InvalidData`.
+///
+/// With `max_length`, slugs are collected until the truncated result can no
+/// longer change, so only about `max_length` characters are kept when
+/// `save_order` is set or `word_boundary` is off (unless
+/// `TruncationStrategy::CutWithHash` needs the whole text for its hash).
+pub fn slugify_stream<R: Read, W: Write>(
+    reader: R,
+    writer: W,
+    opts: &SlugifyOptions,
+) -> io::Result<()> {
+    slugify_stream_chunked(reader, writer, opts, CHUNK_BYTES)
+}
+
+fn check_streamable(opts: &SlugifyOptions) -> io::Result<()> {
+    let unsupported = if opts.compat != Compat::None {
+        Some("compat")
+    } else if opts.identifier.is_some() {
+        Some("identifier")
+    } else if opts.idna {
+        Some("idna")
+    } else if opts.case_style != CaseStyle::Separated {
+        Some("case_style")
+    } else if opts.rtl_word_order == RtlWordOrder::Visual {
+        Some("rtl_word_order")
+    } else if opts.max_length > 0 && opts.separator.is_empty() {
+        Some("max_length with an empty separator")
+    } else if opts.numeric_id_pattern.is_some() {
+        Some("strip_numeric_ids")
+    } else if opts.replacements.iter().any(|(from, _)| spans_words(from))
+        || opts
+            .replacement_rules
+            .iter()
+            .any(|(rule, _)| spans_words(&rule.pattern))
+    {
+        Some("a replacement containing whitespace")
+    } else if opts.abbreviations.iter().any(|(from, _)| spans_words(from)) {
+        Some("an abbreviation containing whitespace")
+    } else {
+        None
+    };
+    match unsupported {
+        Some(option) => Err(io::Error::new(
+            ErrorKind::InvalidInput,
+            format!("{} cannot be applied to a stream", option),
+        )),
+        None => Ok(()),
+    }
+}
+
+// A chunk may end between the words of such a pattern.
+fn spans_words(pattern: &str) -> bool {
+    pattern.contains(char::is_whitespace)
+}
+
+fn slugify_stream_chunked<R: Read, W: Write>(
+    mut reader: R,
+    writer: W,
+    opts: &SlugifyOptions,
+    chunk_bytes: usize,
+) -> io::Result<()> {
+    check_streamable(opts)?;
+    let mut out = Joiner::new(writer, opts);
+    let mut pending: Vec<u8> = Vec::new();
+    let mut buf = vec![0u8; chunk_bytes];
+    loop {
+        let n = match reader.read(&mut buf) {
+            Ok(0) => break,
+            Ok(n) => n,
+            Err(e) if e.kind() == ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e),
+        };
+        pending.extend_from_slice(&buf[..n]);
+        if pending.len() < chunk_bytes {
+            continue;
+        }
+        // keep an incomplete trailing UTF-8 sequence for the next read
+        let valid = match std::str::from_utf8(&pending) {
+            Ok(s) => s.len(),
+            Err(e) if e.error_len().is_none() => e.valid_up_to(),
+            Err(_) => return Err(not_utf8()),
+        };
+        let text = std::str::from_utf8(&pending[..valid]).map_err(|_| not_utf8())?;
+        let cut = match text.rfind(char::is_whitespace) {
+            Some(i) => i + text[i..].chars().next().map_or(0, char::len_utf8),
+            None if valid >= 4 * chunk_bytes => valid,
+            None => continue,
+        };
+        out.push(&text[..cut])?;
+        pending.drain(..cut);
+        if out.is_complete() {
+            return out.finish();
+        }
+    }
+    let text = std::str::from_utf8(&pending).map_err(|_| not_utf8())?;
+    out.push(text)?;
+    out.finish()
+}
+
+fn not_utf8() -> io::Error {
+    io::Error::new(ErrorKind::InvalidData, "stream is not valid UTF-8")
+}
+
+// Joins chunk slugs with the separator. Without `max_length` they are
+// written as they come; otherwise they are collected and truncated at the
+// end.
+struct Joiner<'a, W: Write> {
+    writer: W,
+    opts: &'a SlugifyOptions,
+    // collected slug (with `max_length`) and its length in `length_unit`
+    joined: String,
+    joined_len: usize,
+    written: bool,
+}
+
+impl<'a, W: Write> Joiner<'a, W> {
+    fn new(writer: W, opts: &'a SlugifyOptions) -> Self {
+        Joiner {
+            writer,
+            opts,
+            joined: String::new(),
+            joined_len: 0,
+            written: false,
+        }
+    }
+
+    fn push(&mut self, text: &str) -> io::Result<()> {
+        let slug = slugify_untruncated(text, self.opts);
+        if slug.is_empty() {
+            return Ok(());
+        }
+        if self.opts.max_length == 0 {
+            if self.written {
+                self.writer.write_all(self.opts.separator.as_bytes())?;
+            }
+            self.written = true;
+            return self.writer.write_all(slug.as_bytes());
+        }
+        if !self.joined.is_empty() {
+            self.joined.push_str(&self.opts.separator);
+            self.joined_len += self.opts.length_unit.measure(&self.opts.separator);
+        }
+        self.joined.push_str(&slug);
+        self.joined_len += self.opts.length_unit.measure(&slug);
+        Ok(())
+    }
+
+    // True once more input cannot change the truncated slug: words are
+    // taken in order and the limit is already exceeded. A truncation hash
+    // covers the whole slug, so it needs every word.
+    fn is_complete(&self) -> bool {
+        let in_order = self.opts.save_order || !self.opts.word_boundary;
+        self.opts.max_length > 0
+            && in_order
+            && !self.opts.truncation_hash
+            && !self.opts.preserve_trailing_number
+            && self.joined_len > self.opts.max_length
+    }
+
+    fn finish(mut self) -> io::Result<()> {
+        if self.opts.max_length > 0 {
+            let slug = truncate_joined(&self.joined, self.opts);
+            self.writer.write_all(slug.as_bytes())?;
+        }
+        self.writer.flush()
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::expect_used)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::slugify::{TruncationStrategy, slugify_with_options_public};
+
+    // Reader returning at most `step` bytes per call, to cut UTF-8
+    // sequences and words at every position.
+    struct Trickle<'a> {
+        data: &'a [u8],
+        step: usize,
+    }
+
+    impl Read for Trickle<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            let n = self.step.min(buf.len()).min(self.data.len());
+            buf[..n].copy_from_slice(&self.data[..n]);
+            self.data = &self.data[n..];
+            Ok(n)
+        }
+    }
+
+    fn stream(text: &str, opts: &SlugifyOptions, chunk: usize, step: usize) -> io::Result<String> {
+        let mut out = Vec::new();
+        let reader = Trickle {
+            data: text.as_bytes(),
+            step,
+        };
+        slugify_stream_chunked(reader, &mut out, opts, chunk)?;
+        Ok(String::from_utf8(out).unwrap())
+    }
+
+    #[test]
+    fn test_stream_matches_whole_text() {
+        let text = "  Ünïcode   déjà vu -- l'été!  C'est   &amp; 10,000 fins  ".repeat(20);
+        for opts in [
+            SlugifyOptions::builder().build().unwrap(),
+            SlugifyOptions::builder()
+                .separator("__")
+                .stopwords(["vu"])
+                .build()
+                .unwrap(),
+        ] {
+            let expected = slugify_with_options_public(&opts, &text);
+            for (chunk, step) in [(4, 1), (7, 3), (16, 5), (CHUNK_BYTES, 4096)] {
+                assert_eq!(stream(&text, &opts, chunk, step).unwrap(), expected);
+            }
+        }
+        assert_eq!(
+            stream("", &SlugifyOptions::builder().build().unwrap(), 4, 1).unwrap(),
+            ""
+        );
+    }
+
+    #[test]
+    fn test_stream_max_length() {
+        let text = "alpha beta gamma delta epsilon ".repeat(50);
+        for save_order in [true, false] {
+            let opts = SlugifyOptions::builder()
+                .max_length(17)
+                .word_boundary(true)
+                .save_order(save_order)
+                .build()
+                .unwrap();
+            let expected = slugify_with_options_public(&opts, &text);
+            assert_eq!(stream(&text, &opts, 8, 3).unwrap(), expected);
+        }
+        // the stream stops once the slug is known
+        let opts = SlugifyOptions::builder().max_length(5).build().unwrap();
+        let mut out = Vec::new();
+        let reader = "hello world ".as_bytes().chain(Trickle {
+            data: b"\xff",
+            step: 1,
+        });
+        slugify_stream_chunked(reader, &mut out, &opts, 4).unwrap();
+        assert_eq!(out, b"hello");
+
+        // the hash covers words past the limit, so inputs sharing a prefix
+        // keep distinct slugs
+        let opts = SlugifyOptions::builder()
+            .max_length(19)
+            .truncation_strategy(TruncationStrategy::CutWithHash)
+            .build()
+            .unwrap();
+        let prefix = "alpha beta gamma delta ".repeat(20);
+        let a = format!("{}epsilon", prefix);
+        let b = format!("{}zeta", prefix);
+        let slug_a = stream(&a, &opts, 8, 3).unwrap();
+        let slug_b = stream(&b, &opts, 8, 3).unwrap();
+        assert_eq!(slug_a, slugify_with_options_public(&opts, &a));
+        assert_eq!(slug_b, slugify_with_options_public(&opts, &b));
+        assert_ne!(slug_a, slug_b);
+    }
+
+    #[test]
+    fn test_stream_errors() {
+        let opts = SlugifyOptions::builder().build().unwrap();
+        // a character split across reads is not an error
+        assert_eq!(
+            stream("caf\u{e9} cr\u{e8}me", &opts, 2, 1).unwrap(),
+            "cafe-creme"
+        );
+        let mut out = Vec::new();
+        let err = slugify_stream(&b"bad \xff bytes"[..], &mut out, &opts).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+
+        let opts = SlugifyOptions::builder()
+            .case_style(CaseStyle::Camel)
+            .build()
+            .unwrap();
+        let err = slugify_stream(&b"a b"[..], &mut out, &opts).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+
+        // anchored or multi-word stages would fire at every chunk edge
+        let text = "Release notes 123456 and more";
+        let opts = SlugifyOptions::builder()
+            .strip_numeric_ids(true)
+            .build()
+            .unwrap();
+        assert_eq!(
+            slugify_with_options_public(&opts, text),
+            "release-notes-123456-and-more"
+        );
+        let err = stream(text, &opts, 16, 4).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+        let opts = SlugifyOptions::builder()
+            .replacements(vec![("notes 1".to_string(), "x".to_string())])
+            .build()
+            .unwrap();
+        let err = stream(text, &opts, 16, 4).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+        let opts = SlugifyOptions::builder()
+            .replacements(vec![("&".to_string(), "and".to_string())])
+            .build()
+            .unwrap();
+        assert_eq!(stream("a & b", &opts, 2, 1).unwrap(), "a-and-b");
+    }
+}
@@ -0,0 +1,136 @@
+// Helpers for appending suffixes (counters, hashes, ...) to a slug stem
+// while keeping the combined result within `max_length`.
+use crate::slugify::{LengthUnit, SlugifyError, smart_truncate_by};
+
+/// Number of characters left for the stem once `separator` and `suffix`
+/// have been reserved out of `max_length`.
+///
+/// A `max_length` of 0 means "no limit" (same convention as
+/// `SlugifyOptions::max_length`) and yields `Ok(0)`. Returns
+/// `SlugifyError::SuffixTooLong` when the limit leaves no room for at least
+/// one stem character, instead of silently cutting into the suffix.
+pub fn stem_budget(max_length: usize, separator: &str, suffix: &str) -> Result<usize, SlugifyError> {
+    stem_budget_by(max_length, LengthUnit::Chars, separator, suffix)
+}
+
+/// `stem_budget` with `max_length` counted in `unit`.
+pub fn stem_budget_by(
+    max_length: usize,
+    unit: LengthUnit,
+    separator: &str,
+    suffix: &str,
+) -> Result<usize, SlugifyError> {
+    if max_length == 0 {
+        return Ok(0);
+    }
+    let reserved = unit.measure(separator) + unit.measure(suffix);
+    if max_length <= reserved {
+        return Err(SlugifyError::SuffixTooLong {
+            max_length,
+            required: reserved + 1,
+        });
+    }
+    Ok(max_length - reserved)
+}
+
+/// Join `stem` and `suffix` with `separator`, shrinking the stem (never the
+/// suffix) so the result fits in `max_length` characters.
+///
+/// The stem is shortened with `smart_truncate`, so `word_boundary` behaves
+/// exactly as it does for regular slugs. An empty suffix returns the
+/// truncated stem without a trailing separator.
+pub fn join_with_suffix(
+    stem: &str,
+    suffix: &str,
+    separator: &str,
+    max_length: usize,
+    word_boundary: bool,
+) -> Result<String, SlugifyError> {
+    join_with_suffix_by(
+        stem,
+        suffix,
+        separator,
+        max_length,
+        LengthUnit::Chars,
+        word_boundary,
+    )
+}
+
+/// `join_with_suffix` with `max_length` counted in `unit`.
+pub fn join_with_suffix_by(
+    stem: &str,
+    suffix: &str,
+    separator: &str,
+    max_length: usize,
+    unit: LengthUnit,
+    word_boundary: bool,
+) -> Result<String, SlugifyError> {
+    if suffix.is_empty() {
+        return Ok(smart_truncate_by(
+            stem,
+            max_length,
+            unit,
+            word_boundary,
+            separator,
+            true,
+        ));
+    }
+    let budget = stem_budget_by(max_length, unit, separator, suffix)?;
+    let stem = smart_truncate_by(stem, budget, unit, word_boundary, separator, true);
+    if stem.is_empty() {
+        return Ok(suffix.to_string());
+    }
+    Ok(format!("{}{}{}", stem, separator, suffix))
+}
+
+#[cfg(test)]
+#[allow(clippy::expect_used)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stem_budget_boundaries() {
+        // "-2" reserves two characters
+        assert_eq!(stem_budget(0, "-", "2").unwrap(), 0);
+        assert_eq!(stem_budget(10, "-", "2").unwrap(), 8);
+        assert_eq!(stem_budget(3, "-", "2").unwrap(), 1);
+        match stem_budget(2, "-", "2") {
+            Err(SlugifyError::SuffixTooLong { max_length, required }) => {
+                assert_eq!(max_length, 2);
+                assert_eq!(required, 3);
+            }
+            other => panic!("expected SuffixTooLong, got {:?}", other),
+        }
+        // multi-char separators count every character
+        assert_eq!(stem_budget(10, "--", "abc").unwrap(), 5);
+        assert!(stem_budget(5, "--", "abc").is_err());
+    }
+
+    #[test]
+    fn test_join_with_suffix_shrinks_stem_only() {
+        assert_eq!(join_with_suffix("my-post", "2", "-", 0, false).unwrap(), "my-post-2");
+        assert_eq!(join_with_suffix("my-post", "2", "-", 9, false).unwrap(), "my-post-2");
+        assert_eq!(join_with_suffix("my-post", "2", "-", 8, false).unwrap(), "my-pos-2");
+        // truncation never leaves a dangling separator before the suffix
+        assert_eq!(join_with_suffix("my-post", "2", "-", 5, false).unwrap(), "my-2");
+        assert_eq!(join_with_suffix("one-two-three", "10", "-", 11, true).unwrap(), "one-two-10");
+        assert_eq!(join_with_suffix("abc", "", "-", 2, false).unwrap(), "ab");
+        assert!(join_with_suffix("my-post", "abcdef", "-", 7, false).is_err());
+    }
+
+    #[test]
+    fn test_join_with_suffix_by_bytes() {
+        let unit = LengthUnit::Bytes;
+        assert_eq!(stem_budget_by(10, unit, "-", "é").unwrap(), 7);
+        // "ü" takes two bytes, so cutting after it leaves "gr"
+        assert_eq!(
+            join_with_suffix_by("grüße", "2", "-", 5, unit, false).unwrap(),
+            "gr-2"
+        );
+        assert_eq!(
+            join_with_suffix_by("grüße", "2", "-", 6, unit, false).unwrap(),
+            "grü-2"
+        );
+    }
+}
@@ -0,0 +1,170 @@
+// Compile-time checks of the built-in tables. Each table is followed by a
+// `const _: () = tables::check_...(..);` item, so a duplicate key, an
+// empty pattern or a replacement that ASCII slugs cannot hold fails the
+// build instead of surfacing as a wrong slug. The compiler error points at
+// the offending table; the panic message names the rule.
+
+/// How keys are compared when looking for duplicates.
+#[derive(Clone, Copy)]
+pub(crate) enum Keys {
+    /// Byte for byte (entity names: "AElig" and "aelig" differ).
+    Exact,
+    /// Ignoring ASCII case, for tables matched case-insensitively.
+    AsciiCaseInsensitive,
+}
+
+/// What replacements may contain.
+#[derive(Clone, Copy)]
+pub(crate) enum Values {
+    /// Only what an ASCII slug keeps as words: ASCII letters and digits,
+    /// plus spaces and `-` between words. May be empty.
+    Slug,
+    /// Anything (decoded entity text).
+    Text,
+}
+
+/// Panic (at compile time when used in a `const` item) unless every key of
+/// `table` is non-empty and unique under `keys` and every replacement
+/// satisfies `values`.
+pub(crate) const fn check_translations(table: &[(&str, &str)], keys: Keys, values: Values) {
+    let mut i = 0;
+    while i < table.len() {
+        let (key, replacement) = table[i];
+        assert!(!key.is_empty(), "translation table has an empty key");
+        let mut j = i + 1;
+        while j < table.len() {
+            assert!(
+                !same_key(key.as_bytes(), table[j].0.as_bytes(), keys),
+                "translation table has a duplicate key"
+            );
+            j += 1;
+        }
+        if let Values::Slug = values {
+            assert!(
+                is_slug_text(replacement.as_bytes()),
+                "translation table replacement has characters ASCII slugs drop"
+            );
+        }
+        i += 1;
+    }
+}
+
+/// `check_translations` for tables keyed by a single character.
+pub(crate) const fn check_symbols(table: &[(char, &str)]) {
+    let mut i = 0;
+    while i < table.len() {
+        let (symbol, word) = table[i];
+        let mut j = i + 1;
+        while j < table.len() {
+            assert!(symbol != table[j].0, "symbol table has a duplicate key");
+            j += 1;
+        }
+        assert!(
+            !word.is_empty() && is_slug_text(word.as_bytes()),
+            "symbol table word is empty or has characters ASCII slugs drop"
+        );
+        i += 1;
+    }
+}
+
+/// Panic unless the keys of `table` are strictly increasing (byte order),
+/// as binary search needs; this also rules out duplicates.
+pub(crate) const fn check_sorted(table: &[(&str, &str)]) {
+    let mut i = 1;
+    while i < table.len() {
+        assert!(
+            is_less(table[i - 1].0.as_bytes(), table[i].0.as_bytes()),
+            "table keys are not sorted"
+        );
+        i += 1;
+    }
+}
+
+const fn same_key(a: &[u8], b: &[u8], keys: Keys) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    let mut i = 0;
+    while i < a.len() {
+        let equal = match keys {
+            Keys::Exact => a[i] == b[i],
+            Keys::AsciiCaseInsensitive => a[i].eq_ignore_ascii_case(&b[i]),
+        };
+        if !equal {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
+const fn is_less(a: &[u8], b: &[u8]) -> bool {
+    let mut i = 0;
+    while i < a.len() && i < b.len() {
+        if a[i] != b[i] {
+            return a[i] < b[i];
+        }
+        i += 1;
+    }
+    a.len() < b.len()
+}
+
+const fn is_slug_text(s: &[u8]) -> bool {
+    let mut i = 0;
+    while i < s.len() {
+        if !(s[i].is_ascii_alphanumeric() || s[i] == b' ' || s[i] == b'-') {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
+#[cfg(test)]
+#[allow(clippy::expect_used)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_valid_tables_pass() {
+        check_translations(&[("ß", "ss"), ("Þ", "Th")], Keys::Exact, Values::Slug);
+        check_translations(&[("AElig", "Æ"), ("aelig", "æ")], Keys::Exact, Values::Text);
+        check_symbols(&[('&', "and"), ('%', "por ciento")]);
+        check_sorted(&[("AElig", "Æ"), ("Aacute", "Á"), ("aelig", "æ")]);
+    }
+
+    #[test]
+    #[should_panic(expected = "duplicate key")]
+    fn test_duplicate_key() {
+        check_translations(
+            &[("Dr.", "doctor"), ("dr.", "drive")],
+            Keys::AsciiCaseInsensitive,
+            Values::Slug,
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "empty key")]
+    fn test_empty_key() {
+        check_translations(&[("", "x")], Keys::Exact, Values::Slug);
+    }
+
+    #[test]
+    #[should_panic(expected = "characters ASCII slugs drop")]
+    fn test_non_ascii_replacement() {
+        check_translations(&[("Ä", "Æ")], Keys::Exact, Values::Slug);
+    }
+
+    #[test]
+    #[should_panic(expected = "duplicate key")]
+    fn test_duplicate_symbol() {
+        check_symbols(&[('&', "and"), ('&', "und")]);
+    }
+
+    #[test]
+    #[should_panic(expected = "not sorted")]
+    fn test_unsorted() {
+        check_sorted(&[("b", "1"), ("a", "2")]);
+    }
+}
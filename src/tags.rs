@@ -0,0 +1,82 @@
+// Split a tag / keyword list ("Rust, Web Dev; CLI") and slugify each entry.
+use std::collections::HashSet;
+
+use crate::slugifier::DEFAULT_OPTIONS;
+use crate::slugify::{SlugifyOptions, slugify_with_options_public};
+
+/// How a tag list is split and post-processed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TagOptions {
+    /// Characters separating tags. Defaults to `,` and `;`.
+    pub delimiters: Vec<char>,
+    /// Sort the tags instead of keeping their first-seen order.
+    pub sort: bool,
+}
+
+impl Default for TagOptions {
+    fn default() -> Self {
+        TagOptions {
+            delimiters: vec![',', ';'],
+            sort: false,
+        }
+    }
+}
+
+/// Slugify each tag of `text` with the builder defaults and `TagOptions`
+/// defaults.
+///
+/// ```
+/// assert_eq!(
+///     slugify_rs::tags::slugify_tags("Rust, Web Dev; CLI, rust"),
+///     ["rust", "web-dev", "cli"]
+/// );
+/// ```
+pub fn slugify_tags(text: &str) -> Vec<String> {
+    slugify_tags_with(text, &DEFAULT_OPTIONS, &TagOptions::default())
+}
+
+/// Split `text` on `tag_opts.delimiters` and slugify every piece with
+/// `opts`. Tags whose slug is empty are dropped and duplicates keep their
+/// first occurrence. Delimiters are split before slugification, so
+/// "1,000" yields the tags "1" and "000" when `,` is a delimiter.
+pub fn slugify_tags_with(
+    text: &str,
+    opts: &SlugifyOptions,
+    tag_opts: &TagOptions,
+) -> Vec<String> {
+    let mut seen = HashSet::new();
+    let mut tags: Vec<String> = text
+        .split(|c| tag_opts.delimiters.contains(&c))
+        .map(|tag| slugify_with_options_public(opts, tag))
+        .filter(|slug| !slug.is_empty() && seen.insert(slug.clone()))
+        .collect();
+    if tag_opts.sort {
+        tags.sort();
+    }
+    tags
+}
+
+#[cfg(test)]
+#[allow(clippy::expect_used)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_slugify_tags_split_dedup_sort() {
+        assert_eq!(
+            slugify_tags("Rust, Web Dev; CLI,, Web-Dev ;"),
+            ["rust", "web-dev", "cli"]
+        );
+        let opts = SlugifyOptions::builder().separator("_").build().unwrap();
+        let tag_opts = TagOptions {
+            delimiters: vec!['|'],
+            sort: true,
+        };
+        assert_eq!(
+            slugify_tags_with("Zebra | Web Dev | 1,000 Ideas", &opts, &tag_opts),
+            ["1000_ideas", "web_dev", "zebra"]
+        );
+        assert!(slugify_tags(" ; , ").is_empty());
+    }
+}
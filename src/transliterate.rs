@@ -0,0 +1,119 @@
+// Pluggable Unicode -> ASCII transliteration backends. `deunicode` is always
+// available; the others are behind cargo features because they pull in
+// larger dependencies and produce different output for CJK and Arabic.
+use std::fmt;
+
+/// Turns (NFKD-normalized) text into ASCII when `allow_unicode` is off.
+///
+/// Implementations must be deterministic; they are shared between threads
+/// by `Slugifier` and `SlugifyOptions`.
+pub trait Transliterator: fmt::Debug + Send + Sync {
+    fn transliterate(&self, s: &str) -> String;
+}
+
+/// The `deunicode` crate (the default): "日本語" -> "Ri Ben Yu ".
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Deunicode;
+
+impl Transliterator for Deunicode {
+    fn transliterate(&self, s: &str) -> String {
+        deunicode::deunicode(s)
+    }
+}
+
+/// The `any_ascii` crate: "日本語" -> "RiBenYu". Requires the `any_ascii`
+/// feature.
+#[cfg(feature = "any_ascii")]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AnyAscii;
+
+#[cfg(feature = "any_ascii")]
+impl Transliterator for AnyAscii {
+    fn transliterate(&self, s: &str) -> String {
+        any_ascii::any_ascii(s)
+    }
+}
+
+/// An ICU4X transform, e.g. `"und-Latn-t-und-cyrl"` (Cyrillic -> Latin)
+/// followed by `deunicode` for whatever the transform leaves non-ASCII.
+/// Requires the `icu` feature.
+///
+/// ICU4X transliterators are not thread-safe, so each thread loads its own
+/// copy on first use.
+#[cfg(feature = "icu")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Icu {
+    id: String,
+}
+
+#[cfg(feature = "icu")]
+thread_local! {
+    static ICU_TRANSFORMS: std::cell::RefCell<
+        std::collections::HashMap<String, icu_experimental::transliterate::Transliterator>,
+    > = std::cell::RefCell::new(std::collections::HashMap::new());
+}
+
+#[cfg(feature = "icu")]
+impl Icu {
+    /// Use the ICU4X transform identified by the BCP-47 `id` from the
+    /// compiled data. Returns `None` for unknown or malformed ids.
+    pub fn new(id: &str) -> Option<Self> {
+        let icu = Icu { id: id.to_string() };
+        icu.with_transform(|_| ())?;
+        Some(icu)
+    }
+
+    fn with_transform<R>(
+        &self,
+        f: impl FnOnce(&icu_experimental::transliterate::Transliterator) -> R,
+    ) -> Option<R> {
+        ICU_TRANSFORMS.with(|cache| {
+            let mut cache = cache.borrow_mut();
+            if !cache.contains_key(&self.id) {
+                let locale = self.id.parse().ok()?;
+                let t = icu_experimental::transliterate::Transliterator::try_new(&locale).ok()?;
+                cache.insert(self.id.clone(), t);
+            }
+            cache.get(&self.id).map(f)
+        })
+    }
+}
+
+#[cfg(feature = "icu")]
+impl Transliterator for Icu {
+    fn transliterate(&self, s: &str) -> String {
+        use unicode_normalization::UnicodeNormalization;
+        // ICU transforms expect composed text
+        let composed: String = s.nfc().collect();
+        let out = self
+            .with_transform(|t| t.transliterate(composed.clone()))
+            .unwrap_or(composed);
+        deunicode::deunicode(&out)
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::expect_used)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deunicode_backend() {
+        assert_eq!(Deunicode.transliterate("Компьютер"), "Komp'iuter");
+    }
+
+    #[cfg(feature = "any_ascii")]
+    #[test]
+    fn test_any_ascii_backend() {
+        assert_eq!(AnyAscii.transliterate("日本語"), "RiBenYu");
+    }
+
+    #[cfg(feature = "icu")]
+    #[test]
+    fn test_icu_backend() {
+        let icu = Icu::new("und-Latn-t-und-cyrl").unwrap();
+        assert_eq!(icu.transliterate("Компьютер"), "Komp'uter");
+        assert!(Icu::new("not a locale").is_none());
+    }
+}
@@ -0,0 +1,262 @@
+// Golden tests: a fixed corpus run through a matrix of option combinations,
+// stored as insta snapshots in `tests/snapshots/`. A change in behavior
+// shows up as a snapshot diff to review (`cargo insta review`, or
+// `INSTA_UPDATE=always cargo test --test options_matrix` and `git diff`)
+// instead of passing silently.
+//
+// The snapshots assume the default features: `emoji_names` and
+// `html5_entities` change how emoji and named entities come out.
+#![cfg(all(feature = "emoji_names", feature = "html5_entities"))]
+#![allow(clippy::expect_used)]
+#![allow(clippy::unwrap_used)]
+
+use std::fmt::Write;
+
+use slugify_rs::slugify::{
+    CaseStyle, CjkQuotes, Compat, DecodedSeparators, DigitBoundaries, EmojiPolicy, IdentifierStyle,
+    LengthUnit, RtlWordOrder, SeparatorScope, SlugifyOptions, SlugifyOptionsBuilder, TrimSet,
+    TruncationStrategy, WordBreaks, WordSegmentation, slugify_with_options_public,
+};
+use slugify_rs::special::Locale;
+
+const CORPUS: &[&str] = &[
+    "Hello World",
+    "C'est déjà l'été.",
+    "1,000 reasons you are #1",
+    "Компьютер и программы",
+    "日本語のテキスト「引用」",
+    "مرحبا بالعالم",
+    "Straße & Grüße",
+    "i love 🦄 & 🚀",
+    "foo &amp; bar &#381; &#x17D;&#32;baz",
+    "  --Leading and trailing--  ",
+    "HTMLParser in camelCase2024",
+    "Rock/Pop | Jazz · Blues",
+    "v1.2_beta-release",
+    "The quick brown fox jumps over the lazy dog",
+    "Annual Report of the Year 2024",
+];
+
+// Every corpus text slugified under each named variant.
+fn render(variants: Vec<(String, SlugifyOptionsBuilder)>) -> String {
+    let mut out = String::new();
+    for (name, builder) in variants {
+        let opts = builder
+            .build()
+            .unwrap_or_else(|e| panic!("variant {name}: {e:?}"));
+        writeln!(out, "## {name}").unwrap();
+        for text in CORPUS {
+            let slug = slugify_with_options_public(&opts, text);
+            writeln!(out, "{text:?} -> {slug:?}").unwrap();
+        }
+        out.push('\n');
+    }
+    out
+}
+
+fn variant(name: &str, builder: SlugifyOptionsBuilder) -> (String, SlugifyOptionsBuilder) {
+    (name.to_string(), builder)
+}
+
+fn defaults() -> SlugifyOptionsBuilder {
+    SlugifyOptions::builder()
+}
+
+#[test]
+fn core() {
+    let mut variants = Vec::new();
+    for allow_unicode in [false, true] {
+        for lowercase in [true, false] {
+            for separator in ["-", "_", "ZZ"] {
+                for max_length in [0, 16] {
+                    let name = format!(
+                        "allow_unicode={allow_unicode} lowercase={lowercase} \
+                         separator={separator:?} max_length={max_length}"
+                    );
+                    let builder = defaults()
+                        .allow_unicode(allow_unicode)
+                        .lowercase(lowercase)
+                        .separator(separator)
+                        .max_length(max_length);
+                    variants.push((name, builder));
+                }
+            }
+        }
+    }
+    insta::assert_snapshot!(render(variants));
+}
+
+#[test]
+fn truncation() {
+    let limited = || defaults().max_length(16);
+    let variants = vec![
+        variant("word_boundary", limited().word_boundary(true)),
+        variant(
+            "word_boundary, any order",
+            limited().word_boundary(true).save_order(false),
+        ),
+        variant(
+            "preserve_trailing_number",
+            limited().preserve_trailing_number(true),
+        ),
+        variant(
+            "truncation_strategy=CutWithHash",
+            limited().truncation_strategy(TruncationStrategy::CutWithHash),
+        ),
+        variant("separator=\"__\"", limited().separator("__")),
+        variant(
+            "separator=\"__\" strict_max_length",
+            limited().separator("__").strict_max_length(true),
+        ),
+        variant(
+            "allow_unicode length_unit=Bytes",
+            limited().allow_unicode(true).length_unit(LengthUnit::Bytes),
+        ),
+        variant(
+            "allow_unicode word_boundary word_breaks=Unicode",
+            limited()
+                .allow_unicode(true)
+                .word_boundary(true)
+                .word_breaks(WordBreaks::Unicode),
+        ),
+        variant(
+            "allow_unicode length_unit=Graphemes",
+            limited()
+                .allow_unicode(true)
+                .length_unit(LengthUnit::Graphemes),
+        ),
+    ];
+    insta::assert_snapshot!(render(variants));
+}
+
+#[test]
+fn emoji() {
+    let mut variants = Vec::new();
+    for allow_unicode in [false, true] {
+        for policy in [
+            EmojiPolicy::Remove,
+            EmojiPolicy::Transliterate,
+            EmojiPolicy::Shortcode,
+            EmojiPolicy::Keep,
+        ] {
+            let name = format!("allow_unicode={allow_unicode} emoji_policy={policy:?}");
+            let builder = defaults().allow_unicode(allow_unicode).emoji_policy(policy);
+            variants.push((name, builder));
+        }
+    }
+    insta::assert_snapshot!(render(variants));
+}
+
+#[test]
+fn references() {
+    let variants = vec![
+        variant("entities=false", defaults().entities(false)),
+        variant(
+            "decimal=false hexadecimal=false",
+            defaults().decimal(false).hexadecimal(false),
+        ),
+        variant(
+            "decoded_separators=Strip",
+            defaults().decoded_separators(DecodedSeparators::Strip),
+        ),
+    ];
+    insta::assert_snapshot!(render(variants));
+}
+
+#[test]
+fn words() {
+    let variants = vec![
+        variant("stopwords", defaults().stopwords(["the", "and", "of"])),
+        variant(
+            "expand_abbreviations",
+            defaults().expand_abbreviations(true),
+        ),
+        variant("split_camel_case", defaults().split_camel_case(true)),
+        variant(
+            "digit_boundaries=Both",
+            defaults().digit_boundaries(DigitBoundaries::Both),
+        ),
+        variant(
+            "symbol_locale=De",
+            defaults().symbol_locale(Some(Locale::De)),
+        ),
+        variant(
+            "symbol_locale=Fr",
+            defaults().symbol_locale(Some(Locale::Fr)),
+        ),
+    ];
+    insta::assert_snapshot!(render(variants));
+}
+
+#[test]
+fn characters() {
+    let unicode = || defaults().allow_unicode(true);
+    let variants = vec![
+        variant(
+            "regex_pattern",
+            defaults().regex_pattern(Some(r"[^-a-z0-9._]+")),
+        ),
+        variant("allowed_chars", defaults().allowed_chars(Some("-a-z0-9._"))),
+        variant(
+            "allowed_extra_chars=\"._\"",
+            defaults().allowed_extra_chars("._"),
+        ),
+        variant(
+            "regex_pattern separator=\"_\" separator_scope=Inserted",
+            defaults()
+                .regex_pattern(Some(r"[^-a-z0-9_]+"))
+                .separator("_")
+                .separator_scope(SeparatorScope::Inserted),
+        ),
+        variant(
+            "regex_pattern edge_trim=NonAlphanumeric",
+            defaults()
+                .regex_pattern(Some(r"[^-a-z0-9._]+"))
+                .edge_trim(TrimSet::NonAlphanumeric),
+        ),
+        variant(
+            "allow_unicode word_segmentation=Unicode",
+            unicode().word_segmentation(WordSegmentation::Unicode),
+        ),
+        variant(
+            "allow_unicode cjk_quotes=Remove",
+            unicode().cjk_quotes(CjkQuotes::Remove),
+        ),
+        variant(
+            "allow_unicode rtl_word_order=Visual",
+            unicode().rtl_word_order(RtlWordOrder::Visual),
+        ),
+    ];
+    insta::assert_snapshot!(render(variants));
+}
+
+#[test]
+fn styles() {
+    let variants = vec![
+        variant("case_style=Camel", defaults().case_style(CaseStyle::Camel)),
+        variant(
+            "case_style=Pascal",
+            defaults().case_style(CaseStyle::Pascal),
+        ),
+        variant("case_style=Snake", defaults().case_style(CaseStyle::Snake)),
+        variant(
+            "case_style=ScreamingSnake",
+            defaults().case_style(CaseStyle::ScreamingSnake),
+        ),
+        variant(
+            "identifier=rust",
+            defaults().identifier(IdentifierStyle::rust()),
+        ),
+        variant(
+            "identifier=python",
+            defaults().identifier(IdentifierStyle::python()),
+        ),
+        variant(
+            "allow_unicode idna",
+            defaults().allow_unicode(true).idna(true),
+        ),
+        variant("compat=GitHub", defaults().compat(Compat::GitHub)),
+        variant("compat=Django", defaults().compat(Compat::Django)),
+    ];
+    insta::assert_snapshot!(render(variants));
+}
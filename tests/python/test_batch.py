@@ -0,0 +1,44 @@
+import pytest
+
+try:
+    import python_slugify_pi as slugify_rs
+except Exception as e:
+    pytest.skip(f"Rust binding not available: {e}", allow_module_level=True)
+
+
+def test_batch_accepts_any_iterable():
+    titles = (f"Post {i}" for i in range(3))
+    assert slugify_rs.slugify_batch(titles) == ["post-0", "post-1", "post-2"]
+    assert slugify_rs.slugify_batch(("A B",), separator="_") == ["a_b"]
+    assert slugify_rs.slugify_batch([]) == []
+
+
+def test_batch_lazy_iterator_consumes_in_chunks():
+    pulled = []
+
+    def source():
+        for i in range(5):
+            pulled.append(i)
+            yield f"Post {i}"
+
+    it = slugify_rs.slugify_batch(source(), lazy=True, chunk_size=2)
+    assert pulled == []
+    assert next(it) == "post-0"
+    assert pulled == [0, 1]
+    assert list(it) == ["post-1", "post-2", "post-3", "post-4"]
+
+
+def test_batch_errors():
+    with pytest.raises(TypeError):
+        slugify_rs.slugify_batch([1])
+    with pytest.raises(ValueError):
+        slugify_rs.slugify_batch([], chunk_size=0)
+
+
+def test_many_releases_the_gil():
+    titles = [f"Post {i}" for i in range(1000)]
+    assert slugify_rs.slugify_many(titles) == [f"post-{i}" for i in range(1000)]
+    assert slugify_rs.slugify_many(["A B"], parallel=False, separator="_") == ["a_b"]
+    assert slugify_rs.slugify_many([]) == []
+    with pytest.raises(TypeError):
+        slugify_rs.slugify_many(["a"], bogus=True)
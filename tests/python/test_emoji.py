@@ -0,0 +1,25 @@
+import pytest
+
+try:
+    import python_slugify_pi as slugify_rs
+except Exception as e:
+    pytest.skip(f"Rust binding not available: {e}", allow_module_level=True)
+
+
+def test_transliterate_icons_flag():
+    assert slugify_rs.slugify("I ♥ 🚀") == "i-hearts-rocket"
+    assert slugify_rs.slugify("I ♥ 🚀", transliterate_icons=False) == "i"
+
+
+def test_emoji_policy_names():
+    assert slugify_rs.slugify("I ♥ 🚀", emoji="transliterate") == "i-hearts-rocket"
+    assert slugify_rs.slugify("I ♥ 🚀", emoji="remove") == "i"
+    assert slugify_rs.slugify("I ♥ 🚀", emoji="keep", allow_unicode=True) == "i-♥-🚀"
+    with slugify_rs.options(emoji="remove"):
+        assert slugify_rs.slugify("I ♥ 🚀") == "i"
+        # either argument replaces the scope's emoji setting
+        assert slugify_rs.slugify("I ♥ 🚀", transliterate_icons=True) == "i-hearts-rocket"
+    with pytest.raises(ValueError):
+        slugify_rs.slugify("I ♥ 🚀", emoji="explode")
+    with pytest.raises(ValueError):
+        slugify_rs.slugify("I ♥ 🚀", emoji="keep", transliterate_icons=True)
@@ -0,0 +1,35 @@
+import threading
+
+import pytest
+
+try:
+    import python_slugify_pi as slugify_rs
+except Exception as e:
+    pytest.skip(f"Rust binding not available: {e}", allow_module_level=True)
+
+
+def test_options_scope_nests_and_restores():
+    assert slugify_rs.slugify("Hello World") == "hello-world"
+    with slugify_rs.options(separator="_", max_length=60):
+        assert slugify_rs.slugify("Hello World") == "hello_world"
+        with slugify_rs.options(lowercase=False):
+            # inner scope inherits the outer separator
+            assert slugify_rs.slugify("Hello World") == "Hello_World"
+        assert slugify_rs.slugify("Hello World") == "hello_world"
+        # explicit arguments win over the scope
+        assert slugify_rs.slugify("Hello World", separator=".") == "hello.world"
+    assert slugify_rs.slugify("Hello World") == "hello-world"
+
+
+def test_options_scope_restored_on_exception_and_thread_local():
+    with pytest.raises(RuntimeError):
+        with slugify_rs.options(separator="_"):
+            raise RuntimeError("boom")
+    assert slugify_rs.slugify("a b") == "a-b"
+
+    seen = []
+    with slugify_rs.options(separator="_"):
+        t = threading.Thread(target=lambda: seen.append(slugify_rs.slugify("a b")))
+        t.start()
+        t.join()
+    assert seen == ["a-b"]
@@ -0,0 +1,52 @@
+import pytest
+
+try:
+    import python_slugify_pi as slugify_rs
+except Exception as e:
+    pytest.skip(f"Rust binding not available: {e}", allow_module_level=True)
+
+
+def test_registry_consults_exists_callable():
+    taken = {"my-post", "my-post-2"}
+    reg = slugify_rs.SlugRegistry(exists=lambda slug: slug in taken)
+    assert reg.slugify("My Post") == "my-post-3"
+    assert reg.slugify("My Post") == "my-post-4"
+    assert "my-post-3" in reg
+    assert len(reg) == 2
+    assert reg.release("my-post-3")
+    assert reg.reserve("fresh")
+    assert reg.slugify("Fresh") == "fresh-2"
+
+
+def test_registry_strategy_and_options():
+    reg = slugify_rs.SlugRegistry(strategy="hash", suffix_length=4, separator="_")
+    assert reg.slugify("A B") == "a_b"
+    assert len(reg.slugify("A B")) == len("a_b_") + 4
+    with pytest.raises(ValueError):
+        slugify_rs.SlugRegistry(strategy="nope")
+    with pytest.raises(TypeError):
+        slugify_rs.SlugRegistry(bogus=1)
+
+
+def test_unique_slug_propagates_errors():
+    assert slugify_rs.unique_slug("My Post", {"my-post"}.__contains__) == "my-post-2"
+
+    def down(slug):
+        raise ConnectionError("db down")
+
+    with pytest.raises(ConnectionError):
+        slugify_rs.unique_slug("x", down)
+    with pytest.raises(ValueError):
+        slugify_rs.unique_slug("ab", lambda slug: True, max_length=2)
+
+
+def test_salted_hash_differs_per_tenant():
+    def second(salt):
+        reg = slugify_rs.SlugRegistry(strategy="hash", salt=salt)
+        reg.slugify("My Post")
+        return reg.slugify("My Post")
+
+    assert second("acme") == second("acme")
+    assert second("acme") != second("globex")
+    with pytest.raises(ValueError):
+        slugify_rs.SlugRegistry(salt="acme")